@@ -0,0 +1,116 @@
+//! Benchmarks for the fill hot path: filtering, aggregation, planning, and encoding,
+//! over synthetic books of Orders.
+//!
+//! Run with `cargo bench --bench fill_path`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use alloy::{
+    eips::Encodable2718,
+    network::{EthereumWallet, TransactionBuilder},
+    primitives::{Address, Bytes},
+    rpc::types::TransactionRequest,
+};
+use common::{
+    DEADLINE, constants, ru_to_host_order, ru_to_ru_order, sign_fills, sign_order, signer,
+};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use orders::{filler::plan::rollup_txn_requests, validate::validate_order};
+use signet_types::{AggregateOrders, SignedOrder, UnsignedFill};
+use std::hint::black_box;
+use tokio::runtime::Runtime;
+
+/// Sizes of the synthetic books.
+const BOOK_SIZES: [usize; 2] = [1_000, 10_000];
+
+/// A time before [`DEADLINE`], so all Orders in the book are valid.
+const NOW: u64 = DEADLINE - 60;
+
+/// Build a synthetic book of signed Orders, alternating RU to Host and RU to RU Orders with
+/// distinct recipients, so aggregation sees many Outputs per token.
+async fn book(size: usize) -> Vec<SignedOrder> {
+    let constants = constants();
+    let mut orders = Vec::with_capacity(size);
+    for i in 0..size {
+        let recipient = Address::with_last_byte((i % 256) as u8);
+        let unsigned = if i % 2 == 0 {
+            ru_to_host_order(&constants, recipient)
+        } else {
+            ru_to_ru_order(&constants, recipient)
+        };
+        orders.push(sign_order(&constants, unsigned, i as u64).await);
+    }
+    orders
+}
+
+fn fill_path(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let constants = constants();
+    let filler = signer().address();
+    let wallet = EthereumWallet::from(signer());
+
+    for size in BOOK_SIZES {
+        let orders = rt.block_on(book(size));
+        let signed_fills = rt.block_on(sign_fills(&constants, &orders, 1));
+        let tx_requests = rollup_txn_requests(&constants, filler, &signed_fills, &orders);
+
+        let mut group = c.benchmark_group("fill_path");
+        group.throughput(Throughput::Elements(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("filter", size), &orders, |b, orders| {
+            b.iter(|| {
+                orders
+                    .iter()
+                    .filter(|order| validate_order(order, &constants, NOW).is_ok())
+                    .count()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("aggregate", size), &orders, |b, orders| {
+            b.iter(|| {
+                let agg: AggregateOrders = black_box(orders).iter().collect();
+                black_box(UnsignedFill::from(&agg));
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("plan", size), &orders, |b, orders| {
+            b.iter(|| rollup_txn_requests(&constants, filler, &signed_fills, black_box(orders)))
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("encode", size),
+            &tx_requests,
+            |b, tx_requests| {
+                b.to_async(&rt)
+                    .iter(|| encode(&wallet, constants.rollup().chain_id(), tx_requests))
+            },
+        );
+
+        group.finish();
+    }
+}
+
+/// Sign and encode the transactions as EIP-1559 transactions, with fixed nonces and fees.
+async fn encode(
+    wallet: &EthereumWallet,
+    chain_id: u64,
+    tx_requests: &[TransactionRequest],
+) -> Vec<Bytes> {
+    let mut encoded = Vec::with_capacity(tx_requests.len());
+    for (nonce, tx) in tx_requests.iter().enumerate() {
+        let tx = tx
+            .clone()
+            .with_chain_id(chain_id)
+            .with_nonce(nonce as u64)
+            .with_gas_limit(1_000_000)
+            .with_max_fee_per_gas(2_000_000_000)
+            .with_max_priority_fee_per_gas(1_000_000_000);
+        let envelope = tx.build(wallet).await.unwrap();
+        encoded.push(Bytes::from(envelope.encoded_2718()));
+    }
+    encoded
+}
+
+criterion_group!(benches, fill_path);
+criterion_main!(benches);
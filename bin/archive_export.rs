@@ -0,0 +1,41 @@
+//! Decrypt a Filler's submission archive and print exactly what was submitted.
+//!
+//! The archive key is read from `ARCHIVE_KEY`, as configured for the Filler. Submissions are
+//! printed one JSON object per line, oldest first.
+
+use alloy::hex;
+use clap::Parser;
+use eyre::WrapErr;
+use orders::archive::SubmissionArchive;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+struct ArchiveExportArgs {
+    /// The archive directory.
+    dir: PathBuf,
+    /// Only print submissions to this Bundle.
+    #[arg(long)]
+    bundle_id: Option<String>,
+}
+
+fn main() -> eyre::Result<()> {
+    let args = ArchiveExportArgs::parse();
+    let key = std::env::var("ARCHIVE_KEY").wrap_err("ARCHIVE_KEY is not set")?;
+    let archive = SubmissionArchive::open(&args.dir, &hex::decode(key.trim())?)?;
+
+    let mut printed = 0;
+    for path in archive.list()? {
+        let submission = archive.read(&path)?;
+        if args
+            .bundle_id
+            .as_ref()
+            .is_some_and(|bundle_id| *bundle_id != submission.bundle_id.to_string())
+        {
+            continue;
+        }
+        println!("{}", serde_json::to_string(&submission)?);
+        printed += 1;
+    }
+    eprintln!("exported {printed} submissions");
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! Export the transaction cache's resting Orders as level-2 market data snapshots.
+//!
+//! Each snapshot is printed as one JSON line, so external pricing systems can ingest Signet
+//! order flow without speaking Signet types.
+
+use clap::Parser;
+use init4_bin_base::utils::{from_env::FromEnv, tracing::init_tracing};
+use orders::{
+    analytics::BookAnalytics, filler::FillerConfig, runtime::RuntimeConfig, tx_cache::TxCacheError,
+};
+use signet_tx_cache::client::TxCache;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+struct BookExportArgs {
+    /// Export a new snapshot on this interval, in milliseconds, instead of exporting once.
+    #[arg(long)]
+    interval_ms: Option<u64>,
+}
+
+fn main() -> eyre::Result<()> {
+    RuntimeConfig::from_env()?.build()?.block_on(run())
+}
+
+async fn run() -> eyre::Result<()> {
+    init_tracing();
+
+    let args = BookExportArgs::parse();
+    let config = FillerConfig::from_env()?;
+    let constants = config.constants()?;
+
+    let tx_cache_url: reqwest::Url = constants.environment().transaction_cache().parse()?;
+    let client = reqwest::ClientBuilder::new().use_rustls_tls().build()?;
+    let tx_cache = TxCache::new_with_client(tx_cache_url, client);
+
+    let mut interval = args
+        .interval_ms
+        .map(|interval_ms| tokio::time::interval(Duration::from_millis(interval_ms)));
+    loop {
+        if let Some(interval) = &mut interval {
+            interval.tick().await;
+        }
+
+        let orders = tx_cache
+            .get_orders()
+            .await
+            .map_err(|err| TxCacheError::classify("get_orders", err))?;
+        let timestamp = chrono::Utc::now().timestamp() as u64;
+        let book = BookAnalytics::from_orders(&orders, timestamp);
+        for snapshot in book.market_snapshots(constants.rollup().chain_id()) {
+            println!("{}", serde_json::to_string(&snapshot)?);
+        }
+
+        if interval.is_none() {
+            return Ok(());
+        }
+    }
+}
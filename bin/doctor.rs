@@ -0,0 +1,215 @@
+//! Pre-flight self-test for Filler operators.
+//!
+//! Checks RPC connectivity, transaction cache reachability, signer balances and Permit2
+//! allowances on both chains, clock skew, and constants consistency, then prints a pass/fail
+//! report. Exits with an error if any check fails.
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+    signers::Signer,
+};
+use clap::Parser;
+use init4_bin_base::utils::{from_env::FromEnv, tracing::init_tracing};
+use orders::{
+    clock::{MAX_CLOCK_SKEW_SECS, clock_skew},
+    contracts::{IERC20, PERMIT2_ADDRESS},
+    filler::{Filler, FillerConfig},
+    output::{OutputFormat, Render},
+    provider::{TxSenderProvider, connect_provider, verify_chain},
+};
+use serde::Serialize;
+
+#[derive(Parser, Debug)]
+struct DoctorArgs {
+    /// How to print the report. In quiet mode, only failed checks are printed.
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+/// The outcome of a single check.
+#[derive(Debug, Serialize)]
+struct Check {
+    name: String,
+    passed: bool,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, result: eyre::Result<String>) -> Self {
+        let (passed, detail) = match result {
+            Ok(detail) => (true, detail),
+            Err(err) => (false, format!("{err:#}")),
+        };
+        Self {
+            name: name.into(),
+            passed,
+            detail,
+        }
+    }
+
+    const fn passed(&self) -> bool {
+        self.passed
+    }
+}
+
+impl Render for Check {
+    fn id(&self) -> String {
+        self.name.clone()
+    }
+
+    fn table_row(&self) -> String {
+        let status = if self.passed { "PASS" } else { "FAIL" };
+        format!("[{status}] {}: {}", self.name, self.detail)
+    }
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    init_tracing();
+
+    let args = DoctorArgs::parse();
+    let config = FillerConfig::from_env()?;
+    config.validate()?;
+
+    let mut signer = config.signer_config.connect().await?;
+    // ensure signer chain ID is unset so it can be used for Host and Rollup
+    signer.set_chain_id(None);
+    let address = signer.address();
+
+    let ru_provider = connect_provider(
+        signer.clone(),
+        config.ru_rpc_url.clone(),
+        config.ru_rpc_rate_limit(),
+        config.rpc_metrics("rollup"),
+    )
+    .await?;
+    let host_provider = connect_provider(
+        signer.clone(),
+        config.host_rpc_url.clone(),
+        config.host_rpc_rate_limit(),
+        config.rpc_metrics("host"),
+    )
+    .await?;
+
+    let constants = config.constants()?;
+    let mut checks = Vec::new();
+
+    for (chain, provider, chain_id, orders, weth) in [
+        (
+            "rollup",
+            &ru_provider,
+            constants.rollup().chain_id(),
+            constants.rollup().orders(),
+            constants.rollup().tokens().weth(),
+        ),
+        (
+            "host",
+            &host_provider,
+            constants.host().chain_id(),
+            constants.host().orders(),
+            constants.host().tokens().weth(),
+        ),
+    ] {
+        checks.push(Check::new(
+            format!("{chain} rpc connectivity"),
+            provider
+                .get_block_number()
+                .await
+                .map(|number| format!("latest block {number}"))
+                .map_err(Into::into),
+        ));
+        checks.push(Check::new(
+            format!("{chain} constants"),
+            verify_chain(provider, chain_id, orders)
+                .await
+                .map(|code_hash| {
+                    format!("chain id {chain_id}, Orders contract {orders} (code hash {code_hash})")
+                }),
+        ));
+        checks.push(Check::new(
+            format!("{chain} clock skew"),
+            check_clock_skew(provider).await,
+        ));
+        checks.push(Check::new(
+            format!("{chain} signer balance"),
+            check_native_balance(provider, address).await,
+        ));
+        checks.push(Check::new(
+            format!("{chain} WETH Permit2 allowance"),
+            check_allowance(provider, weth, address).await,
+        ));
+    }
+
+    let filler = Filler::new(signer, ru_provider, host_provider, constants)?;
+    checks.push(Check::new(
+        "transaction cache",
+        filler
+            .get_orders()
+            .await
+            .map(|orders| format!("{} orders in cache", orders.len())),
+    ));
+
+    let total = checks.len();
+    let failed = checks.iter().filter(|check| !check.passed()).count();
+    match args.output {
+        OutputFormat::Table => {
+            println!("signer: {address}");
+            args.output.print(&checks)?;
+        }
+        OutputFormat::Json => args.output.print(&checks)?,
+        OutputFormat::Quiet => {
+            checks.retain(|check| !check.passed());
+            args.output.print(&checks)?;
+        }
+    }
+
+    if failed > 0 {
+        eyre::bail!("{failed} of {total} checks failed");
+    }
+
+    if args.output == OutputFormat::Table {
+        println!("all {total} checks passed");
+    }
+    Ok(())
+}
+
+/// Compare the latest block timestamp against the local clock.
+async fn check_clock_skew(provider: &TxSenderProvider) -> eyre::Result<String> {
+    let skew = clock_skew(provider).await?;
+    if skew.unsigned_abs() > MAX_CLOCK_SKEW_SECS {
+        eyre::bail!("local clock is {skew}s ahead of the latest block");
+    }
+
+    Ok(format!("{skew}s"))
+}
+
+/// Ensure the signer holds native gas tokens.
+async fn check_native_balance(
+    provider: &TxSenderProvider,
+    address: Address,
+) -> eyre::Result<String> {
+    let balance = provider.get_balance(address).await?;
+    if balance.is_zero() {
+        eyre::bail!("signer has no gas tokens");
+    }
+
+    Ok(format!("{balance} wei"))
+}
+
+/// Ensure the signer has approved Permit2 to spend the given token.
+async fn check_allowance(
+    provider: &TxSenderProvider,
+    token: Address,
+    owner: Address,
+) -> eyre::Result<String> {
+    let allowance: U256 = IERC20::new(token, provider)
+        .allowance(owner, PERMIT2_ADDRESS)
+        .call()
+        .await?;
+    if allowance.is_zero() {
+        eyre::bail!("Permit2 is not approved to spend token {token}");
+    }
+
+    Ok(format!("{allowance}"))
+}
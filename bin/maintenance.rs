@@ -0,0 +1,78 @@
+//! Start and end maintenance windows, during which a Filler initiates no new fills.
+//!
+//! Edits the maintenance file named by the Filler's `MAINTENANCE_FILE`, which the Filler checks
+//! before every fill. Windows are time-boxed: fills resume when a window ends, even if it is
+//! never ended explicitly. Windows are timed in Rollup chain time, the clock the Filler checks
+//! them against.
+
+use alloy::signers::Signer;
+use clap::{Parser, Subcommand};
+use init4_bin_base::utils::{from_env::FromEnv, tracing::init_tracing};
+use orders::{
+    clock::chain_time, filler::FillerConfig, maintenance::MaintenanceWindow,
+    provider::connect_provider,
+};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+struct MaintenanceArgs {
+    /// The maintenance file.
+    #[arg(long, default_value = "maintenance.json")]
+    file: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show the current maintenance window, if any.
+    Status,
+    /// Start a maintenance window now, replacing any current window.
+    Start {
+        /// How long the window lasts, in minutes.
+        #[arg(long)]
+        minutes: u64,
+        /// Why fills are stopped, e.g. a deploy or chain upgrade.
+        #[arg(long)]
+        reason: String,
+    },
+    /// End the current maintenance window, resuming fills.
+    End,
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    init_tracing();
+
+    let args = MaintenanceArgs::parse();
+    let config = FillerConfig::from_env()?;
+    config.validate()?;
+
+    let mut signer = config.signer_config.connect().await?;
+    signer.set_chain_id(None);
+    let ru_provider = connect_provider(
+        signer,
+        config.ru_rpc_url.clone(),
+        config.ru_rpc_rate_limit(),
+        config.rpc_metrics("rollup"),
+    )
+    .await?;
+    let now = chain_time(&ru_provider).await?;
+
+    match args.command {
+        Command::Status => {}
+        Command::Start { minutes, reason } => MaintenanceWindow {
+            reason,
+            started_at: now,
+            until: now.saturating_add(minutes.saturating_mul(60)),
+        }
+        .save(&args.file)?,
+        Command::End => MaintenanceWindow::clear(&args.file)?,
+    }
+
+    match MaintenanceWindow::load(&args.file)?.filter(|window| window.active(now)) {
+        Some(window) => println!("{}", serde_json::to_string_pretty(&window)?),
+        None => println!("not in maintenance"),
+    }
+    Ok(())
+}
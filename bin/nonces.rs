@@ -0,0 +1,119 @@
+//! Inspect Permit2 nonce usage for the signer's outstanding Orders.
+//!
+//! Outstanding Orders spread across many Permit2 nonce words make cancellation expensive, since
+//! each word is invalidated in a separate transaction.
+
+use alloy::{primitives::U256, signers::Signer};
+use clap::{Parser, Subcommand};
+use init4_bin_base::utils::{from_env::FromEnv, tracing::init_tracing};
+use orders::{
+    filler::{Filler, FillerConfig},
+    nonces::NonceStatus,
+    output::{OutputFormat, Render},
+    provider::connect_provider,
+};
+use serde::Serialize;
+
+#[derive(Parser, Debug)]
+struct NoncesArgs {
+    /// How to print the nonce words.
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show Permit2 nonce usage for the words holding the signer's outstanding Orders.
+    Status {
+        /// Warn when cancelling outstanding Orders would take more than this many
+        /// transactions on a chain.
+        #[arg(long, default_value_t = 4)]
+        max_cancellation_txs: usize,
+    },
+}
+
+/// Usage of one Permit2 nonce word on one chain.
+#[derive(Debug, Serialize)]
+struct NonceRow {
+    chain: &'static str,
+    word_pos: U256,
+    used: u32,
+    outstanding: usize,
+}
+
+impl Render for NonceRow {
+    fn id(&self) -> String {
+        format!("{}:{}", self.chain, self.word_pos)
+    }
+
+    fn table_row(&self) -> String {
+        format!(
+            "{} word={} used={}/256 outstanding={}",
+            self.chain, self.word_pos, self.used, self.outstanding
+        )
+    }
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    init_tracing();
+
+    let args = NoncesArgs::parse();
+    let config = FillerConfig::from_env()?;
+    config.validate()?;
+
+    let mut signer = config.signer_config.connect().await?;
+    // ensure signer chain ID is unset so it can be used for Host and Rollup
+    signer.set_chain_id(None);
+    let owner = signer.address();
+
+    let ru_provider = connect_provider(
+        signer.clone(),
+        config.ru_rpc_url.clone(),
+        config.ru_rpc_rate_limit(),
+        config.rpc_metrics("rollup"),
+    )
+    .await?;
+    let host_provider = connect_provider(
+        signer.clone(),
+        config.host_rpc_url.clone(),
+        config.host_rpc_rate_limit(),
+        config.rpc_metrics("host"),
+    )
+    .await?;
+
+    let Command::Status {
+        max_cancellation_txs,
+    } = args.command;
+
+    // the signer's Orders still served by the transaction cache
+    let filler = Filler::new(
+        signer,
+        ru_provider.clone(),
+        host_provider.clone(),
+        config.constants()?,
+    )?;
+    let nonces: Vec<U256> = filler
+        .get_orders()
+        .await?
+        .iter()
+        .filter(|order| order.permit.owner == owner)
+        .map(|order| order.permit.permit.nonce)
+        .collect();
+
+    let mut rows = Vec::new();
+    for (chain, provider) in [("rollup", &ru_provider), ("host", &host_provider)] {
+        let status = NonceStatus::fetch(chain, provider, owner, nonces.iter().copied()).await?;
+        status.warn_on_gaps(max_cancellation_txs);
+        rows.extend(status.words.iter().map(|word| NonceRow {
+            chain,
+            word_pos: word.word_pos,
+            used: word.used(),
+            outstanding: word.outstanding.len(),
+        }));
+    }
+
+    args.output.print(&rows)
+}
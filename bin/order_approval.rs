@@ -0,0 +1,95 @@
+//! Four-eyes approval workflow for outgoing Orders.
+//!
+//! Orders are proposed from a JSON [`OrderIntent`] file, approved or rejected by a second
+//! operator, and only then signed and submitted to the transaction cache.
+
+use alloy::signers::Signer;
+use clap::{Parser, Subcommand};
+use init4_bin_base::utils::{from_env::FromEnv, tracing::init_tracing};
+use orders::{
+    approval::{OrderIntent, PendingOrder, PendingOrderStore},
+    filler::FillerConfig,
+    order::SendOrder,
+    output::OutputFormat,
+};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+struct ApprovalArgs {
+    /// Directory in which pending orders are stored.
+    #[arg(long, default_value = "pending-orders")]
+    store: PathBuf,
+    /// How to print the resulting orders.
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Propose an order for approval.
+    Propose {
+        /// Path to a JSON file containing the order intent.
+        intent: PathBuf,
+        /// Name of the proposer.
+        #[arg(long)]
+        proposer: String,
+    },
+    /// List all orders in the store.
+    List,
+    /// Approve a pending order.
+    Approve {
+        /// Id of the pending order.
+        id: String,
+        /// Name of the approver. Must differ from the proposer.
+        #[arg(long)]
+        approver: String,
+    },
+    /// Reject a pending order.
+    Reject {
+        /// Id of the pending order.
+        id: String,
+        /// Name of the approver.
+        #[arg(long)]
+        approver: String,
+        /// Why the order was rejected.
+        #[arg(long)]
+        reason: String,
+    },
+    /// Sign and submit all approved orders.
+    Submit,
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    init_tracing();
+
+    let args = ApprovalArgs::parse();
+    let store = PendingOrderStore::open(args.store)?;
+
+    let orders: Vec<PendingOrder> = match args.command {
+        Command::Propose { intent, proposer } => {
+            let intent: OrderIntent = serde_json::from_str(&std::fs::read_to_string(intent)?)?;
+            vec![store.propose(proposer, intent)?]
+        }
+        Command::List => store.list()?,
+        Command::Approve { id, approver } => vec![store.approve(&id, approver)?],
+        Command::Reject {
+            id,
+            approver,
+            reason,
+        } => vec![store.reject(&id, approver, reason)?],
+        Command::Submit => {
+            let config = FillerConfig::from_env()?;
+            let mut signer = config.signer_config.connect().await?;
+            // ensure signer chain ID is unset so it can be used for Host and Rollup
+            signer.set_chain_id(None);
+
+            let sender = SendOrder::new(signer, config.constants()?)?;
+            store.submit_approved(&sender).await?
+        }
+    };
+
+    args.output.print(&orders)
+}
@@ -0,0 +1,87 @@
+//! Pause and resume fills in tokens and token pairs while a Filler runs.
+//!
+//! Edits the pause file named by the Filler's `PAUSE_FILE`, which the Filler re-reads whenever
+//! it changes, and prints the resulting pause list.
+
+use alloy::primitives::Address;
+use clap::{Parser, Subcommand};
+use orders::pause::{PauseList, PausedPair};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+struct PauseArgs {
+    /// The pause file.
+    #[arg(long, default_value = "paused.json")]
+    file: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List paused tokens and pairs.
+    List,
+    /// Pause every pair involving a token, on any chain.
+    PauseToken {
+        /// The token.
+        token: Address,
+    },
+    /// Resume a paused token.
+    ResumeToken {
+        /// The token.
+        token: Address,
+    },
+    /// Pause a single pair.
+    PausePair {
+        /// The Input token, on the Rollup.
+        input: Address,
+        /// The chain the Output token is delivered on.
+        output_chain_id: u64,
+        /// The Output token.
+        output: Address,
+    },
+    /// Resume a paused pair.
+    ResumePair {
+        /// The Input token, on the Rollup.
+        input: Address,
+        /// The chain the Output token is delivered on.
+        output_chain_id: u64,
+        /// The Output token.
+        output: Address,
+    },
+}
+
+fn main() -> eyre::Result<()> {
+    let args = PauseArgs::parse();
+    let mut pauses = PauseList::load_or_default(&args.file)?;
+
+    let changed = match args.command {
+        Command::List => false,
+        Command::PauseToken { token } => pauses.tokens.insert(token),
+        Command::ResumeToken { token } => pauses.tokens.remove(&token),
+        Command::PausePair {
+            input,
+            output_chain_id,
+            output,
+        } => pauses.pairs.insert(PausedPair {
+            input,
+            output_chain_id,
+            output,
+        }),
+        Command::ResumePair {
+            input,
+            output_chain_id,
+            output,
+        } => pauses.pairs.remove(&PausedPair {
+            input,
+            output_chain_id,
+            output,
+        }),
+    };
+    if changed {
+        pauses.save(&args.file)?;
+    }
+
+    println!("{}", serde_json::to_string_pretty(&pauses)?);
+    Ok(())
+}
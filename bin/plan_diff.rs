@@ -0,0 +1,98 @@
+//! Dry-run fill plans and diff them, to make planner changes reviewable.
+//!
+//! Run `plan` on the same Orders before and after a planner change, then `diff` the two plans
+//! to see which transactions were added, removed, changed, or reordered.
+
+use alloy::signers::Signer;
+use clap::{Parser, Subcommand};
+use eyre::WrapErr;
+use init4_bin_base::utils::{from_env::FromEnv, tracing::init_tracing};
+use orders::{
+    filler::{BundlePlan, Filler, FillerConfig, load_orders},
+    output::OutputFormat,
+    provider::connect_provider,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Parser, Debug)]
+struct PlanDiffArgs {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Plan the fill of the Orders in a JSON file, printing the plan as JSON without sending
+    /// anything.
+    Plan {
+        /// The JSON file of Orders to plan.
+        orders_file: PathBuf,
+    },
+    /// Diff two plans of the same Orders.
+    Diff {
+        /// The plan before the change.
+        before: PathBuf,
+        /// The plan after the change.
+        after: PathBuf,
+        /// How to print the differences.
+        #[arg(long, value_enum, default_value_t)]
+        output: OutputFormat,
+    },
+}
+
+/// Load a plan printed by the `plan` command.
+fn load_plan(path: &Path) -> eyre::Result<BundlePlan> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read plan {}", path.display()))?;
+    serde_json::from_str(&contents).wrap_err_with(|| format!("invalid plan {}", path.display()))
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    init_tracing();
+
+    let args = PlanDiffArgs::parse();
+    let orders_file = match args.command {
+        Command::Plan { orders_file } => orders_file,
+        Command::Diff {
+            before,
+            after,
+            output,
+        } => {
+            let diffs = load_plan(&before)?.diff(&load_plan(&after)?);
+            if diffs.is_empty() && output == OutputFormat::Table {
+                println!("plans are identical");
+            }
+            return output.print(&diffs);
+        }
+    };
+
+    let config = FillerConfig::from_env()?;
+    config.validate()?;
+
+    let mut signer = config.signer_config.connect().await?;
+    // ensure signer chain ID is unset so it can be used for Host and Rollup
+    signer.set_chain_id(None);
+    let signer = config.timeout_signer(signer);
+
+    let ru_provider = connect_provider(
+        signer.clone(),
+        config.ru_rpc_url.clone(),
+        config.ru_rpc_rate_limit(),
+        config.rpc_metrics("rollup"),
+    )
+    .await?;
+    let host_provider = connect_provider(
+        signer.clone(),
+        config.host_rpc_url.clone(),
+        config.host_rpc_rate_limit(),
+        config.rpc_metrics("host"),
+    )
+    .await?;
+
+    let filler = Filler::new(signer, ru_provider, host_provider, config.constants()?)?
+        .with_fill_deadline_policy(config.fill_deadline_policy());
+    let plan = filler.plan(&load_orders(&orders_file)?).await?;
+    println!("{}", serde_json::to_string_pretty(&plan)?);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Manual review of Orders quarantined as anomalous by a Filler.
+//!
+//! Released Orders are evaluated normally on the Filler's next pass; rejected Orders are never
+//! filled.
+
+use clap::{Parser, Subcommand};
+use orders::{
+    anomaly::{QuarantineStore, QuarantinedOrder},
+    ids::OrderHash,
+    output::OutputFormat,
+};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+struct QuarantineArgs {
+    /// Directory in which quarantined orders are stored.
+    #[arg(long, default_value = "quarantine")]
+    store: PathBuf,
+    /// How to print the resulting orders.
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List all quarantined orders.
+    List,
+    /// Release a quarantined order, so it is evaluated normally.
+    Release {
+        /// Hash of the quarantined order.
+        order_hash: OrderHash,
+        /// Name of the reviewer.
+        #[arg(long)]
+        reviewer: String,
+    },
+    /// Reject a quarantined order, so it is never filled.
+    Reject {
+        /// Hash of the quarantined order.
+        order_hash: OrderHash,
+        /// Name of the reviewer.
+        #[arg(long)]
+        reviewer: String,
+        /// Why the order was rejected.
+        #[arg(long)]
+        reason: String,
+    },
+}
+
+fn main() -> eyre::Result<()> {
+    let args = QuarantineArgs::parse();
+    let store = QuarantineStore::open(args.store)?;
+
+    let orders: Vec<QuarantinedOrder> = match args.command {
+        Command::List => store.list()?,
+        Command::Release {
+            order_hash,
+            reviewer,
+        } => vec![store.release(&order_hash, reviewer)?],
+        Command::Reject {
+            order_hash,
+            reviewer,
+            reason,
+        } => vec![store.reject(&order_hash, reviewer, reason)?],
+    };
+
+    args.output.print(&orders)
+}
@@ -3,26 +3,72 @@ use alloy::{
     primitives::{Address, U256},
     signers::Signer,
 };
-use chrono::Utc;
 use clap::Parser;
 use init4_bin_base::{
-    deps::tracing::{debug, info, instrument},
+    deps::tracing::{debug, info, instrument, warn},
     utils::{from_env::FromEnv, signer::LocalOrAws, tracing::init_tracing},
 };
 use orders::{
-    filler::{Filler, FillerConfig},
+    clock::chain_time,
+    filler::{FillPipeline, Filler, FillerConfig, Profitability, load_orders},
+    ids::{BundleId, OrderHash},
+    metrics_push::MetricsPushConfig,
     order::SendOrder,
-    provider::{TxSenderProvider, connect_provider},
+    output::{OutputFormat, Render},
+    pretty::OrderFormatter,
+    provider::connect_provider,
+    quoting::StaticPrices,
+    timeout_signer::TimeoutSigner,
 };
+use serde::Serialize;
+use signet_constants::SignetConstants;
 use signet_types::{SignedOrder, UnsignedOrder};
+use std::path::PathBuf;
 use tokio::time::{Duration, sleep};
 
+/// Interval between polls of the transaction cache when watching for Orders.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Parser, Debug)]
 struct OrdersArgs {
     /// If present, the order will be filled on the rollup chain.
     /// If absent, the order will be filled on the host chain.
     #[arg(long, default_value_t = false)]
     pub rollup: bool,
+    /// How to print the order hash and submitted bundle ids.
+    #[arg(long, value_enum, default_value_t)]
+    pub output: OutputFormat,
+    /// Fill the orders in this JSON file in a single bundle, instead of sending an example
+    /// order and filling it from the transaction cache.
+    #[arg(long)]
+    pub orders_file: Option<PathBuf>,
+    /// Fill orders from the transaction cache as they arrive, forever, instead of sending an
+    /// example order.
+    #[arg(long, default_value_t = false)]
+    pub watch: bool,
+    /// The recipient of the order's output, as an address or an `ADDRESS_BOOK` name on the
+    /// output's chain.
+    /// Defaults to the signer.
+    #[arg(long)]
+    pub recipient: Option<String>,
+}
+
+/// The order sent and the bundles submitted to fill it.
+#[derive(Debug, Serialize)]
+struct RoundtripResult {
+    order_hash: OrderHash,
+    bundle_ids: Vec<BundleId>,
+}
+
+impl Render for RoundtripResult {
+    fn id(&self) -> String {
+        self.order_hash.to_string()
+    }
+
+    fn table_row(&self) -> String {
+        let bundle_ids: Vec<String> = self.bundle_ids.iter().map(ToString::to_string).collect();
+        format!("{} bundles={}", self.order_hash, bundle_ids.join(","))
+    }
 }
 
 /// Construct, sign, and send a Signet Order, then Fill the same Order.
@@ -31,67 +77,144 @@ async fn main() -> eyre::Result<()> {
     // initialize tracing
     init_tracing();
 
+    // push metrics to a Pushgateway if one is configured, so they outlive this run
+    let pusher = MetricsPushConfig::from_env()?.install()?;
+    // boxed, so the example's future stays within the recursion limit
+    let result = Box::pin(run()).await;
+    if let Some(pusher) = pusher
+        && let Err(err) = pusher.finish().await
+    {
+        warn!(%err, "Failed to push final metrics");
+    }
+    result
+}
+
+/// Run the example, as configured by environment variables and arguments.
+async fn run() -> eyre::Result<()> {
     // load config from environment variables
     let config = FillerConfig::from_env()?;
+    config.validate()?;
     let args = OrdersArgs::parse();
 
     // connect signer and provider
     let mut signer = config.signer_config.connect().await?;
     // ensure signer chain ID is unset so it can be used for Host and Rollup
     signer.set_chain_id(None);
+    // bound signing operations, so a hanging signer fails the fill rather than stalling it
+    let signer = config.timeout_signer(signer);
 
-    let ru_provider = connect_provider(signer.clone(), config.ru_rpc_url.clone()).await?;
-    let host_provider = connect_provider(signer.clone(), config.host_rpc_url.clone()).await?;
+    let ru_provider = connect_provider(
+        signer.clone(),
+        config.ru_rpc_url.clone(),
+        config.ru_rpc_rate_limit(),
+        config.rpc_metrics("rollup"),
+    )
+    .await?;
+    let host_provider = connect_provider(
+        signer.clone(),
+        config.host_rpc_url.clone(),
+        config.host_rpc_rate_limit(),
+        config.rpc_metrics("host"),
+    )
+    .await?;
     info!(signer_address = %signer.address(), "Connected to Signer and Provider");
 
-    // create an example order
-    let example_order = get_example_order(&config, signer.address(), args.rollup);
+    let constants = config.constants()?;
+    let formatter = OrderFormatter::new(&constants);
+
+    // create the filler, and ensure the providers match the configured chain constants
+    // NOTE: this example fills its own orders, so enabling self-trade prevention will skip them
+    let filler =
+        Filler::from_config(&config, signer.clone(), ru_provider.clone(), host_provider).await?;
+
+    // fill only orders with at least the minimum margin, if token prices are configured
+    let profitability = config.profitability()?;
+    let min_margin_bps = config.min_margin_bps();
+
+    // fill orders as they arrive in the transaction cache, until stopped
+    if args.watch {
+        let watcher = filler.watcher(WATCH_INTERVAL)?;
+        FillPipeline::new(filler, watcher).spawn().await?;
+        return Ok(());
+    }
+
+    // fill orders from a fixture file, if given, to reproduce issues with specific orders
+    if let Some(orders_file) = &args.orders_file {
+        let orders = load_orders(orders_file)?;
+        let bundle_ids = match &profitability {
+            Some(profitability) => {
+                fill_profitable(&filler, &orders, profitability, min_margin_bps).await?
+            }
+            None => filler.fill(&orders).await?,
+        };
+        let results: Vec<RoundtripResult> = orders
+            .iter()
+            .map(|order| RoundtripResult {
+                order_hash: OrderHash::from(order),
+                bundle_ids: bundle_ids.clone(),
+            })
+            .collect();
+        return args.output.print(&results);
+    }
+
+    // create an example order, with a deadline relative to the rollup's chain time
+    let now = chain_time(&ru_provider).await?;
+    let recipient = match &args.recipient {
+        Some(recipient) => {
+            let chain = if args.rollup { "rollup" } else { "host" };
+            config.address_book()?.resolve_on(chain, recipient)?
+        }
+        None => signer.address(),
+    };
+    let example_order = get_example_order(&constants, recipient, args.rollup, now);
 
     // sign & send the order to the transaction cache
-    let signed = send_order(example_order, &signer, &config).await?;
-    debug!(?signed, "Order contents");
+    let signed = send_order(example_order, &signer, &constants).await?;
+    debug!(order = %formatter.render(&signed), "Order contents");
     info!("Order signed and sent to transaction cache");
 
     // wait ~1 sec to ensure order is in cache
     sleep(Duration::from_secs(1)).await;
 
     // fill the order from the transaction cache
-    fill_orders(&signed, signer, ru_provider, host_provider, config).await?;
+    let bundle_ids = fill_orders(&signed, &filler, profitability.as_ref(), min_margin_bps).await?;
     info!("Bundle sent to tx cache successfully; wait for bundle to mine.");
 
-    Ok(())
+    args.output.print(&[RoundtripResult {
+        order_hash: OrderHash::from(&signed),
+        bundle_ids,
+    }])
 }
 
-/// Constructs an example [`UnsignedOrder`] based on the provided configuration and recipient
+/// Constructs an example [`UnsignedOrder`] based on the provided constants and recipient
 /// address.
 ///
 /// If `rollup` is true, it creates an order that targets the rollup; otherwise, it creates an
-/// order that targets the host chain.
+/// order that targets the host chain. The order deadline is set relative to `now`, which should
+/// be the current chain time.
 fn get_example_order(
-    config: &FillerConfig,
+    constants: &SignetConstants,
     recipient: Address,
     rollup: bool,
+    now: u64,
 ) -> UnsignedOrder<'static> {
     let unsigned = UnsignedOrder::default()
-        .with_input(
-            config.constants.rollup().tokens().weth(),
-            U256::from(GWEI_TO_WEI),
-        )
-        .with_deadline(Utc::now().timestamp() as u64 + (60 * 10));
+        .with_input(constants.rollup().tokens().weth(), U256::from(GWEI_TO_WEI))
+        .with_deadline(now + (60 * 10));
 
     if rollup {
         unsigned.with_output(
-            config.constants.rollup().tokens().weth(),
+            constants.rollup().tokens().weth(),
             U256::from(GWEI_TO_WEI),
             recipient,
-            config.constants.rollup().chain_id() as u32,
+            constants.rollup().chain_id() as u32,
         )
     } else {
         unsigned.with_output(
-            config.constants.host().tokens().weth(),
+            constants.host().tokens().weth(),
             U256::from(GWEI_TO_WEI),
             recipient,
-            config.constants.host().chain_id() as u32,
+            constants.host().chain_id() as u32,
         )
     }
 }
@@ -100,18 +223,15 @@ fn get_example_order(
 #[instrument(skip_all, level = "debug", fields(signer_address = %signer.address()))]
 async fn send_order(
     order: UnsignedOrder<'_>,
-    signer: &LocalOrAws,
-    config: &FillerConfig,
+    signer: &TimeoutSigner<LocalOrAws>,
+    constants: &SignetConstants,
 ) -> eyre::Result<SignedOrder> {
     info!("signing and sending order");
 
-    let send_order = SendOrder::new(signer.clone(), config.constants.clone())?;
+    let send_order = SendOrder::new(signer.clone(), constants.clone())?;
 
     // sign the order, return it back for comparison
-    let signed = order
-        .with_chain(config.constants.system())
-        .sign(signer)
-        .await?;
+    let signed = order.with_chain(constants.system()).sign(signer).await?;
 
     // send the signed order to the transaction cache
     send_order.send_order(signed.clone()).await?;
@@ -119,17 +239,37 @@ async fn send_order(
     Ok(signed)
 }
 
-/// Fill example [`SignedOrder`]s from the transaction cache.
+/// Fill the [`SignedOrder`]s whose margin is at least `min_margin_bps`, logging those left out.
+async fn fill_profitable(
+    filler: &Filler<TimeoutSigner<LocalOrAws>>,
+    orders: &[SignedOrder],
+    profitability: &Profitability<StaticPrices>,
+    min_margin_bps: u64,
+) -> eyre::Result<Vec<BundleId>> {
+    let fill = filler
+        .fill_profitable(orders, profitability, min_margin_bps)
+        .await?;
+    if !fill.unprofitable.is_empty() || !fill.unscored.is_empty() {
+        info!(
+            unprofitable = fill.unprofitable.len(),
+            unscored = fill.unscored.len(),
+            min_margin_bps,
+            "Left out unprofitable or unscored orders"
+        );
+    }
+    Ok(fill.bundle_ids)
+}
+
+/// Fill example [`SignedOrder`]s from the transaction cache, only if profitable when token
+/// prices are configured.
 #[instrument(skip_all, level = "debug")]
 async fn fill_orders(
     target_order: &SignedOrder,
-    signer: LocalOrAws,
-    ru_provider: TxSenderProvider,
-    host_provider: TxSenderProvider,
-    config: FillerConfig,
-) -> eyre::Result<()> {
+    filler: &Filler<TimeoutSigner<LocalOrAws>>,
+    profitability: Option<&Profitability<StaticPrices>>,
+    min_margin_bps: u64,
+) -> eyre::Result<Vec<BundleId>> {
     info!("filling orders from transaction cache");
-    let filler = Filler::new(signer, ru_provider, host_provider, config.constants)?;
 
     // get all the [`SignedOrder`]s from tx cache
     let mut orders: Vec<SignedOrder> = filler.get_orders().await?;
@@ -141,8 +281,14 @@ async fn fill_orders(
     // Retain only the orders that match the target order
     orders.retain(|o| o == target_order);
 
-    // fill each individually
-    filler.fill_individually(orders.as_slice()).await?;
+    // fill each individually, or only the profitable ones if token prices are configured
+    let bundle_ids = match profitability {
+        Some(profitability) => {
+            fill_profitable(filler, &orders, profitability, min_margin_bps).await?
+        }
+        None => filler.fill_individually(orders.as_slice()).await?,
+    };
+    debug!(?bundle_ids, "Submitted bundles");
 
-    Ok(())
+    Ok(bundle_ids)
 }
@@ -3,16 +3,24 @@ use alloy::{
     primitives::{Address, U256},
     signers::Signer,
 };
-use chrono::Utc;
 use init4_bin_base::{
     deps::tracing::{debug, info, instrument},
     utils::{from_env::FromEnv, signer::LocalOrAws, tracing::init_tracing},
 };
 use orders::{
+    balances::{BalanceMonitor, BalanceMonitorConfig},
+    clock::chain_time,
+    fill_monitor::{FillMonitor, FillMonitorConfig},
     filler::{Filler, FillerConfig},
+    ids::OrderHash,
     order::SendOrder,
-    provider::{TxSenderProvider, connect_provider},
+    pretty::OrderFormatter,
+    provider::connect_provider,
+    runtime::RuntimeConfig,
+    sweep::{ProfitSweep, ProfitSweepConfig},
+    timeout_signer::TimeoutSigner,
 };
+use signet_constants::SignetConstants;
 use signet_types::{SignedOrder, UnsignedOrder};
 use tokio::time::{Duration, sleep};
 
@@ -29,78 +37,149 @@ struct OrdersArgs {
     pub send_to_rollup: bool,
     #[from_env(var = "SLEEP_TIME", desc = "Time to sleep between transactions, in ms")]
     sleep_time: u64,
+    /// The recipient of each order's output, as an address or an `ADDRESS_BOOK` name on the
+    /// output's chain.
+    /// Defaults to the signer.
+    #[from_env(
+        var = "ORDER_RECIPIENT",
+        desc = "Recipient of each order's output, as an address or address book name",
+        optional
+    )]
+    recipient: Option<String>,
 }
 
 /// Construct, sign, and send a Signet Order, then Fill the same Order.
-#[tokio::main]
-async fn main() -> eyre::Result<()> {
+fn main() -> eyre::Result<()> {
+    RuntimeConfig::from_env()?.build()?.block_on(run())
+}
+
+/// Run the example on the configured tokio runtime.
+async fn run() -> eyre::Result<()> {
     init_tracing();
 
     let config = FillerConfig::from_env()?;
+    config.validate()?;
     let OrdersArgs {
         send_to_rollup,
         sleep_time,
+        recipient,
     } = OrdersArgs::from_env()?;
 
     let mut signer = config.signer_config.connect().await?;
     // ensure signer chain ID is unset so it can be used for Host and Rollup
     signer.set_chain_id(None);
-
-    let ru_provider = connect_provider(signer.clone(), config.ru_rpc_url.clone()).await?;
-    let host_provider = connect_provider(signer.clone(), config.host_rpc_url.clone()).await?;
+    // bound signing operations, so a hanging signer fails the fill rather than stalling it
+    let signer = config.timeout_signer(signer);
+
+    let ru_provider = connect_provider(
+        signer.clone(),
+        config.ru_rpc_url.clone(),
+        config.ru_rpc_rate_limit(),
+        config.rpc_metrics("rollup"),
+    )
+    .await?;
+    let host_provider = connect_provider(
+        signer.clone(),
+        config.host_rpc_url.clone(),
+        config.host_rpc_rate_limit(),
+        config.rpc_metrics("host"),
+    )
+    .await?;
     info!(signer_address = %signer.address(), "Connected to Signer and Provider");
 
+    let recipient = match &recipient {
+        Some(recipient) => {
+            let chain = if send_to_rollup { "rollup" } else { "host" };
+            config.address_book()?.resolve_on(chain, recipient)?
+        }
+        None => signer.address(),
+    };
+
+    let constants = config.constants()?;
+    let formatter = OrderFormatter::new(&constants);
+
+    // create the filler, and ensure the providers match the configured chain constants
+    // NOTE: this example fills its own orders, so enabling self-trade prevention will skip them
+    let filler = Filler::from_config(
+        &config,
+        signer.clone(),
+        ru_provider.clone(),
+        host_provider.clone(),
+    )
+    .await?;
+
+    // monitor signer balances in the background, if configured
+    let _balance_monitor = BalanceMonitor::from_config(
+        &BalanceMonitorConfig::from_env()?,
+        signer.address(),
+        &constants,
+        ru_provider.clone(),
+        host_provider.clone(),
+    )
+    .map(BalanceMonitor::spawn);
+
+    // sweep profit above the float to the treasury in the background, if configured
+    let _profit_sweep = ProfitSweep::from_config(
+        &ProfitSweepConfig::from_env()?,
+        signer.address(),
+        &constants,
+        ru_provider.clone(),
+        host_provider.clone(),
+    )
+    .map(ProfitSweep::spawn);
+
+    // notify when submitted orders fill, expire, or get stuck, if configured
+    let fill_monitor =
+        FillMonitor::from_config(&FillMonitorConfig::from_env()?, ru_provider.clone())?;
+    let _fill_monitor = fill_monitor.clone().map(FillMonitor::spawn);
+
     loop {
-        let example_order = get_example_order(&config, signer.address(), send_to_rollup);
+        let now = chain_time(&ru_provider).await?;
+        let example_order = get_example_order(&constants, recipient, send_to_rollup, now);
 
-        let signed = send_order(example_order, &signer, &config).await?;
-        debug!(?signed, "Order contents");
+        let signed = send_order(example_order, &signer, &constants).await?;
+        debug!(order = %formatter.render(&signed), "Order contents");
+        if let Some(fill_monitor) = &fill_monitor {
+            fill_monitor.watch(&signed, now);
+        }
 
         sleep(TX_CACHE_WAIT_TIME).await;
 
-        fill_orders(
-            &signed,
-            signer.clone(),
-            ru_provider.clone(),
-            host_provider.clone(),
-            &config,
-        )
-        .await?;
+        fill_orders(&signed, &filler).await?;
 
         sleep(Duration::from_millis(sleep_time)).await;
     }
 }
 
-/// Constructs an example [`UnsignedOrder`] based on the provided configuration and recipient
+/// Constructs an example [`UnsignedOrder`] based on the provided constants and recipient
 /// address.
 ///
 /// If `rollup` is true, it creates an order that targets the rollup; otherwise, it creates an
-/// order that targets the host chain.
+/// order that targets the host chain. The order deadline is set relative to `now`, which should
+/// be the current chain time.
 fn get_example_order(
-    config: &FillerConfig,
+    constants: &SignetConstants,
     recipient: Address,
     rollup: bool,
+    now: u64,
 ) -> UnsignedOrder<'static> {
     let unsigned = UnsignedOrder::default()
-        .with_input(
-            config.constants.rollup().tokens().weth(),
-            U256::from(GWEI_TO_WEI),
-        )
-        .with_deadline(Utc::now().timestamp() as u64 + (60 * 10));
+        .with_input(constants.rollup().tokens().weth(), U256::from(GWEI_TO_WEI))
+        .with_deadline(now + (60 * 10));
 
     if rollup {
         unsigned.with_output(
-            config.constants.rollup().tokens().weth(),
+            constants.rollup().tokens().weth(),
             U256::from(GWEI_TO_WEI),
             recipient,
-            config.constants.rollup().chain_id() as u32,
+            constants.rollup().chain_id() as u32,
         )
     } else {
         unsigned.with_output(
-            config.constants.host().tokens().weth(),
+            constants.host().tokens().weth(),
             U256::from(GWEI_TO_WEI),
             recipient,
-            config.constants.host().chain_id() as u32,
+            constants.host().chain_id() as u32,
         )
     }
 }
@@ -109,20 +188,20 @@ fn get_example_order(
 #[instrument(skip_all, fields(signer_address = %signer.address()))]
 async fn send_order(
     order: UnsignedOrder<'_>,
-    signer: &LocalOrAws,
-    config: &FillerConfig,
+    signer: &TimeoutSigner<LocalOrAws>,
+    constants: &SignetConstants,
 ) -> eyre::Result<SignedOrder> {
     info!("signing and sending order");
 
-    let send_order = SendOrder::new(signer.clone(), config.constants.clone())?;
+    let send_order = SendOrder::new(signer.clone(), constants.clone())?;
 
     // sign the order, return it back for comparison
-    let signed = order
-        .with_chain(config.constants.system())
-        .sign(signer)
-        .await?;
+    let signed = order.with_chain(constants.system()).sign(signer).await?;
 
-    tracing::Span::current().record("signed_order_signature", signed.order_hash().to_string());
+    tracing::Span::current().record(
+        "signed_order_signature",
+        OrderHash::from(&signed).to_string(),
+    );
     debug!(?signed, "Signed order contents");
 
     // send the signed order to the transaction cache
@@ -136,13 +215,9 @@ async fn send_order(
 #[instrument(skip_all, fields(target_order_signature = %target_order.permit.signature, target_order_owner = %target_order.permit.owner))]
 async fn fill_orders(
     target_order: &SignedOrder,
-    signer: LocalOrAws,
-    ru_provider: TxSenderProvider,
-    host_provider: TxSenderProvider,
-    config: &FillerConfig,
+    filler: &Filler<TimeoutSigner<LocalOrAws>>,
 ) -> eyre::Result<()> {
     info!("filling orders from transaction cache");
-    let filler = Filler::new(signer, ru_provider, host_provider, config.constants.clone())?;
 
     // get all the [`SignedOrder`]s from tx cache
     let mut orders: Vec<SignedOrder> = filler.get_orders().await?;
@@ -155,7 +230,8 @@ async fn fill_orders(
     orders.retain(|o| o == target_order);
 
     // fill each individually
-    filler.fill_individually(orders.as_slice()).await?;
+    let bundle_ids = filler.fill_individually(orders.as_slice()).await?;
+    debug!(?bundle_ids, "Submitted bundles");
 
     info!("Bundle sent to tx cache successfully; wait for bundle to mine.");
 
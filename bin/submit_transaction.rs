@@ -19,8 +19,9 @@ use init4_bin_base::{
         tracing::{debug, error},
     },
     init4,
-    utils::{from_env::FromEnv, signer::LocalOrAwsConfig},
+    utils::{from_env::FromEnv, signer::LocalOrAwsConfig, tracing::init_tracing},
 };
+use orders::metrics_push::MetricsPushConfig;
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
 use tracing::{info, instrument};
@@ -44,7 +45,7 @@ impl Config {
 
         ProviderBuilder::new_with_network()
             .disable_recommended_fillers()
-            .filler(BlobGasFiller)
+            .filler(BlobGasFiller::default())
             .with_gas_estimation()
             .with_nonce_management(SimpleNonceManager::default())
             .fetch_chain_id()
@@ -57,7 +58,13 @@ impl Config {
 
 #[tokio::main]
 async fn main() {
-    let _guard = init4();
+    // push metrics to a Pushgateway if one is configured, rather than serving them for scraping
+    let push = MetricsPushConfig::from_env().unwrap();
+    let (_guard, _pusher) = if push.is_enabled() {
+        (init_tracing(), push.install().unwrap())
+    } else {
+        (init4(), None)
+    };
 
     let config = Config::from_env().unwrap();
 
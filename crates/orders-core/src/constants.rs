@@ -0,0 +1,123 @@
+use alloy::primitives::Address;
+use eyre::{Error, eyre};
+use signet_constants::{
+    HostConstants, HostTokens, KnownChains, RollupConstants, RollupTokens, SignetConstants,
+    SignetEnvironmentConstants, SignetSystemConstants, UsdRecords,
+};
+use std::borrow::Cow;
+
+/// The built-in Signet constants for a known network, or `None` if the chain name is not a
+/// known network.
+pub fn known_constants(chain_name: &str) -> Result<Option<SignetConstants>, Error> {
+    let Ok(chain) = chain_name.parse::<KnownChains>() else {
+        return Ok(None);
+    };
+    SignetConstants::try_from(chain)
+        .map(Some)
+        .map_err(|e| eyre!("invalid constants for chain {chain_name}: {e}"))
+}
+
+/// Builder for [`SignetConstants`] of a custom Signet deployment.
+///
+/// Only the values used by Fillers and Order senders are configurable; contracts that are not
+/// used by this crate (Zenith, Passage, Transactor) are set to the zero address.
+#[derive(Debug, Clone, Default)]
+pub struct SignetConstantsBuilder {
+    host_chain_id: Option<u64>,
+    ru_chain_id: Option<u64>,
+    host_orders: Option<Address>,
+    ru_orders: Option<Address>,
+    host_weth: Address,
+    ru_weth: Address,
+    tx_cache_url: Option<String>,
+}
+
+impl SignetConstantsBuilder {
+    /// Set the Host chain id.
+    pub const fn host_chain_id(mut self, chain_id: u64) -> Self {
+        self.host_chain_id = Some(chain_id);
+        self
+    }
+
+    /// Set the Rollup chain id.
+    pub const fn ru_chain_id(mut self, chain_id: u64) -> Self {
+        self.ru_chain_id = Some(chain_id);
+        self
+    }
+
+    /// Set the Host Orders contract address.
+    pub const fn host_orders(mut self, orders: Address) -> Self {
+        self.host_orders = Some(orders);
+        self
+    }
+
+    /// Set the Rollup Orders contract address.
+    pub const fn ru_orders(mut self, orders: Address) -> Self {
+        self.ru_orders = Some(orders);
+        self
+    }
+
+    /// Set the Host WETH address.
+    pub const fn host_weth(mut self, weth: Address) -> Self {
+        self.host_weth = weth;
+        self
+    }
+
+    /// Set the Rollup WETH address.
+    pub const fn ru_weth(mut self, weth: Address) -> Self {
+        self.ru_weth = weth;
+        self
+    }
+
+    /// Set the transaction cache URL.
+    pub fn tx_cache_url(mut self, url: impl Into<String>) -> Self {
+        self.tx_cache_url = Some(url.into());
+        self
+    }
+
+    /// Build the [`SignetConstants`], failing if a required value is missing.
+    pub fn build(self) -> Result<SignetConstants, Error> {
+        let host_chain_id = self
+            .host_chain_id
+            .ok_or_else(|| eyre!("missing host chain id"))?;
+        let ru_chain_id = self
+            .ru_chain_id
+            .ok_or_else(|| eyre!("missing rollup chain id"))?;
+        let host_orders = self
+            .host_orders
+            .ok_or_else(|| eyre!("missing host orders address"))?;
+        let ru_orders = self
+            .ru_orders
+            .ok_or_else(|| eyre!("missing rollup orders address"))?;
+        let tx_cache_url = self
+            .tx_cache_url
+            .ok_or_else(|| eyre!("missing transaction cache url"))?;
+
+        let host = HostConstants::new(
+            host_chain_id,
+            0,
+            Address::ZERO,
+            host_orders,
+            Address::ZERO,
+            Address::ZERO,
+            HostTokens::new(UsdRecords::new(), Address::ZERO, self.host_weth),
+        );
+        let rollup = RollupConstants::new(
+            ru_chain_id,
+            ru_orders,
+            Address::ZERO,
+            Address::ZERO,
+            RollupTokens::new(Address::ZERO, self.ru_weth),
+        );
+        let environment = SignetEnvironmentConstants::new(
+            Cow::Owned(format!("host-{host_chain_id}")),
+            Cow::Owned(format!("rollup-{ru_chain_id}")),
+            Cow::Owned(tx_cache_url),
+        );
+
+        Ok(SignetConstants::new(
+            SignetSystemConstants::new(host, rollup),
+            environment,
+        ))
+    }
+}
@@ -0,0 +1,70 @@
+use alloy::primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+use signet_constants::SignetConstants;
+use signet_types::UnsignedOrder;
+
+/// An Input of an [`OrderIntent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntentInput {
+    /// The token provided by the Order sender on the Rollup.
+    pub token: Address,
+    /// The amount of the token.
+    pub amount: U256,
+}
+
+/// An Output of an [`OrderIntent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntentOutput {
+    /// The token to be received.
+    pub token: Address,
+    /// The amount of the token.
+    pub amount: U256,
+    /// The recipient of the token.
+    pub recipient: Address,
+    /// The chain on which the token is received.
+    pub chain_id: u32,
+}
+
+/// A composed, not-yet-signed Order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderIntent {
+    /// The Order Inputs.
+    pub inputs: Vec<IntentInput>,
+    /// The Order Outputs.
+    pub outputs: Vec<IntentOutput>,
+    /// The Order deadline, as a unix timestamp.
+    pub deadline: u64,
+}
+
+impl OrderIntent {
+    /// Build the [`UnsignedOrder`] described by this intent.
+    pub fn to_unsigned(&self) -> UnsignedOrder<'static> {
+        let unsigned = self
+            .inputs
+            .iter()
+            .fold(UnsignedOrder::default(), |unsigned, input| {
+                unsigned.with_input(input.token, input.amount)
+            });
+
+        self.outputs
+            .iter()
+            .fold(unsigned, |unsigned, output| {
+                unsigned.with_output(
+                    output.token,
+                    output.amount,
+                    output.recipient,
+                    output.chain_id,
+                )
+            })
+            .with_deadline(self.deadline)
+    }
+}
+
+/// Prepare an [`UnsignedOrder`] for signing on the chains of `constants`, binding its Permit2
+/// signature to the Rollup Orders contract and chain.
+pub fn prepare_order<'a>(
+    unsigned: UnsignedOrder<'a>,
+    constants: &'a SignetConstants,
+) -> UnsignedOrder<'a> {
+    unsigned.with_chain(constants.system())
+}
@@ -0,0 +1,29 @@
+//! Order construction and validation, shared by the Order tooling and by web frontends.
+//!
+//! Nothing here depends on an async runtime or an HTTP client, so frontends can build and
+//! pre-validate Orders with exactly the logic the `signet-orders` tooling uses, which
+//! re-exports these modules.
+//!
+//! NOTE: this crate does not yet build for `wasm32-unknown-unknown`: the Signet SDK crates it
+//! depends on enable alloy features whose dependencies don't support that target.
+
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    unreachable_pub,
+    clippy::missing_const_for_fn,
+    rustdoc::all
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+/// Signet constants for known networks and custom deployments.
+pub mod constants;
+
+/// Orders composed from their Inputs, Outputs, and deadline, ready to be signed.
+pub mod intent;
+
+/// Validation of untrusted signed Orders.
+pub mod validate;
@@ -0,0 +1,69 @@
+use alloy::primitives::{Address, U256};
+use eyre::{Error, eyre};
+use signet_constants::SignetConstants;
+use signet_types::SignedOrder;
+use std::collections::{HashMap, HashSet};
+
+/// Validate an untrusted [`SignedOrder`] before it is planned or filled.
+///
+/// Orders served by the transaction cache are untrusted input. This rejects Orders that could
+/// never be filled, or that would break fill planning:
+/// - no Inputs or no Outputs
+/// - zero amounts
+/// - duplicate Input tokens
+/// - Outputs targeting a chain other than the Host or Rollup
+/// - a deadline that has passed, relative to `now`, or that does not fit in a u64
+/// - Output amounts that overflow when aggregated
+///
+/// Signatures are not checked here; invalid signatures cause the `initiate` transaction to revert.
+pub fn validate_order(
+    order: &SignedOrder,
+    constants: &SignetConstants,
+    now: u64,
+) -> Result<(), Error> {
+    let permit = &order.permit.permit;
+
+    if permit.permitted.is_empty() {
+        eyre::bail!("order has no inputs");
+    }
+    if order.outputs.is_empty() {
+        eyre::bail!("order has no outputs");
+    }
+
+    let deadline: u64 = permit
+        .deadline
+        .try_into()
+        .map_err(|_| eyre!("order deadline {} is out of range", permit.deadline))?;
+    if deadline < now {
+        eyre::bail!("order deadline {deadline} has passed");
+    }
+
+    let mut input_tokens = HashSet::new();
+    for input in &permit.permitted {
+        if input.amount.is_zero() {
+            eyre::bail!("order input {} has zero amount", input.token);
+        }
+        if !input_tokens.insert(input.token) {
+            eyre::bail!("order has duplicate input token {}", input.token);
+        }
+    }
+
+    let chain_ids = [constants.host().chain_id(), constants.rollup().chain_id()];
+    let mut output_totals: HashMap<(u32, Address), U256> = HashMap::new();
+    for output in &order.outputs {
+        if output.amount.is_zero() {
+            eyre::bail!("order output {} has zero amount", output.token);
+        }
+        if !chain_ids.contains(&u64::from(output.chainId)) {
+            eyre::bail!("order output targets unknown chain {}", output.chainId);
+        }
+        let total = output_totals
+            .entry((output.chainId, output.token))
+            .or_default();
+        *total = total
+            .checked_add(output.amount)
+            .ok_or_else(|| eyre!("order output amounts overflow"))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Feed arbitrary JSON through `SignedOrder` deserialization, validation, and fill planning.
+//!
+//! The transaction cache is untrusted input: malformed or adversarial Orders (huge amounts,
+//! zero outputs, absurd deadlines, duplicate tokens) must be rejected, never cause a panic.
+//!
+//! Run with `cargo +nightly fuzz run order_json`.
+
+#![no_main]
+
+use alloy::primitives::Address;
+use libfuzzer_sys::fuzz_target;
+use orders::{
+    constants::resolve_constants, filler::plan::rollup_txn_requests, validate::validate_order,
+};
+use signet_types::{AggregateOrders, SignedOrder, UnsignedFill};
+use std::collections::HashMap;
+
+/// Fixed reference time for deadline validation.
+const NOW: u64 = 1_800_000_000;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(orders) = serde_json::from_slice::<Vec<SignedOrder>>(data) else {
+        return;
+    };
+    let constants = resolve_constants("pecorino").unwrap();
+
+    let valid: Vec<SignedOrder> = orders
+        .into_iter()
+        .filter(|order| validate_order(order, &constants, NOW).is_ok())
+        .collect();
+    if valid.is_empty() {
+        return;
+    }
+
+    // planning must not panic for any set of valid orders
+    let agg: AggregateOrders = valid.iter().collect();
+    let _ = UnsignedFill::from(&agg);
+    let _ = rollup_txn_requests(&constants, Address::ZERO, &HashMap::new(), &valid);
+});
@@ -0,0 +1,107 @@
+use alloy::primitives::Address;
+use eyre::{Error, eyre};
+use std::collections::BTreeMap;
+
+/// Named addresses, so operators can refer to recipients and counterparties by alias rather
+/// than raw hex.
+///
+/// Names may be scoped to a chain, `rollup` or `host`, for accounts whose address differs
+/// between chains; unscoped names apply on every chain. Addresses can also be resolved back to
+/// names, so logs and CLI output show the alias alongside the address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressBook {
+    /// Addresses, by chain (or `None` for every chain), then by name.
+    entries: BTreeMap<Option<String>, BTreeMap<String, Address>>,
+}
+
+impl AddressBook {
+    /// Parse an address book from comma-separated `name=address` or `chain:name=address`
+    /// entries, e.g. `treasury=0x...,host:alice=0x...`.
+    pub fn parse(entries: &str) -> Result<Self, Error> {
+        let mut book = Self::default();
+        for entry in entries.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, address) = entry.split_once('=').ok_or_else(|| {
+                eyre!("invalid address book entry {entry}: expected name=address")
+            })?;
+            let address = address
+                .trim()
+                .parse()
+                .map_err(|e| eyre!("invalid address for {name}: {e}"))?;
+            book = match name.trim().split_once(':') {
+                Some((chain, name)) => book.with_chain_entry(chain.trim(), name.trim(), address),
+                None => book.with_entry(name.trim(), address),
+            };
+        }
+        Ok(book)
+    }
+
+    /// Add a named address, used on every chain.
+    pub fn with_entry(mut self, name: impl Into<String>, address: Address) -> Self {
+        self.entries
+            .entry(None)
+            .or_default()
+            .insert(name.into(), address);
+        self
+    }
+
+    /// Add a named address, used only on the named chain.
+    pub fn with_chain_entry(
+        mut self,
+        chain: impl Into<String>,
+        name: impl Into<String>,
+        address: Address,
+    ) -> Self {
+        self.entries
+            .entry(Some(chain.into()))
+            .or_default()
+            .insert(name.into(), address);
+        self
+    }
+
+    /// Resolve an unscoped name in the address book, or a hex address.
+    pub fn resolve(&self, name_or_address: &str) -> Result<Address, Error> {
+        self.lookup(None, name_or_address)
+    }
+
+    /// Resolve a name on the given chain, falling back to unscoped names, or a hex address.
+    pub fn resolve_on(&self, chain: &str, name_or_address: &str) -> Result<Address, Error> {
+        self.lookup(Some(chain), name_or_address)
+    }
+
+    /// Resolve a name, scoped to `chain` if given, or a hex address.
+    fn lookup(&self, chain: Option<&str>, name_or_address: &str) -> Result<Address, Error> {
+        let scoped = chain
+            .and_then(|chain| self.entries.get(&Some(chain.to_owned())))
+            .and_then(|names| names.get(name_or_address));
+        let unscoped = self
+            .entries
+            .get(&None)
+            .and_then(|names| names.get(name_or_address));
+        if let Some(address) = scoped.or(unscoped) {
+            return Ok(*address);
+        }
+        name_or_address
+            .parse()
+            .map_err(|_| eyre!("{name_or_address} is neither a known name nor an address"))
+    }
+
+    /// The name of an address on the given chain, if it is in the address book.
+    pub fn name_of(&self, chain: &str, address: Address) -> Option<&str> {
+        let named = |chain: Option<String>| {
+            self.entries
+                .get(&chain)?
+                .iter()
+                .find(|&(_, &named)| named == address)
+                .map(|(name, _)| name.as_str())
+        };
+        named(Some(chain.to_owned())).or_else(|| named(None))
+    }
+
+    /// Label an address for display, as `name (address)` if it is named, or the address.
+    pub fn label(&self, chain: &str, address: Address) -> String {
+        match self.name_of(chain, address) {
+            Some(name) => format!("{name} ({address})"),
+            None => address.to_string(),
+        }
+    }
+}
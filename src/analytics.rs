@@ -0,0 +1,253 @@
+use alloy::primitives::{Address, U256};
+use serde::Serialize;
+use signet_types::SignedOrder;
+use std::collections::HashMap;
+
+/// A token on a specific chain.
+pub type ChainToken = (u64, Address);
+
+/// A directed token pair: Orders in the pair offer the Rollup `input` token in exchange for the
+/// `output` token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenPair {
+    /// The Input token, on the Rollup.
+    pub input: Address,
+    /// The Output token, and the chain it is delivered on.
+    pub output: ChainToken,
+}
+
+impl TokenPair {
+    /// The pair of an Order's first Input and first Output, if it has both.
+    pub fn of_order(order: &SignedOrder) -> Option<Self> {
+        let input = order.permit.permit.permitted.first()?;
+        let output = order.outputs.first()?;
+        Some(Self {
+            input: input.token,
+            output: (u64::from(output.chainId), output.token),
+        })
+    }
+}
+
+/// One level of a depth chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthLevel {
+    /// The implied exchange rate of the Order, as Output amount per Input amount.
+    pub rate: f64,
+    /// Cumulative Input amount offered at this rate or better.
+    pub cumulative_input: U256,
+    /// Cumulative Output amount required at this rate or better.
+    pub cumulative_output: U256,
+}
+
+/// Depth and implied exchange rates for a single [`TokenPair`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PairDepth {
+    /// Depth levels, one per Order, best rate for a Filler (lowest Output per Input) first.
+    pub levels: Vec<DepthLevel>,
+}
+
+impl PairDepth {
+    /// The best implied exchange rate for a Filler, if any Orders are resting in the pair.
+    pub fn best_rate(&self) -> Option<f64> {
+        self.levels.first().map(|level| level.rate)
+    }
+
+    /// Total Input amount offered in the pair.
+    pub fn total_input(&self) -> U256 {
+        self.levels
+            .last()
+            .map(|level| level.cumulative_input)
+            .unwrap_or_default()
+    }
+}
+
+/// Point-in-time depth and spread analytics over a set of Orders, e.g. those returned by
+/// [`Filler::get_orders`].
+///
+/// Only Orders with a single Input and a single Output have an implied exchange rate; other
+/// Orders are counted in [`BookAnalytics::skipped`] but otherwise ignored.
+///
+/// Analytics are computed from a snapshot; tracking them over time is left to the caller, e.g.
+/// by recording snapshots tagged with [`BookAnalytics::timestamp`].
+///
+/// [`Filler::get_orders`]: crate::filler::Filler::get_orders
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookAnalytics {
+    /// The time at which the snapshot was taken, in seconds.
+    pub timestamp: u64,
+    /// Depth, per token pair.
+    pub pairs: HashMap<TokenPair, PairDepth>,
+    /// Number of Orders without an implied exchange rate.
+    pub skipped: usize,
+}
+
+impl BookAnalytics {
+    /// Compute analytics over a snapshot of Orders taken at `timestamp`.
+    pub fn from_orders(orders: &[SignedOrder], timestamp: u64) -> Self {
+        let mut quotes: HashMap<TokenPair, Vec<(f64, U256, U256)>> = HashMap::new();
+        let mut skipped = 0;
+
+        for order in orders {
+            let ([input], [output]) = (&order.permit.permit.permitted[..], &order.outputs[..])
+            else {
+                skipped += 1;
+                continue;
+            };
+            if input.amount.is_zero() {
+                skipped += 1;
+                continue;
+            }
+            let pair = TokenPair {
+                input: input.token,
+                output: (u64::from(output.chainId), output.token),
+            };
+            let rate = f64::from(output.amount) / f64::from(input.amount);
+            quotes
+                .entry(pair)
+                .or_default()
+                .push((rate, input.amount, output.amount));
+        }
+
+        let pairs = quotes
+            .into_iter()
+            .map(|(pair, mut quotes)| {
+                quotes.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let (mut cumulative_input, mut cumulative_output) = (U256::ZERO, U256::ZERO);
+                let levels = quotes
+                    .into_iter()
+                    .map(|(rate, input, output)| {
+                        cumulative_input = cumulative_input.saturating_add(input);
+                        cumulative_output = cumulative_output.saturating_add(output);
+                        DepthLevel {
+                            rate,
+                            cumulative_input,
+                            cumulative_output,
+                        }
+                    })
+                    .collect();
+                (pair, PairDepth { levels })
+            })
+            .collect();
+
+        Self {
+            timestamp,
+            pairs,
+            skipped,
+        }
+    }
+
+    /// Depth for a token pair, if any Orders are resting in it.
+    pub fn depth(&self, pair: &TokenPair) -> Option<&PairDepth> {
+        self.pairs.get(pair)
+    }
+
+    /// The spread between two Rollup tokens `base` and `quote`, expressed in `quote` per `base`.
+    ///
+    /// Orders offering `base` for `quote` ask for `quote`; Orders offering `quote` for `base`
+    /// bid for `base`. The spread is the lowest ask minus the highest bid, and is only defined
+    /// when both sides have Orders resting.
+    pub fn spread(&self, rollup_chain_id: u64, base: Address, quote: Address) -> Option<f64> {
+        let ask = self
+            .depth(&TokenPair {
+                input: base,
+                output: (rollup_chain_id, quote),
+            })?
+            .best_rate()?;
+        // the reverse pair quotes `base` per `quote`; invert it to compare like for like
+        let bid = self
+            .depth(&TokenPair {
+                input: quote,
+                output: (rollup_chain_id, base),
+            })?
+            .best_rate()
+            .map(|rate| 1.0 / rate)?;
+        Some(ask - bid)
+    }
+}
+
+/// A token in a [`MarketSnapshot`], identified without Signet types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MarketToken {
+    /// The chain the token is on.
+    pub chain_id: u64,
+    /// The token address.
+    pub address: Address,
+}
+
+/// One price level of a [`MarketSnapshot`], as `[price, size]`.
+///
+/// The price is the quote amount per base amount, in raw token units; the size is the base
+/// amount offered at that price, in raw token units, as a decimal string.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceLevel(pub f64, pub String);
+
+/// A level-2 order book snapshot of one market, in a generic market-data schema, so external
+/// pricing systems can ingest Signet order flow without speaking Signet types.
+///
+/// Each resting Order offering `base` for `quote` is an ask for `base`, priced in `quote`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MarketSnapshot {
+    /// The message type, always `l2_snapshot`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// The venue, always `signet`.
+    pub venue: &'static str,
+    /// The time at which the snapshot was taken, in seconds.
+    pub timestamp: u64,
+    /// The market symbol, `base/quote` as `chain_id:address`.
+    pub symbol: String,
+    /// The token offered.
+    pub base: MarketToken,
+    /// The token asked for.
+    pub quote: MarketToken,
+    /// Bids, best first. Signet Orders only ask, so this is empty unless the reverse market is
+    /// merged in by the consumer.
+    pub bids: Vec<PriceLevel>,
+    /// Asks, best (lowest price) first.
+    pub asks: Vec<PriceLevel>,
+}
+
+impl BookAnalytics {
+    /// Export the book as one [`MarketSnapshot`] per token pair, sorted by symbol.
+    pub fn market_snapshots(&self, rollup_chain_id: u64) -> Vec<MarketSnapshot> {
+        let mut snapshots: Vec<MarketSnapshot> = self
+            .pairs
+            .iter()
+            .map(|(pair, depth)| {
+                let base = MarketToken {
+                    chain_id: rollup_chain_id,
+                    address: pair.input,
+                };
+                let quote = MarketToken {
+                    chain_id: pair.output.0,
+                    address: pair.output.1,
+                };
+                let mut previous = U256::ZERO;
+                let asks = depth
+                    .levels
+                    .iter()
+                    .map(|level| {
+                        let size = level.cumulative_input - previous;
+                        previous = level.cumulative_input;
+                        PriceLevel(level.rate, size.to_string())
+                    })
+                    .collect();
+                MarketSnapshot {
+                    kind: "l2_snapshot",
+                    venue: "signet",
+                    timestamp: self.timestamp,
+                    symbol: format!(
+                        "{}:{}/{}:{}",
+                        base.chain_id, base.address, quote.chain_id, quote.address
+                    ),
+                    base,
+                    quote,
+                    bids: vec![],
+                    asks,
+                }
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        snapshots
+    }
+}
@@ -0,0 +1,456 @@
+use crate::{
+    analytics::{BookAnalytics, TokenPair},
+    ids::OrderHash,
+    output::Render,
+};
+use alloy::primitives::{Address, B256, Keccak256};
+use eyre::{Error, WrapErr, eyre};
+use init4_bin_base::{
+    deps::{
+        metrics::counter,
+        tracing::{debug, warn},
+    },
+    utils::from_env::FromEnv,
+};
+use serde::{Deserialize, Serialize};
+use signet_types::SignedOrder;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Default number of identical Orders seen within the flood window before further copies are
+/// quarantined.
+const DEFAULT_FLOOD_THRESHOLD: usize = 20;
+
+/// Default window over which identical Orders are counted, in seconds.
+const DEFAULT_FLOOD_WINDOW_SECS: u64 = 60;
+
+/// Minimum number of Orders resting in a pair before its median rate is trusted.
+const MIN_PAIR_DEPTH: usize = 3;
+
+/// Basis points in one whole.
+const BPS: f64 = 10_000.0;
+
+/// Configuration for an [`AnomalyDetector`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct AnomalyConfig {
+    /// Directory in which quarantined Orders are stored. Anomaly detection is disabled if unset.
+    #[from_env(
+        var = "QUARANTINE_DIR",
+        desc = "Directory in which anomalous Orders are quarantined for review",
+        optional
+    )]
+    pub quarantine_dir: Option<String>,
+    /// Number of identical Orders within the flood window after which further copies are
+    /// quarantined.
+    #[from_env(
+        var = "ANOMALY_FLOOD_THRESHOLD",
+        desc = "Number of identical Orders within the window before copies are quarantined",
+        optional
+    )]
+    pub flood_threshold: Option<usize>,
+    /// Window over which identical Orders are counted, in seconds.
+    #[from_env(
+        var = "ANOMALY_FLOOD_WINDOW_SECS",
+        desc = "Window over which identical Orders are counted, in seconds",
+        optional
+    )]
+    pub flood_window_secs: Option<u64>,
+    /// Maximum deviation of an Order's rate from its pair's median rate, in basis points.
+    /// Pricing is not checked if unset.
+    #[from_env(
+        var = "ANOMALY_MAX_RATE_DEVIATION_BPS",
+        desc = "Maximum deviation of an Order's rate from its pair's median, in bps",
+        optional
+    )]
+    pub max_rate_deviation_bps: Option<u32>,
+    /// Comma-separated flagged addresses, whose Orders are always quarantined.
+    #[from_env(
+        var = "FLAGGED_ADDRESSES",
+        desc = "Comma-separated addresses whose Orders are quarantined",
+        optional
+    )]
+    pub flagged_addresses: Option<String>,
+}
+
+impl AnomalyConfig {
+    /// Build an [`AnomalyDetector`] using this configuration, if a quarantine directory is set.
+    pub fn build(&self) -> Result<Option<AnomalyDetector>, Error> {
+        let Some(dir) = &self.quarantine_dir else {
+            return Ok(None);
+        };
+        let flagged = self
+            .flagged_addresses
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .map(|address| {
+                address
+                    .parse()
+                    .map_err(|e| eyre!("invalid flagged address {address}: {e}"))
+            })
+            .collect::<Result<HashSet<Address>, Error>>()?;
+        Ok(Some(
+            AnomalyDetector::new(QuarantineStore::open(dir)?)
+                .with_flood_limit(
+                    self.flood_threshold.unwrap_or(DEFAULT_FLOOD_THRESHOLD),
+                    self.flood_window_secs.unwrap_or(DEFAULT_FLOOD_WINDOW_SECS),
+                )
+                .with_max_rate_deviation_bps(self.max_rate_deviation_bps)
+                .with_flagged(flagged),
+        ))
+    }
+}
+
+/// The review status of a [`QuarantinedOrder`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ReviewStatus {
+    /// Awaiting review; the Order is not filled.
+    Pending,
+    /// Released by a reviewer; the Order is evaluated normally.
+    Released {
+        /// The reviewer.
+        reviewer: String,
+    },
+    /// Rejected by a reviewer; the Order is never filled.
+    Rejected {
+        /// The reviewer.
+        reviewer: String,
+        /// Why the Order was rejected.
+        reason: String,
+    },
+}
+
+/// An Order held for manual review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedOrder {
+    /// The hash of the Order.
+    pub order_hash: OrderHash,
+    /// The Order.
+    pub order: SignedOrder,
+    /// Why the Order was quarantined.
+    pub reason: String,
+    /// Chain time at which the Order was quarantined, in seconds.
+    pub quarantined_at: u64,
+    /// The review status.
+    pub status: ReviewStatus,
+}
+
+impl Render for QuarantinedOrder {
+    fn id(&self) -> String {
+        self.order_hash.to_string()
+    }
+
+    fn table_row(&self) -> String {
+        format!(
+            "{} owner={} reason={:?} status={:?}",
+            self.order_hash, self.order.permit.owner, self.reason, self.status
+        )
+    }
+}
+
+/// A file-backed store of quarantined Orders.
+///
+/// Each Order is stored as a JSON file named by its hash in the store directory, so reviewers
+/// can release or reject Orders while the Filler runs.
+#[derive(Debug, Clone)]
+pub struct QuarantineStore {
+    /// The directory containing the quarantined Orders.
+    dir: PathBuf,
+}
+
+impl QuarantineStore {
+    /// Open a store in the given directory, creating it if necessary.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, Error> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .wrap_err_with(|| format!("failed to create quarantine directory {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// The directory containing the quarantined Orders.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Load a quarantined Order by hash, if it has been quarantined.
+    pub fn get(&self, order_hash: &OrderHash) -> Result<Option<QuarantinedOrder>, Error> {
+        let path = self.path(order_hash);
+        match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map(Some)
+                .wrap_err_with(|| format!("invalid quarantine file {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).wrap_err_with(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    /// List all Orders in the store, oldest first.
+    pub fn list(&self) -> Result<Vec<QuarantinedOrder>, Error> {
+        let mut orders = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let contents = fs::read_to_string(&path)?;
+                orders.push(
+                    serde_json::from_str(&contents)
+                        .wrap_err_with(|| format!("invalid quarantine file {}", path.display()))?,
+                );
+            }
+        }
+        orders.sort_by_key(|order: &QuarantinedOrder| order.quarantined_at);
+        Ok(orders)
+    }
+
+    /// Quarantine an Order for review, unless it has already been quarantined.
+    pub fn quarantine(
+        &self,
+        order: &SignedOrder,
+        reason: impl Into<String>,
+        now: u64,
+    ) -> Result<QuarantinedOrder, Error> {
+        let order_hash = OrderHash::from(order);
+        if let Some(existing) = self.get(&order_hash)? {
+            return Ok(existing);
+        }
+        let quarantined = QuarantinedOrder {
+            order_hash,
+            order: order.clone(),
+            reason: reason.into(),
+            quarantined_at: now,
+            status: ReviewStatus::Pending,
+        };
+        self.save(&quarantined)?;
+        Ok(quarantined)
+    }
+
+    /// Release a pending Order, so it is evaluated normally.
+    pub fn release(
+        &self,
+        order_hash: &OrderHash,
+        reviewer: impl Into<String>,
+    ) -> Result<QuarantinedOrder, Error> {
+        let mut quarantined = self.get_pending(order_hash)?;
+        quarantined.status = ReviewStatus::Released {
+            reviewer: reviewer.into(),
+        };
+        self.save(&quarantined)?;
+        Ok(quarantined)
+    }
+
+    /// Reject a pending Order, so it is never filled.
+    pub fn reject(
+        &self,
+        order_hash: &OrderHash,
+        reviewer: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Result<QuarantinedOrder, Error> {
+        let mut quarantined = self.get_pending(order_hash)?;
+        quarantined.status = ReviewStatus::Rejected {
+            reviewer: reviewer.into(),
+            reason: reason.into(),
+        };
+        self.save(&quarantined)?;
+        Ok(quarantined)
+    }
+
+    /// Load a quarantined Order by hash, ensuring it is still awaiting review.
+    fn get_pending(&self, order_hash: &OrderHash) -> Result<QuarantinedOrder, Error> {
+        let quarantined = self
+            .get(order_hash)?
+            .ok_or_else(|| eyre!("order {order_hash} is not quarantined"))?;
+        if quarantined.status != ReviewStatus::Pending {
+            eyre::bail!(
+                "order {order_hash} has already been reviewed: {:?}",
+                quarantined.status
+            );
+        }
+        Ok(quarantined)
+    }
+
+    /// The path of the file storing a quarantined Order.
+    fn path(&self, order_hash: &OrderHash) -> PathBuf {
+        self.dir.join(format!("{order_hash}.json"))
+    }
+
+    /// Write a quarantined Order to the store.
+    fn save(&self, quarantined: &QuarantinedOrder) -> Result<(), Error> {
+        let path = self.path(&quarantined.order_hash);
+        fs::write(&path, serde_json::to_string_pretty(quarantined)?)
+            .wrap_err_with(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// A fingerprint of an Order's Inputs and Outputs, ignoring its owner, nonce, and deadline, so
+/// copies of the same Order share a fingerprint.
+fn fingerprint(order: &SignedOrder) -> B256 {
+    let mut hasher = Keccak256::new();
+    for input in &order.permit.permit.permitted {
+        hasher.update(input.token);
+        hasher.update(input.amount.to_be_bytes::<32>());
+    }
+    for output in &order.outputs {
+        hasher.update(output.token);
+        hasher.update(output.amount.to_be_bytes::<32>());
+        hasher.update(output.recipient);
+        hasher.update(output.chainId.to_be_bytes());
+    }
+    hasher.finalize()
+}
+
+/// Detects anomalous order flow, quarantining it for manual review instead of filling it.
+///
+/// An Order is anomalous if:
+/// - its owner or any Output recipient is a flagged address;
+/// - more than the flood threshold of identical Orders, differing only in owner, nonce, or
+///   deadline, were seen within the flood window; or
+/// - its implied rate deviates from the median rate of its token pair by more than the
+///   configured maximum, a common way to bait naive Fillers. Pairs with fewer than three
+///   Orders resting are not checked.
+///
+/// Quarantined Orders are held in a [`QuarantineStore`] until reviewed. Released Orders are
+/// evaluated normally; pending and rejected Orders are never filled. Each quarantine
+/// increments the `anomaly.quarantined` counter, labelled by `kind`.
+#[derive(Debug)]
+pub struct AnomalyDetector {
+    /// Where quarantined Orders are held.
+    store: QuarantineStore,
+    /// Number of identical Orders within the window after which copies are quarantined.
+    flood_threshold: usize,
+    /// Window over which identical Orders are counted, in seconds.
+    flood_window: u64,
+    /// Maximum deviation of an Order's rate from its pair's median, in basis points.
+    max_rate_deviation_bps: Option<u32>,
+    /// Addresses whose Orders are always quarantined.
+    flagged: HashSet<Address>,
+    /// When each Order was first seen, by fingerprint and Order hash.
+    seen: Mutex<HashMap<B256, HashMap<OrderHash, u64>>>,
+}
+
+impl AnomalyDetector {
+    /// Create a detector holding quarantined Orders in `store`, with default flood limits and
+    /// no pricing check or flagged addresses.
+    pub fn new(store: QuarantineStore) -> Self {
+        Self {
+            store,
+            flood_threshold: DEFAULT_FLOOD_THRESHOLD,
+            flood_window: DEFAULT_FLOOD_WINDOW_SECS,
+            max_rate_deviation_bps: None,
+            flagged: HashSet::new(),
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Quarantine copies of an Order beyond `threshold` identical Orders within `window`
+    /// seconds.
+    pub const fn with_flood_limit(mut self, threshold: usize, window: u64) -> Self {
+        self.flood_threshold = threshold;
+        self.flood_window = window;
+        self
+    }
+
+    /// Quarantine Orders whose rate deviates from their pair's median by more than `bps`.
+    pub const fn with_max_rate_deviation_bps(mut self, bps: Option<u32>) -> Self {
+        self.max_rate_deviation_bps = bps;
+        self
+    }
+
+    /// Quarantine every Order owned by, or paying out to, a flagged address.
+    pub fn with_flagged(mut self, flagged: HashSet<Address>) -> Self {
+        self.flagged = flagged;
+        self
+    }
+
+    /// The store holding quarantined Orders.
+    pub const fn store(&self) -> &QuarantineStore {
+        &self.store
+    }
+
+    /// Why the Order is anomalous, if it is, given the batch of Orders it arrived with.
+    fn anomaly(
+        &self,
+        order: &SignedOrder,
+        order_hash: OrderHash,
+        book: &BookAnalytics,
+        now: u64,
+    ) -> Option<(&'static str, String)> {
+        if let Some(flagged) = std::iter::once(order.permit.owner)
+            .chain(order.outputs.iter().map(|output| output.recipient))
+            .find(|address| self.flagged.contains(address))
+        {
+            return Some(("flagged", format!("flagged address {flagged}")));
+        }
+
+        let copies = {
+            let mut seen = self.seen.lock().unwrap();
+            seen.retain(|_, orders| {
+                orders.retain(|_, first_seen| *first_seen + self.flood_window >= now);
+                !orders.is_empty()
+            });
+            let copies = seen.entry(fingerprint(order)).or_default();
+            copies.entry(order_hash).or_insert(now);
+            copies.len()
+        };
+        if copies > self.flood_threshold {
+            return Some((
+                "flood",
+                format!(
+                    "{copies} identical orders within {}s, more than {}",
+                    self.flood_window, self.flood_threshold
+                ),
+            ));
+        }
+
+        let max_bps = self.max_rate_deviation_bps?;
+        let pair = TokenPair::of_order(order)?;
+        let ([input], [output]) = (&order.permit.permit.permitted[..], &order.outputs[..]) else {
+            return None;
+        };
+        let depth = book.depth(&pair)?;
+        if depth.levels.len() < MIN_PAIR_DEPTH || input.amount.is_zero() {
+            return None;
+        }
+        let median = depth.levels[depth.levels.len() / 2].rate;
+        let rate = f64::from(output.amount) / f64::from(input.amount);
+        let deviation_bps = ((rate - median) / median).abs() * BPS;
+        (deviation_bps > f64::from(max_bps)).then(|| {
+            (
+                "pricing",
+                format!("rate {rate} deviates {deviation_bps:.0} bps from pair median {median}"),
+            )
+        })
+    }
+
+    /// Remove anomalous Orders from a batch, quarantining any not already reviewed.
+    ///
+    /// Released Orders are kept; Orders pending review or rejected are removed.
+    pub fn screen(&self, orders: Vec<SignedOrder>, now: u64) -> Result<Vec<SignedOrder>, Error> {
+        let book = BookAnalytics::from_orders(&orders, now);
+        let mut kept = Vec::with_capacity(orders.len());
+        for order in orders {
+            let order_hash = OrderHash::from(&order);
+            let status = match self.store.get(&order_hash)? {
+                Some(quarantined) => Some(quarantined.status),
+                None => match self.anomaly(&order, order_hash, &book, now) {
+                    Some((kind, reason)) => {
+                        warn!(%order_hash, kind, reason, "Quarantining anomalous order");
+                        counter!("anomaly.quarantined", "kind" => kind).increment(1);
+                        Some(self.store.quarantine(&order, reason, now)?.status)
+                    }
+                    None => None,
+                },
+            };
+            match status {
+                None | Some(ReviewStatus::Released { .. }) => kept.push(order),
+                Some(status) => debug!(%order_hash, ?status, "Skipping quarantined order"),
+            }
+        }
+        Ok(kept)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{ids::OrderHash, order::SendOrder, output::Render};
+use alloy::signers::Signer;
+use chrono::Utc;
+use eyre::{Error, WrapErr, eyre};
+use init4_bin_base::deps::tracing::{info, instrument};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub use orders_core::intent::{IntentInput, IntentOutput, OrderIntent};
+
+/// The approval status of a [`PendingOrder`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// Awaiting approval.
+    Pending,
+    /// Approved, and ready to be signed and submitted.
+    Approved {
+        /// The approver.
+        approver: String,
+    },
+    /// Rejected; will never be submitted.
+    Rejected {
+        /// The approver who rejected the Order.
+        approver: String,
+        /// Why the Order was rejected.
+        reason: String,
+    },
+    /// Signed and submitted to the transaction cache.
+    Submitted {
+        /// The approver.
+        approver: String,
+        /// The hash of the signed Order.
+        order_hash: OrderHash,
+    },
+}
+
+/// An Order awaiting approval before it is signed and submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingOrder {
+    /// Unique identifier of the pending Order.
+    pub id: String,
+    /// Who proposed the Order.
+    pub proposer: String,
+    /// When the Order was proposed, as a unix timestamp.
+    pub created_at: i64,
+    /// The Order to be signed.
+    pub intent: OrderIntent,
+    /// The approval status.
+    pub status: ApprovalStatus,
+}
+
+impl Render for PendingOrder {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn table_row(&self) -> String {
+        format!(
+            "{} proposer={} status={:?}",
+            self.id, self.proposer, self.status
+        )
+    }
+}
+
+/// A file-backed store of Orders awaiting approval.
+///
+/// Each pending Order is stored as a JSON file in the store directory.
+/// An Order must be approved by someone other than its proposer before it can be submitted,
+/// for desks that require four-eyes review of outgoing Orders.
+#[derive(Debug, Clone)]
+pub struct PendingOrderStore {
+    /// The directory containing the pending Orders.
+    dir: PathBuf,
+}
+
+impl PendingOrderStore {
+    /// Open a store in the given directory, creating it if necessary.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, Error> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .wrap_err_with(|| format!("failed to create store directory {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// The directory containing the pending Orders.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Propose a new Order for approval.
+    pub fn propose(
+        &self,
+        proposer: impl Into<String>,
+        intent: OrderIntent,
+    ) -> Result<PendingOrder, Error> {
+        let created_at = Utc::now();
+        let pending = PendingOrder {
+            id: created_at.timestamp_micros().to_string(),
+            proposer: proposer.into(),
+            created_at: created_at.timestamp(),
+            intent,
+            status: ApprovalStatus::Pending,
+        };
+        self.save(&pending)?;
+        Ok(pending)
+    }
+
+    /// Load a pending Order by id.
+    pub fn get(&self, id: &str) -> Result<PendingOrder, Error> {
+        let path = self.path(id);
+        let contents =
+            fs::read_to_string(&path).wrap_err_with(|| format!("no pending order with id {id}"))?;
+        serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("invalid pending order file {}", path.display()))
+    }
+
+    /// List all Orders in the store, oldest first.
+    pub fn list(&self) -> Result<Vec<PendingOrder>, Error> {
+        let mut orders = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let contents = fs::read_to_string(&path)?;
+                orders.push(
+                    serde_json::from_str(&contents).wrap_err_with(|| {
+                        format!("invalid pending order file {}", path.display())
+                    })?,
+                );
+            }
+        }
+        orders.sort_by_key(|order: &PendingOrder| order.id.clone());
+        Ok(orders)
+    }
+
+    /// Approve a pending Order. The approver must not be the proposer.
+    pub fn approve(&self, id: &str, approver: impl Into<String>) -> Result<PendingOrder, Error> {
+        let approver = approver.into();
+        let mut pending = self.get_pending(id)?;
+        if pending.proposer == approver {
+            eyre::bail!("order {id} must be approved by someone other than its proposer");
+        }
+
+        pending.status = ApprovalStatus::Approved { approver };
+        self.save(&pending)?;
+        Ok(pending)
+    }
+
+    /// Reject a pending Order.
+    pub fn reject(
+        &self,
+        id: &str,
+        approver: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Result<PendingOrder, Error> {
+        let mut pending = self.get_pending(id)?;
+        pending.status = ApprovalStatus::Rejected {
+            approver: approver.into(),
+            reason: reason.into(),
+        };
+        self.save(&pending)?;
+        Ok(pending)
+    }
+
+    /// Sign and submit every approved Order, marking each as submitted.
+    #[instrument(skip_all)]
+    pub async fn submit_approved<S: Signer>(
+        &self,
+        sender: &SendOrder<S>,
+    ) -> Result<Vec<PendingOrder>, Error> {
+        let mut submitted = Vec::new();
+        for mut pending in self.list()? {
+            let ApprovalStatus::Approved { approver } = &pending.status else {
+                continue;
+            };
+            let approver = approver.clone();
+
+            let signed = sender
+                .sign_unsigned_order(pending.intent.to_unsigned())
+                .await?;
+            let order_hash = OrderHash::from(&signed);
+            sender.send_order(signed).await?;
+            info!(id = pending.id, %order_hash, "Submitted approved order");
+
+            pending.status = ApprovalStatus::Submitted {
+                approver,
+                order_hash,
+            };
+            self.save(&pending)?;
+            submitted.push(pending);
+        }
+        Ok(submitted)
+    }
+
+    /// Load a pending Order by id, ensuring it is still awaiting approval.
+    fn get_pending(&self, id: &str) -> Result<PendingOrder, Error> {
+        let pending = self.get(id)?;
+        if pending.status != ApprovalStatus::Pending {
+            return Err(eyre!("order {id} is not pending: {:?}", pending.status));
+        }
+        Ok(pending)
+    }
+
+    /// Write a pending Order to the store.
+    fn save(&self, pending: &PendingOrder) -> Result<(), Error> {
+        let contents = serde_json::to_string_pretty(pending)?;
+        fs::write(self.path(&pending.id), contents)?;
+        Ok(())
+    }
+
+    /// The path of a pending Order's file.
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+}
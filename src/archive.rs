@@ -0,0 +1,183 @@
+use crate::ids::{BundleId, OrderHash};
+use alloy::primitives::Bytes;
+use eyre::{Error, WrapErr, eyre};
+use init4_bin_base::deps::tracing::debug;
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use signet_types::SignedFill;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Extension of archived submission files.
+const EXTENSION: &str = "sealed";
+
+/// Exactly what was submitted in one Bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSubmission {
+    /// The id the transaction cache assigned the Bundle.
+    pub bundle_id: BundleId,
+    /// The Rollup block the Bundle targeted.
+    pub target_block: u64,
+    /// Chain time of the submission, in seconds.
+    pub submitted_at: u64,
+    /// The Orders filled.
+    pub order_hashes: Vec<OrderHash>,
+    /// The signed Fills.
+    pub fills: Vec<SignedFill>,
+    /// The signed, encoded Rollup transactions, in order.
+    pub ru_txs: Vec<Bytes>,
+    /// The signed, encoded Host transactions, in order.
+    pub host_txs: Vec<Bytes>,
+}
+
+/// An archive of the exact signed transactions and Fill signatures in every Bundle a Filler
+/// submits, so disputes about what was sent to a builder can be settled from local records.
+///
+/// Each submission is stored as its own file, encrypted with AES-256-GCM under the archive
+/// key, and authenticated against its file name, so files can't be swapped undetected. Files
+/// older than the retention period are deleted as new submissions are archived.
+#[derive(Debug)]
+pub struct SubmissionArchive {
+    /// The directory containing the archived submissions.
+    dir: PathBuf,
+    /// The archive key.
+    key: LessSafeKey,
+    /// How long submissions are kept, in seconds, if not forever.
+    retention: Option<u64>,
+    /// Source of nonces.
+    rng: SystemRandom,
+}
+
+impl SubmissionArchive {
+    /// Open an archive in the given directory, creating it if necessary, sealing submissions
+    /// with a 32-byte key.
+    pub fn open(dir: impl Into<PathBuf>, key: &[u8]) -> Result<Self, Error> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .wrap_err_with(|| format!("failed to create archive directory {}", dir.display()))?;
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| eyre!("archive key must be 32 bytes"))?;
+        Ok(Self {
+            dir,
+            key: LessSafeKey::new(key),
+            retention: None,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Delete archived submissions older than `retention` seconds.
+    pub const fn with_retention(mut self, retention: Option<u64>) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// The directory containing the archived submissions.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The file name of an archived submission.
+    fn file_name(submission: &ArchivedSubmission) -> String {
+        format!(
+            "{:020}-{}-{}.{EXTENSION}",
+            submission.submitted_at, submission.bundle_id, submission.target_block
+        )
+    }
+
+    /// Seal and store a submission, then delete any past the retention period.
+    pub fn record(&self, submission: &ArchivedSubmission) -> Result<PathBuf, Error> {
+        let name = Self::file_name(submission);
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| eyre!("failed to generate nonce"))?;
+
+        let mut sealed = serde_json::to_vec(submission)?;
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(name.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| eyre!("failed to seal submission"))?;
+        let mut file = nonce.to_vec();
+        file.extend(sealed);
+
+        let path = self.dir.join(&name);
+        fs::write(&path, file).wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        debug!(path = %path.display(), "Archived submission");
+
+        if let Some(retention) = self.retention {
+            self.prune(submission.submitted_at.saturating_sub(retention))?;
+        }
+        Ok(path)
+    }
+
+    /// Read and unseal an archived submission.
+    pub fn read(&self, path: impl AsRef<Path>) -> Result<ArchivedSubmission, Error> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| eyre!("invalid archive file {}", path.display()))?;
+        let mut sealed =
+            fs::read(path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+        if sealed.len() < NONCE_LEN {
+            eyre::bail!("archive file {} is truncated", path.display());
+        }
+        let mut contents = sealed.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&sealed)
+            .map_err(|_| eyre!("invalid nonce in {}", path.display()))?;
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(name.as_bytes()), &mut contents)
+            .map_err(|_| {
+                eyre!(
+                    "archive file {} was modified or sealed with another key",
+                    path.display()
+                )
+            })?;
+        serde_json::from_slice(plaintext)
+            .wrap_err_with(|| format!("invalid submission in {}", path.display()))
+    }
+
+    /// Paths of every archived submission, oldest first.
+    pub fn list(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION) {
+                paths.push(path);
+            }
+        }
+        // names start with the zero-padded submission time
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Delete archived submissions made before chain time `cutoff`, returning how many.
+    pub fn prune(&self, cutoff: u64) -> Result<usize, Error> {
+        let mut pruned = 0;
+        for path in self.list()? {
+            let submitted_at = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.split('-').next())
+                .and_then(|time| time.parse::<u64>().ok());
+            if submitted_at.is_some_and(|submitted_at| submitted_at < cutoff) {
+                fs::remove_file(&path)
+                    .wrap_err_with(|| format!("failed to delete {}", path.display()))?;
+                pruned += 1;
+            }
+        }
+        if pruned > 0 {
+            debug!(pruned, cutoff, "Pruned archived submissions");
+        }
+        Ok(pruned)
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{contracts::IERC20, provider::TxSenderProvider};
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use eyre::Error;
+use init4_bin_base::{
+    deps::{
+        metrics::{counter, gauge},
+        tracing::{debug, error, instrument, warn},
+    },
+    utils::from_env::FromEnv,
+};
+use signet_constants::SignetConstants;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Configuration for periodic signer balance monitoring.
+#[derive(Debug, Clone, Copy, FromEnv)]
+pub struct BalanceMonitorConfig {
+    /// Interval between balance checks, in milliseconds. Monitoring is disabled if unset.
+    #[from_env(
+        var = "BALANCE_POLL_INTERVAL_MS",
+        desc = "Interval between signer balance checks, in ms",
+        optional
+    )]
+    pub poll_interval_ms: Option<u64>,
+    /// Warn when the native gas balance on either chain falls below this amount, in wei.
+    #[from_env(
+        var = "MIN_GAS_BALANCE_WEI",
+        desc = "Low gas balance warning threshold, in wei",
+        optional
+    )]
+    pub min_gas_balance: Option<u64>,
+    /// Warn when the WETH balance on either chain falls below this amount, in wei.
+    #[from_env(
+        var = "MIN_WETH_BALANCE_WEI",
+        desc = "Low WETH balance warning threshold, in wei",
+        optional
+    )]
+    pub min_weth_balance: Option<u64>,
+}
+
+/// A single balance watched by the [`BalanceMonitor`].
+#[derive(Debug, Clone)]
+struct WatchedBalance {
+    /// Name of the chain, used as a metric label.
+    chain: &'static str,
+    /// Provider for the chain.
+    provider: TxSenderProvider,
+    /// The token to watch, or `None` for the native gas token.
+    token: Option<Address>,
+    /// Balance below which a low-balance warning is raised.
+    threshold: U256,
+}
+
+impl WatchedBalance {
+    /// Label used for the token in metrics and logs.
+    fn token_label(&self) -> String {
+        self.token
+            .map(|token| token.to_string())
+            .unwrap_or_else(|| "native".to_string())
+    }
+
+    /// Fetch the current balance of the owner.
+    async fn fetch(&self, owner: Address) -> Result<U256, Error> {
+        match self.token {
+            Some(token) => Ok(IERC20::new(token, &self.provider)
+                .balanceOf(owner)
+                .call()
+                .await?),
+            None => Ok(self.provider.get_balance(owner).await?),
+        }
+    }
+}
+
+/// Periodically exports the signer's native and token balances on each chain as gauges,
+/// warning when a balance falls below its configured threshold.
+///
+/// Balances are exported as the `signer.balance` gauge, labelled by `chain` and `token`.
+/// Each low-balance observation increments the `signer.low_balance` counter,
+/// which alerting rules can be built on.
+#[derive(Debug)]
+pub struct BalanceMonitor {
+    /// The address whose balances are watched.
+    owner: Address,
+    /// Interval between balance checks.
+    interval: Duration,
+    /// The balances to watch.
+    balances: Vec<WatchedBalance>,
+}
+
+impl BalanceMonitor {
+    /// Create a new BalanceMonitor for the given owner, checking balances on the given interval.
+    pub const fn new(owner: Address, interval: Duration) -> Self {
+        Self {
+            owner,
+            interval,
+            balances: Vec::new(),
+        }
+    }
+
+    /// Create a BalanceMonitor watching the native gas token and WETH on both the Host and Rollup.
+    ///
+    /// Returns `None` if no poll interval is configured.
+    pub fn from_config(
+        config: &BalanceMonitorConfig,
+        owner: Address,
+        constants: &SignetConstants,
+        ru_provider: TxSenderProvider,
+        host_provider: TxSenderProvider,
+    ) -> Option<Self> {
+        let interval = Duration::from_millis(config.poll_interval_ms?);
+        let min_gas = U256::from(config.min_gas_balance.unwrap_or_default());
+        let min_weth = U256::from(config.min_weth_balance.unwrap_or_default());
+
+        Some(
+            Self::new(owner, interval)
+                .watch_native("rollup", ru_provider.clone(), min_gas)
+                .watch_token(
+                    "rollup",
+                    ru_provider,
+                    constants.rollup().tokens().weth(),
+                    min_weth,
+                )
+                .watch_native("host", host_provider.clone(), min_gas)
+                .watch_token(
+                    "host",
+                    host_provider,
+                    constants.host().tokens().weth(),
+                    min_weth,
+                ),
+        )
+    }
+
+    /// Watch the native gas token balance on a chain.
+    pub fn watch_native(
+        mut self,
+        chain: &'static str,
+        provider: TxSenderProvider,
+        threshold: U256,
+    ) -> Self {
+        self.balances.push(WatchedBalance {
+            chain,
+            provider,
+            token: None,
+            threshold,
+        });
+        self
+    }
+
+    /// Watch an ERC20 token balance on a chain.
+    pub fn watch_token(
+        mut self,
+        chain: &'static str,
+        provider: TxSenderProvider,
+        token: Address,
+        threshold: U256,
+    ) -> Self {
+        self.balances.push(WatchedBalance {
+            chain,
+            provider,
+            token: Some(token),
+            threshold,
+        });
+        self
+    }
+
+    /// Check every watched balance once, exporting gauges and warning on low balances.
+    #[instrument(skip_all, fields(owner = %self.owner))]
+    pub async fn check(&self) -> Result<(), Error> {
+        for watched in &self.balances {
+            let token = watched.token_label();
+            let balance = watched.fetch(self.owner).await?;
+            debug!(chain = watched.chain, %token, %balance, "Fetched balance");
+
+            gauge!("signer.balance", "chain" => watched.chain, "token" => token.clone())
+                .set(f64::from(balance));
+
+            if balance < watched.threshold {
+                warn!(
+                    chain = watched.chain,
+                    %token,
+                    %balance,
+                    threshold = %watched.threshold,
+                    "Signer balance below threshold"
+                );
+                counter!("signer.low_balance", "chain" => watched.chain, "token" => token)
+                    .increment(1);
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawn a task that checks balances on the configured interval, forever.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                if let Err(err) = self.check().await {
+                    error!(%err, "Failed to check signer balances");
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    contracts::{IERC20, IPassage},
+    provider::TxSenderProvider,
+};
+use alloy::{
+    primitives::{Address, B256, U256},
+    providers::{Provider, WalletProvider},
+};
+use eyre::Error;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{info, instrument, warn},
+};
+use signet_constants::SignetConstants;
+
+/// The status of a [`BridgeTransfer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeStatus {
+    /// Sent, and awaiting inclusion on the Host.
+    Pending,
+    /// Included on the Host. The tokens are credited on the Rollup in the corresponding
+    /// Rollup block.
+    Confirmed,
+    /// Reverted on the Host. The tokens were not moved, and the transfer may be retried.
+    Failed,
+}
+
+/// A transfer of tokens from the Host to the Rollup, through the Passage contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeTransfer {
+    /// The token transferred, or `None` for native Ether.
+    pub token: Option<Address>,
+    /// The amount transferred.
+    pub amount: U256,
+    /// The hash of the most recent Host transaction for the transfer.
+    pub tx_hash: B256,
+    /// The current status.
+    pub status: BridgeStatus,
+    /// How many times the transfer has been sent.
+    pub attempts: u32,
+}
+
+/// Moves inventory from the Host to the Rollup by entering it through the Passage contract,
+/// tracking each transfer until it is included on the Host.
+///
+/// Transfers that revert can be resent with [`Bridge::retry_failed`]. Moving inventory from
+/// the Rollup to the Host is done with an Order, through
+/// [`SendOrder`](crate::order::SendOrder), like any other cross-chain swap.
+#[derive(Debug)]
+pub struct Bridge {
+    /// Provider for the Host, used to send and track transfers.
+    host_provider: TxSenderProvider,
+    /// The Passage contract to enter through.
+    passage: Address,
+    /// The Rollup chain id.
+    ru_chain_id: u64,
+    /// The Rollup address credited with transferred tokens.
+    recipient: Address,
+    /// Transfers sent, in order.
+    transfers: Vec<BridgeTransfer>,
+}
+
+impl Bridge {
+    /// Create a new Bridge entering through the Passage contract in the constants, crediting
+    /// `recipient` on the Rollup.
+    pub fn new(
+        host_provider: TxSenderProvider,
+        constants: &SignetConstants,
+        recipient: Address,
+    ) -> Result<Self, Error> {
+        let passage = constants.host().passage();
+        if passage.is_zero() {
+            eyre::bail!("no Passage contract configured for the host; set one with `with_passage`");
+        }
+        Ok(Self {
+            host_provider,
+            passage,
+            ru_chain_id: constants.rollup().chain_id(),
+            recipient,
+            transfers: Vec::new(),
+        })
+    }
+
+    /// Enter through a different Passage contract, e.g. for custom deployments.
+    pub const fn with_passage(mut self, passage: Address) -> Self {
+        self.passage = passage;
+        self
+    }
+
+    /// All transfers sent, in order. Transfer ids are indices into this list.
+    pub fn transfers(&self) -> &[BridgeTransfer] {
+        &self.transfers
+    }
+
+    /// Send `amount` of `token` (or native Ether, if `None`) to the Rollup, returning the
+    /// transfer id.
+    #[instrument(skip(self))]
+    pub async fn enter(&mut self, token: Option<Address>, amount: U256) -> Result<usize, Error> {
+        let tx_hash = self.send(token, amount).await?;
+        self.transfers.push(BridgeTransfer {
+            token,
+            amount,
+            tx_hash,
+            status: BridgeStatus::Pending,
+            attempts: 1,
+        });
+        Ok(self.transfers.len() - 1)
+    }
+
+    /// Send the Passage transaction for a transfer, approving the Passage contract to spend
+    /// the token first if needed.
+    async fn send(&self, token: Option<Address>, amount: U256) -> Result<B256, Error> {
+        let passage = IPassage::new(self.passage, &self.host_provider);
+        let ru_chain_id = U256::from(self.ru_chain_id);
+
+        let pending = match token {
+            None => {
+                passage
+                    .enter(ru_chain_id, self.recipient)
+                    .value(amount)
+                    .send()
+                    .await?
+            }
+            Some(token) => {
+                let erc20 = IERC20::new(token, &self.host_provider);
+                let owner = self.host_provider.default_signer_address();
+                if erc20.allowance(owner, self.passage).call().await? < amount {
+                    // the approval must land before the enter can be estimated
+                    erc20
+                        .approve(self.passage, amount)
+                        .send()
+                        .await?
+                        .watch()
+                        .await?;
+                }
+                passage
+                    .enterToken(ru_chain_id, self.recipient, token, amount)
+                    .send()
+                    .await?
+            }
+        };
+
+        let tx_hash = *pending.tx_hash();
+        info!(%tx_hash, ?token, %amount, "Bridge transfer sent");
+        counter!("bridge.transfers_sent").increment(1);
+        Ok(tx_hash)
+    }
+
+    /// Check each pending transfer's Host receipt, marking it confirmed or failed.
+    #[instrument(skip(self))]
+    pub async fn poll(&mut self) -> Result<(), Error> {
+        for transfer in &mut self.transfers {
+            if transfer.status != BridgeStatus::Pending {
+                continue;
+            }
+            let Some(receipt) = self
+                .host_provider
+                .get_transaction_receipt(transfer.tx_hash)
+                .await?
+            else {
+                continue;
+            };
+            if receipt.status() {
+                info!(tx_hash = %transfer.tx_hash, "Bridge transfer confirmed");
+                transfer.status = BridgeStatus::Confirmed;
+            } else {
+                warn!(tx_hash = %transfer.tx_hash, "Bridge transfer reverted");
+                counter!("bridge.transfers_failed").increment(1);
+                transfer.status = BridgeStatus::Failed;
+            }
+        }
+        Ok(())
+    }
+
+    /// Resend every failed transfer that has been sent fewer than `max_attempts` times.
+    #[instrument(skip(self))]
+    pub async fn retry_failed(&mut self, max_attempts: u32) -> Result<(), Error> {
+        for id in 0..self.transfers.len() {
+            let transfer = &self.transfers[id];
+            if transfer.status != BridgeStatus::Failed || transfer.attempts >= max_attempts {
+                continue;
+            }
+            let tx_hash = self.send(transfer.token, transfer.amount).await?;
+            let transfer = &mut self.transfers[id];
+            transfer.tx_hash = tx_hash;
+            transfer.status = BridgeStatus::Pending;
+            transfer.attempts += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use alloy::{
+    consensus::SignableTransaction,
+    network::TxSigner,
+    primitives::{Address, B256, ChainId, Signature},
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    signers::{self, Signer},
+    transports::{Transport, TransportError, TransportErrorKind, TransportFut},
+};
+use async_trait::async_trait;
+use init4_bin_base::deps::{metrics::counter, tracing::warn};
+use std::{
+    error::Error as StdError,
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+/// A failure injected by [`Faults`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InjectedFault {
+    /// The kind of call the fault was injected into, e.g. `rpc`.
+    pub target: &'static str,
+}
+
+impl fmt::Display for InjectedFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "injected {} fault", self.target)
+    }
+}
+
+impl StdError for InjectedFault {}
+
+/// Randomly delays or fails calls, to exercise retry and failure handling under adverse
+/// conditions in tests.
+///
+/// Faults are drawn from a seeded generator, so a failing run can be reproduced with the same
+/// seed and call sequence. The generator is shared by every clone, so one `Faults` can be
+/// injected into RPC calls with a [`ChaosLayer`], signing with a [`ChaosSigner`], and
+/// transaction cache calls with `Filler::with_faults`. Injected delays increment the
+/// `chaos.delays` counter and failures the `chaos.failures` counter, labelled by `target`.
+#[derive(Debug, Clone)]
+pub struct Faults {
+    /// Probability that a call is delayed.
+    delay_probability: f64,
+    /// Maximum delay of a delayed call. Delays are uniform up to this bound.
+    max_delay: Duration,
+    /// Probability that a call fails.
+    failure_probability: f64,
+    /// State of the shared generator.
+    state: Arc<Mutex<u64>>,
+}
+
+impl Faults {
+    /// Inject no faults, drawing from a generator seeded with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            delay_probability: 0.0,
+            max_delay: Duration::ZERO,
+            failure_probability: 0.0,
+            state: Arc::new(Mutex::new(seed)),
+        }
+    }
+
+    /// Delay calls with the given probability, by up to `max_delay`.
+    pub const fn with_delays(mut self, probability: f64, max_delay: Duration) -> Self {
+        self.delay_probability = probability;
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Fail calls with the given probability, after any delay.
+    pub const fn with_failures(mut self, probability: f64) -> Self {
+        self.failure_probability = probability;
+        self
+    }
+
+    /// Draw a uniform value in `[0, 1)` from the shared generator (splitmix64).
+    fn next_f64(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Draw the faults for one call: an optional delay, and whether it fails.
+    fn draw(&self) -> (Option<Duration>, bool) {
+        let delay = (self.next_f64() < self.delay_probability)
+            .then(|| self.max_delay.mul_f64(self.next_f64()));
+        let fail = self.next_f64() < self.failure_probability;
+        (delay, fail)
+    }
+
+    /// Delay or fail a call into `target`, as drawn.
+    pub async fn inject(&self, target: &'static str) -> Result<(), InjectedFault> {
+        let (delay, fail) = self.draw();
+        if let Some(delay) = delay {
+            counter!("chaos.delays", "target" => target).increment(1);
+            tokio::time::sleep(delay).await;
+        }
+        if fail {
+            warn!(target, "Injecting fault");
+            counter!("chaos.failures", "target" => target).increment(1);
+            return Err(InjectedFault { target });
+        }
+        Ok(())
+    }
+}
+
+/// A transport layer injecting [`Faults`] into RPC calls made through a provider.
+#[derive(Debug, Clone)]
+pub struct ChaosLayer {
+    /// The faults to inject.
+    faults: Faults,
+}
+
+impl ChaosLayer {
+    /// Inject `faults` into every RPC call.
+    pub const fn new(faults: Faults) -> Self {
+        Self { faults }
+    }
+}
+
+impl<S> Layer<S> for ChaosLayer {
+    type Service = ChaosService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ChaosService {
+            inner,
+            faults: self.faults.clone(),
+        }
+    }
+}
+
+/// The transport service produced by [`ChaosLayer`].
+#[derive(Debug, Clone)]
+pub struct ChaosService<S> {
+    /// The wrapped transport.
+    inner: S,
+    /// The faults to inject.
+    faults: Faults,
+}
+
+impl<S> Service<RequestPacket> for ChaosService<S>
+where
+    S: Transport + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let faults = self.faults.clone();
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            faults
+                .inject("rpc")
+                .await
+                .map_err(TransportErrorKind::custom)?;
+            inner.call(request).await
+        })
+    }
+}
+
+/// A signer wrapper injecting [`Faults`] into every signing operation.
+#[derive(Debug, Clone)]
+pub struct ChaosSigner<S> {
+    /// The wrapped signer.
+    inner: S,
+    /// The faults to inject.
+    faults: Faults,
+}
+
+impl<S> ChaosSigner<S> {
+    /// Wrap a signer, injecting `faults` into each signing operation.
+    pub const fn new(inner: S, faults: Faults) -> Self {
+        Self { inner, faults }
+    }
+}
+
+#[async_trait]
+impl<S> Signer for ChaosSigner<S>
+where
+    S: Signer + Send + Sync,
+{
+    async fn sign_hash(&self, hash: &B256) -> signers::Result<Signature> {
+        self.faults
+            .inject("signer")
+            .await
+            .map_err(signers::Error::other)?;
+        self.inner.sign_hash(hash).await
+    }
+
+    fn address(&self) -> Address {
+        self.inner.address()
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.inner.chain_id()
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.inner.set_chain_id(chain_id);
+    }
+}
+
+#[async_trait]
+impl<S> TxSigner<Signature> for ChaosSigner<S>
+where
+    S: TxSigner<Signature> + Send + Sync,
+{
+    fn address(&self) -> Address {
+        TxSigner::address(&self.inner)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> signers::Result<Signature> {
+        self.faults
+            .inject("signer")
+            .await
+            .map_err(signers::Error::other)?;
+        self.inner.sign_transaction(tx).await
+    }
+}
@@ -0,0 +1,89 @@
+use crate::provider::TxSenderProvider;
+use alloy::{eips::BlockNumberOrTag, providers::Provider};
+use chrono::Utc;
+use eyre::{Error, eyre};
+use init4_bin_base::deps::tracing::{debug, warn};
+
+/// Maximum tolerated difference between the local clock and chain time, in seconds.
+pub const MAX_CLOCK_SKEW_SECS: u64 = 30;
+
+/// Fetch the number and timestamp of the latest block on the provider's chain.
+pub async fn latest_block(provider: &TxSenderProvider) -> Result<(u64, u64), Error> {
+    provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .map(|block| (block.header.number, block.header.timestamp))
+        .ok_or_else(|| eyre!("latest block not found"))
+}
+
+/// Fetch the timestamp of the latest block on the provider's chain.
+pub async fn latest_block_timestamp(provider: &TxSenderProvider) -> Result<u64, Error> {
+    latest_block(provider).await.map(|(_, timestamp)| timestamp)
+}
+
+/// Compute how far the local clock is ahead of chain time, in seconds.
+///
+/// A negative value means the local clock is behind chain time.
+pub async fn clock_skew(provider: &TxSenderProvider) -> Result<i64, Error> {
+    let chain_time = latest_block_timestamp(provider).await?;
+    Ok(Utc::now().timestamp() - chain_time as i64)
+}
+
+/// Fetch the current chain time, to use as the reference clock for deadline calculations.
+///
+/// Deadlines are enforced onchain against block timestamps, so chain time is used rather than
+/// the local clock. Warns if the local clock has drifted from chain time by more than
+/// [`MAX_CLOCK_SKEW_SECS`].
+pub async fn chain_time(provider: &TxSenderProvider) -> Result<u64, Error> {
+    let chain_time = latest_block_timestamp(provider).await?;
+    let skew = Utc::now().timestamp() - chain_time as i64;
+    debug!(chain_time, skew, "Fetched chain time");
+
+    if skew.unsigned_abs() > MAX_CLOCK_SKEW_SECS {
+        warn!(
+            chain_time,
+            skew, "Local clock has drifted from chain time; using chain time for deadlines"
+        );
+    }
+
+    Ok(chain_time)
+}
+
+/// How far the Rollup head lags behind the wall clock and the Host head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadLag {
+    /// The latest Rollup block number.
+    pub ru_block_number: u64,
+    /// The timestamp of the latest Rollup block.
+    pub ru_timestamp: u64,
+    /// Seconds the latest Rollup block is behind the local clock.
+    pub behind_wall_clock: i64,
+    /// Seconds the latest Rollup block is behind the latest Host block.
+    pub behind_host: i64,
+}
+
+impl HeadLag {
+    /// The larger of the two lags, in seconds.
+    pub fn max_lag(&self) -> i64 {
+        self.behind_wall_clock.max(self.behind_host)
+    }
+}
+
+/// Measure how far the Rollup head lags behind the wall clock and the Host head.
+///
+/// Rollup blocks are produced alongside Host blocks, so a Rollup head that falls behind either
+/// indicates a stale Rollup RPC, and Bundles targeted from it will never land.
+pub async fn head_lag(
+    ru_provider: &TxSenderProvider,
+    host_provider: &TxSenderProvider,
+) -> Result<HeadLag, Error> {
+    let (ru_block_number, ru_timestamp) = latest_block(ru_provider).await?;
+    let host_timestamp = latest_block_timestamp(host_provider).await?;
+
+    Ok(HeadLag {
+        ru_block_number,
+        ru_timestamp,
+        behind_wall_clock: Utc::now().timestamp() - ru_timestamp as i64,
+        behind_host: host_timestamp as i64 - ru_timestamp as i64,
+    })
+}
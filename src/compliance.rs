@@ -0,0 +1,189 @@
+use crate::ids::{BundleId, OrderHash};
+use alloy::primitives::{Address, B256, keccak256};
+use eyre::{Error, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// The Filler's decision on an Order it considered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    /// The Order was selected for filling.
+    Accepted,
+    /// The Order was not filled.
+    Skipped,
+}
+
+/// An event recorded in a [`ComplianceLog`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ComplianceEvent {
+    /// The Filler considered an Order for filling.
+    OrderConsidered {
+        /// The Order considered.
+        order_hash: OrderHash,
+        /// The owner of the Order.
+        owner: Address,
+        /// Whether the Order was selected for filling.
+        decision: Decision,
+        /// Why the Order was skipped, if it was.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// The Filler submitted a fill of Orders.
+    FillExecuted {
+        /// The Orders filled.
+        order_hashes: Vec<OrderHash>,
+        /// The Bundles submitted, one per target block.
+        bundle_ids: Vec<BundleId>,
+    },
+}
+
+/// The hashed contents of a [`ComplianceRecord`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceEntry {
+    /// Position of the record in the log, from zero.
+    pub seq: u64,
+    /// Chain time of the event, in seconds.
+    pub timestamp: u64,
+    /// The Filler that recorded the event.
+    pub filler: Address,
+    /// What happened.
+    #[serde(flatten)]
+    pub event: ComplianceEvent,
+    /// Hash of the previous record, or zero for the first.
+    pub prev_hash: B256,
+}
+
+impl ComplianceEntry {
+    /// The record hash: keccak256 of the previous hash followed by the entry's JSON.
+    pub fn hash(&self) -> Result<B256, Error> {
+        let mut preimage = self.prev_hash.to_vec();
+        preimage.extend(serde_json::to_vec(self)?);
+        Ok(keccak256(preimage))
+    }
+}
+
+/// One line of a [`ComplianceLog`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceRecord {
+    /// The hashed contents.
+    #[serde(flatten)]
+    pub entry: ComplianceEntry,
+    /// Hash of this record, chaining it to the previous one.
+    pub hash: B256,
+}
+
+/// The end of the log: the next sequence number, and the hash of the last record.
+#[derive(Debug)]
+struct ChainHead {
+    /// The open log file, appended to.
+    file: File,
+    /// Sequence number of the next record.
+    seq: u64,
+    /// Hash of the last record, or zero if the log is empty.
+    hash: B256,
+}
+
+/// An append-only, hash-chained log of every Order a Filler considered and every fill it
+/// executed, for operators subject to audit requirements.
+///
+/// Records are written as JSON Lines. Each record's hash covers the previous record's hash, so
+/// removing, reordering, or editing any record breaks the chain from that point on; see
+/// [`ComplianceLog::verify`]. Records are written before the action they describe proceeds, so
+/// a fill is never sent if its decisions cannot be recorded.
+#[derive(Debug)]
+pub struct ComplianceLog {
+    /// The log file.
+    path: PathBuf,
+    /// The end of the chain.
+    head: Mutex<ChainHead>,
+}
+
+impl ComplianceLog {
+    /// Open the log at `path`, verifying any records already there and appending after them.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let (seq, hash) = if path.exists() {
+            let records = Self::verify(&path)?;
+            (
+                records.len() as u64,
+                records.last().map(|record| record.hash).unwrap_or_default(),
+            )
+        } else {
+            (0, B256::ZERO)
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .wrap_err_with(|| format!("failed to open {}", path.display()))?;
+        Ok(Self {
+            path,
+            head: Mutex::new(ChainHead { file, seq, hash }),
+        })
+    }
+
+    /// Read the log at `path`, checking that every record chains to the one before it.
+    ///
+    /// Returns the records, or an error identifying the first record that breaks the chain.
+    pub fn verify(path: impl AsRef<Path>) -> Result<Vec<ComplianceRecord>, Error> {
+        let path = path.as_ref();
+        let file =
+            File::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+
+        let mut records = Vec::new();
+        let mut prev_hash = B256::ZERO;
+        for (line_number, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            let record: ComplianceRecord = serde_json::from_str(&line)
+                .wrap_err_with(|| format!("invalid record on line {}", line_number + 1))?;
+            if record.entry.seq != records.len() as u64 || record.entry.prev_hash != prev_hash {
+                eyre::bail!("record on line {} is out of sequence", line_number + 1);
+            }
+            if record.entry.hash()? != record.hash {
+                eyre::bail!("record on line {} has been modified", line_number + 1);
+            }
+            prev_hash = record.hash;
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// The log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an event recorded by `filler` at chain time `timestamp`, returning its hash.
+    pub fn record(
+        &self,
+        filler: Address,
+        timestamp: u64,
+        event: ComplianceEvent,
+    ) -> Result<B256, Error> {
+        let mut head = self.head.lock().unwrap();
+        let entry = ComplianceEntry {
+            seq: head.seq,
+            timestamp,
+            filler,
+            event,
+            prev_hash: head.hash,
+        };
+        let hash = entry.hash()?;
+        let mut line = serde_json::to_vec(&ComplianceRecord { entry, hash })?;
+        line.push(b'\n');
+        head.file
+            .write_all(&line)
+            .and_then(|()| head.file.sync_data())
+            .wrap_err_with(|| format!("failed to write {}", self.path.display()))?;
+        head.seq += 1;
+        head.hash = hash;
+        Ok(hash)
+    }
+}
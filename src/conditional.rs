@@ -0,0 +1,221 @@
+use crate::{
+    analytics::ChainToken, approval::OrderIntent, clock::chain_time, contracts::IERC20,
+    ids::OrderHash, order::SendOrder, provider::TxSenderProvider, quoting::PriceOracle,
+};
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+    signers::Signer,
+};
+use eyre::Error;
+use init4_bin_base::deps::tracing::{debug, error, info, instrument, warn};
+use serde::{Deserialize, Serialize};
+use signet_types::SignedOrder;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// A condition that must hold before a [`ConditionalOrder`] is signed and submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Trigger {
+    /// The oracle price of `base` in `quote` is at or above `threshold`.
+    PriceAbove {
+        /// The priced token.
+        base: ChainToken,
+        /// The token the price is expressed in.
+        quote: ChainToken,
+        /// The price threshold, in raw `quote` units per raw `base` unit.
+        threshold: f64,
+    },
+    /// The oracle price of `base` in `quote` is at or below `threshold`, e.g. a stop-loss.
+    PriceBelow {
+        /// The priced token.
+        base: ChainToken,
+        /// The token the price is expressed in.
+        quote: ChainToken,
+        /// The price threshold, in raw `quote` units per raw `base` unit.
+        threshold: f64,
+    },
+    /// The owner's Rollup balance of `token` is at or above `threshold`.
+    BalanceAbove {
+        /// The token, or `None` for the native gas token.
+        token: Option<Address>,
+        /// The balance threshold.
+        threshold: U256,
+    },
+}
+
+/// An Order that is only signed and submitted once its [`Trigger`] holds.
+///
+/// The Order is signed with the intent's deadline; once the deadline has passed, the
+/// conditional Order is dropped without being submitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionalOrder {
+    /// The Order to submit.
+    pub intent: OrderIntent,
+    /// The condition to wait for.
+    pub trigger: Trigger,
+}
+
+/// Evaluates [`ConditionalOrder`]s locally, signing and submitting each Order once its trigger
+/// holds.
+///
+/// Signet Orders have no conditional semantics of their own: a signed Order is fillable as soon
+/// as it is sent. Conditions are therefore checked before signing, and each conditional Order
+/// fires at most once.
+#[derive(Debug)]
+pub struct ConditionalScheduler<S: Signer, O> {
+    /// Signs and sends the Orders.
+    sender: SendOrder<S>,
+    /// Prices tokens for price triggers.
+    oracle: O,
+    /// Provider for the Rollup, used for balance triggers and chain time.
+    ru_provider: TxSenderProvider,
+    /// The address whose balances are checked.
+    owner: Address,
+    /// Conditional Orders that have not yet fired.
+    pending: Vec<ConditionalOrder>,
+}
+
+impl<S, O> ConditionalScheduler<S, O>
+where
+    S: Signer,
+    O: PriceOracle,
+{
+    /// Create a new ConditionalScheduler, checking balance triggers against `owner`.
+    pub const fn new(
+        sender: SendOrder<S>,
+        oracle: O,
+        ru_provider: TxSenderProvider,
+        owner: Address,
+    ) -> Self {
+        Self {
+            sender,
+            oracle,
+            ru_provider,
+            owner,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Add a conditional Order.
+    pub fn add(&mut self, order: ConditionalOrder) {
+        self.pending.push(order);
+    }
+
+    /// Conditional Orders that have not yet fired.
+    pub fn pending(&self) -> &[ConditionalOrder] {
+        &self.pending
+    }
+
+    /// Check whether a trigger holds.
+    async fn holds(&self, trigger: &Trigger) -> Result<bool, Error> {
+        match *trigger {
+            Trigger::PriceAbove {
+                base,
+                quote,
+                threshold,
+            } => Ok(self.oracle.price(base, quote).await? >= threshold),
+            Trigger::PriceBelow {
+                base,
+                quote,
+                threshold,
+            } => Ok(self.oracle.price(base, quote).await? <= threshold),
+            Trigger::BalanceAbove { token, threshold } => {
+                let balance = match token {
+                    Some(token) => {
+                        IERC20::new(token, &self.ru_provider)
+                            .balanceOf(self.owner)
+                            .call()
+                            .await?
+                    }
+                    None => self.ru_provider.get_balance(self.owner).await?,
+                };
+                Ok(balance >= threshold)
+            }
+        }
+    }
+
+    /// Sign and submit the Order described by an intent.
+    async fn submit(&self, intent: &OrderIntent) -> Result<SignedOrder, Error> {
+        let signed = self
+            .sender
+            .sign_unsigned_order(intent.to_unsigned())
+            .await?;
+        self.sender.send_order(signed.clone()).await?;
+        Ok(signed)
+    }
+
+    /// Evaluate every pending conditional Order at the given time, signing and submitting those
+    /// whose trigger holds, and dropping those whose deadline has passed.
+    ///
+    /// Returns the Orders submitted.
+    #[instrument(skip(self), fields(pending = self.pending.len()))]
+    pub async fn evaluate(&mut self, now: u64) -> Result<Vec<SignedOrder>, Error> {
+        let mut submitted = Vec::new();
+        let mut remaining = Vec::with_capacity(self.pending.len());
+
+        for conditional in std::mem::take(&mut self.pending) {
+            if conditional.intent.deadline < now {
+                warn!(trigger = ?conditional.trigger, "Conditional order expired before triggering");
+                continue;
+            }
+
+            let fired = match self.holds(&conditional.trigger).await {
+                Ok(holds) => holds,
+                Err(err) => {
+                    // keep the order, and retry on the next evaluation
+                    error!(%err, trigger = ?conditional.trigger, "Failed to evaluate trigger");
+                    false
+                }
+            };
+            if !fired {
+                debug!(trigger = ?conditional.trigger, "Trigger does not hold");
+                remaining.push(conditional);
+                continue;
+            }
+
+            match self.submit(&conditional.intent).await {
+                Ok(signed) => {
+                    info!(
+                        order_hash = %OrderHash::from(&signed),
+                        trigger = ?conditional.trigger,
+                        "Conditional order triggered and submitted"
+                    );
+                    submitted.push(signed);
+                }
+                Err(err) => {
+                    error!(%err, trigger = ?conditional.trigger, "Failed to submit conditional order");
+                    remaining.push(conditional);
+                }
+            }
+        }
+
+        self.pending = remaining;
+        Ok(submitted)
+    }
+}
+
+impl<S, O> ConditionalScheduler<S, O>
+where
+    S: Signer + Send + Sync + 'static,
+    O: PriceOracle + Send + Sync + 'static,
+{
+    /// Spawn a task that evaluates conditional Orders on the given interval, forever, using the
+    /// Rollup chain time as the reference clock for deadlines.
+    pub fn spawn(mut self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                let evaluated = match chain_time(&self.ru_provider).await {
+                    Ok(now) => self.evaluate(now).await,
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = evaluated {
+                    error!(%err, "Failed to evaluate conditional orders");
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use alloy::primitives::Address;
+use eyre::{Error, eyre};
+use init4_bin_base::utils::from_env::FromEnv;
+use signet_constants::SignetConstants;
+
+pub use orders_core::constants::{SignetConstantsBuilder, known_constants};
+
+/// Resolve the Signet constants for a chain name.
+///
+/// Known networks resolve to their built-in constants. Any other chain name is treated as a
+/// custom deployment, whose constants are loaded from [`CustomConstantsConfig`].
+pub fn resolve_constants(chain_name: &str) -> Result<SignetConstants, Error> {
+    if let Some(constants) = known_constants(chain_name)? {
+        return Ok(constants);
+    }
+    CustomConstantsConfig::from_env()?
+        .builder()
+        .build()
+        .map_err(|e| eyre!("invalid constants for custom chain {chain_name}: {e}"))
+}
+
+/// Explicit chain constants, for private Signet deployments that are not known networks.
+#[derive(Debug, Clone, FromEnv)]
+pub struct CustomConstantsConfig {
+    /// The Host chain id.
+    #[from_env(var = "HOST_CHAIN_ID", desc = "Host chain id, for custom deployments")]
+    pub host_chain_id: u64,
+    /// The Rollup chain id.
+    #[from_env(var = "RU_CHAIN_ID", desc = "Rollup chain id, for custom deployments")]
+    pub ru_chain_id: u64,
+    /// The Host Orders contract address.
+    #[from_env(
+        var = "HOST_ORDERS_ADDRESS",
+        desc = "Host Orders contract address, for custom deployments"
+    )]
+    pub host_orders: Address,
+    /// The Rollup Orders contract address.
+    #[from_env(
+        var = "RU_ORDERS_ADDRESS",
+        desc = "Rollup Orders contract address, for custom deployments"
+    )]
+    pub ru_orders: Address,
+    /// The transaction cache URL.
+    #[from_env(
+        var = "TX_CACHE_URL",
+        desc = "Transaction cache URL, for custom deployments"
+    )]
+    pub tx_cache_url: String,
+    /// The Host WETH address.
+    #[from_env(
+        var = "HOST_WETH_ADDRESS",
+        desc = "Host WETH address, for custom deployments",
+        optional
+    )]
+    pub host_weth: Option<Address>,
+    /// The Rollup WETH address.
+    #[from_env(
+        var = "RU_WETH_ADDRESS",
+        desc = "Rollup WETH address, for custom deployments",
+        optional
+    )]
+    pub ru_weth: Option<Address>,
+}
+
+impl CustomConstantsConfig {
+    /// Create a [`SignetConstantsBuilder`] populated from this config.
+    pub fn builder(&self) -> SignetConstantsBuilder {
+        let mut builder = SignetConstantsBuilder::default()
+            .host_chain_id(self.host_chain_id)
+            .ru_chain_id(self.ru_chain_id)
+            .host_orders(self.host_orders)
+            .ru_orders(self.ru_orders)
+            .tx_cache_url(self.tx_cache_url.clone());
+        if let Some(weth) = self.host_weth {
+            builder = builder.host_weth(weth);
+        }
+        if let Some(weth) = self.ru_weth {
+            builder = builder.ru_weth(weth);
+        }
+        builder
+    }
+}
@@ -0,0 +1,68 @@
+use crate::provider::TxSenderProvider;
+use alloy::{
+    primitives::{Address, U256, address},
+    sol,
+};
+use eyre::Error;
+
+/// The canonical Permit2 address, shared by the Host and Rollup.
+pub const PERMIT2_ADDRESS: Address = address!("0x000000000022D473030F116dDEE9F6B43aC78BA3");
+
+sol! {
+    /// Minimal ERC20 interface, used to inspect balances and allowances, approve spenders, and
+    /// transfer tokens.
+    #[sol(rpc)]
+    interface IERC20 {
+        function balanceOf(address owner) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 amount) external returns (bool);
+        function transfer(address to, uint256 amount) external returns (bool);
+    }
+}
+
+sol! {
+    /// Minimal Host Passage interface, used to bridge tokens from the Host to the Rollup.
+    #[sol(rpc)]
+    interface IPassage {
+        function enter(uint256 rollupChainId, address rollupRecipient) external payable;
+        function enterToken(uint256 rollupChainId, address rollupRecipient, address token, uint256 amount) external;
+    }
+}
+
+sol! {
+    /// Minimal Permit2 interface, used to inspect unordered nonces.
+    #[sol(rpc)]
+    interface IPermit2 {
+        function nonceBitmap(address owner, uint256 wordPos) external view returns (uint256);
+    }
+}
+
+/// Check whether a Permit2 unordered nonce has been used by `owner`.
+///
+/// A used nonce means the Order signed with it was filled, or the nonce was invalidated.
+pub async fn permit2_nonce_used(
+    provider: &TxSenderProvider,
+    owner: Address,
+    nonce: U256,
+) -> Result<bool, Error> {
+    let (word_pos, bit_pos) = permit2_nonce_position(nonce);
+    let bitmap = permit2_nonce_bitmap(provider, owner, word_pos).await?;
+    Ok(bitmap.bit(bit_pos))
+}
+
+/// Split a Permit2 unordered nonce into its bitmap word position and bit position.
+pub fn permit2_nonce_position(nonce: U256) -> (U256, usize) {
+    (nonce >> 8, (nonce.as_limbs()[0] & 0xff) as usize)
+}
+
+/// Fetch one word of `owner`'s Permit2 unordered nonce bitmap. Each set bit is a used nonce.
+pub async fn permit2_nonce_bitmap(
+    provider: &TxSenderProvider,
+    owner: Address,
+    word_pos: U256,
+) -> Result<U256, Error> {
+    Ok(IPermit2::new(PERMIT2_ADDRESS, provider)
+        .nonceBitmap(owner, word_pos)
+        .call()
+        .await?)
+}
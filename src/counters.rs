@@ -0,0 +1,117 @@
+use eyre::{Error, WrapErr};
+use init4_bin_base::deps::metrics::gauge;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Orders included in submitted fills.
+pub const ORDERS_FILLED: &str = "orders_filled";
+
+/// Bundles submitted to the transaction cache.
+pub const BUNDLES_SUBMITTED: &str = "bundles_submitted";
+
+/// Counter values, by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct CounterValues {
+    /// The counters, by name.
+    counters: BTreeMap<String, u64>,
+}
+
+/// All-time and process-lifetime values of the counters.
+#[derive(Debug, Default)]
+struct CounterState {
+    /// Values since the counters file was created.
+    all_time: CounterValues,
+    /// Values since this process opened the counters file.
+    process: CounterValues,
+}
+
+/// Cumulative counters persisted to a file, so dashboards don't reset on every deploy.
+///
+/// Each counter has an all-time value, carried across restarts, and a process-lifetime value,
+/// starting from zero. Both are exported as gauges, `counters.all_time` and
+/// `counters.process`, labelled by `counter`. The file is rewritten on each increment, via a
+/// temporary file and rename, so a crash never leaves it partially written.
+#[derive(Debug)]
+pub struct PersistentCounters {
+    /// The counters file.
+    path: PathBuf,
+    /// The current values.
+    state: Mutex<CounterState>,
+}
+
+impl PersistentCounters {
+    /// Open the counters file at `path`, loading the all-time values if it exists.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let all_time: CounterValues = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+            serde_json::from_str(&contents)
+                .wrap_err_with(|| format!("invalid counters file {}", path.display()))?
+        } else {
+            CounterValues::default()
+        };
+        for (name, value) in &all_time.counters {
+            gauge!("counters.all_time", "counter" => name.clone()).set(*value as f64);
+        }
+        Ok(Self {
+            path,
+            state: Mutex::new(CounterState {
+                all_time,
+                process: CounterValues::default(),
+            }),
+        })
+    }
+
+    /// The counters file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The all-time value of a counter.
+    pub fn all_time(&self, name: &str) -> u64 {
+        let state = self.state.lock().unwrap();
+        state
+            .all_time
+            .counters
+            .get(name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The value of a counter since this process opened the counters file.
+    pub fn process(&self, name: &str) -> u64 {
+        let state = self.state.lock().unwrap();
+        state
+            .process
+            .counters
+            .get(name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Add `by` to a counter, persisting the new all-time value.
+    pub fn increment(&self, name: &'static str, by: u64) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap();
+        let all_time = state.all_time.counters.entry(name.to_string()).or_default();
+        *all_time = all_time.saturating_add(by);
+        let all_time = *all_time;
+        let process = state.process.counters.entry(name.to_string()).or_default();
+        *process = process.saturating_add(by);
+        let process = *process;
+
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&state.all_time)?)
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .wrap_err_with(|| format!("failed to write {}", self.path.display()))?;
+
+        gauge!("counters.all_time", "counter" => name).set(all_time as f64);
+        gauge!("counters.process", "counter" => name).set(process as f64);
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use eyre::{Error, eyre};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+/// Default time to wait for etcd to respond.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Response to `POST /v3/lease/grant`.
+#[derive(Debug, Deserialize)]
+struct LeaseGrant {
+    /// The lease id.
+    #[serde(rename = "ID")]
+    id: String,
+}
+
+/// Result of `POST /v3/lease/keepalive`.
+#[derive(Debug, Deserialize)]
+struct KeepAliveResult {
+    /// Seconds remaining on the lease, absent if it has expired.
+    #[serde(rename = "TTL", default)]
+    ttl: Option<String>,
+}
+
+/// Response to `POST /v3/lease/keepalive`.
+#[derive(Debug, Deserialize)]
+struct KeepAlive {
+    /// The renewed lease.
+    result: KeepAliveResult,
+}
+
+/// Response to `POST /v3/kv/txn`.
+#[derive(Debug, Deserialize)]
+struct TxnResponse {
+    /// Whether the comparisons held and the success requests ran.
+    #[serde(default)]
+    succeeded: bool,
+}
+
+/// A client for the leases and keys used to coordinate Filler replicas, through the etcd v3
+/// HTTP gateway.
+#[derive(Debug, Clone)]
+pub(crate) struct EtcdClient {
+    /// Base URL of the etcd HTTP gateway.
+    url: reqwest::Url,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl EtcdClient {
+    /// Create a new client for the etcd HTTP gateway at `url`.
+    pub(crate) fn new(mut url: reqwest::Url) -> Result<Self, Error> {
+        // request paths are joined onto the base URL, which must end in a slash
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self {
+            url,
+            client: reqwest::ClientBuilder::new().use_rustls_tls().build()?,
+        })
+    }
+
+    /// Send a request to the gateway.
+    async fn post<T>(&self, path: &str, body: serde_json::Value) -> Result<T, Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Ok(self
+            .client
+            .post(self.url.join(path)?)
+            .timeout(DEFAULT_TIMEOUT)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Grant a lease expiring after `ttl`, returning its id.
+    pub(crate) async fn grant(&self, ttl: Duration) -> Result<i64, Error> {
+        let grant: LeaseGrant = self
+            .post(
+                "v3/lease/grant",
+                json!({ "TTL": ttl.as_secs().max(1).to_string() }),
+            )
+            .await?;
+        grant
+            .id
+            .parse()
+            .map_err(|e| eyre!("invalid lease id {}: {e}", grant.id))
+    }
+
+    /// Renew a lease, returning whether it is still live.
+    pub(crate) async fn keep_alive(&self, lease: i64) -> Result<bool, Error> {
+        let keep_alive: KeepAlive = self
+            .post("v3/lease/keepalive", json!({ "ID": lease.to_string() }))
+            .await?;
+        Ok(keep_alive
+            .result
+            .ttl
+            .and_then(|ttl| ttl.parse::<i64>().ok())
+            .is_some_and(|ttl| ttl > 0))
+    }
+
+    /// Revoke a lease, deleting every key attached to it.
+    pub(crate) async fn revoke(&self, lease: i64) -> Result<(), Error> {
+        let _: serde_json::Value = self
+            .post("v3/lease/revoke", json!({ "ID": lease.to_string() }))
+            .await?;
+        Ok(())
+    }
+
+    /// Put a key attached to a lease, only if the key does not exist. Returns whether the key
+    /// was put.
+    pub(crate) async fn put_if_absent(
+        &self,
+        key: &str,
+        value: &str,
+        lease: i64,
+    ) -> Result<bool, Error> {
+        let key = BASE64.encode(key);
+        let txn: TxnResponse = self
+            .post(
+                "v3/kv/txn",
+                json!({
+                    "compare": [{ "key": key, "target": "CREATE", "create_revision": "0" }],
+                    "success": [{ "request_put": {
+                        "key": key,
+                        "value": BASE64.encode(value),
+                        "lease": lease.to_string(),
+                    } }],
+                }),
+            )
+            .await?;
+        Ok(txn.succeeded)
+    }
+
+    /// Put a key attached to a lease, only if the key already holds `value`, moving it onto
+    /// the lease. Returns whether the key was put.
+    pub(crate) async fn put_if_value(
+        &self,
+        key: &str,
+        value: &str,
+        lease: i64,
+    ) -> Result<bool, Error> {
+        let key = BASE64.encode(key);
+        let value = BASE64.encode(value);
+        let txn: TxnResponse = self
+            .post(
+                "v3/kv/txn",
+                json!({
+                    "compare": [{ "key": key, "target": "VALUE", "value": value }],
+                    "success": [{ "request_put": {
+                        "key": key,
+                        "value": value,
+                        "lease": lease.to_string(),
+                    } }],
+                }),
+            )
+            .await?;
+        Ok(txn.succeeded)
+    }
+}
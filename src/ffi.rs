@@ -0,0 +1,156 @@
+use crate::{approval::OrderIntent, constants::resolve_constants, order::SendOrder};
+use alloy::signers::local::PrivateKeySigner;
+use eyre::{Error, WrapErr, eyre};
+use signet_types::SignedOrder;
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString, c_char, c_int},
+    ptr,
+    sync::OnceLock,
+};
+use tokio::runtime::Runtime;
+
+thread_local! {
+    /// The message of the last error on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// The runtime driving asynchronous calls made through the FFI.
+fn runtime() -> Result<&'static Runtime, Error> {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .wrap_err("failed to build tokio runtime")?;
+    Ok(RUNTIME.get_or_init(|| runtime))
+}
+
+/// Record an error as the last error on this thread.
+fn set_last_error(err: &Error) {
+    let message = CString::new(format!("{err:#}").replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Read a string argument.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string valid for the duration of the call.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, Error> {
+    if ptr.is_null() {
+        eyre::bail!("{name} is null");
+    }
+    // SAFETY: the caller guarantees `ptr` is a valid NUL-terminated string
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .wrap_err_with(|| format!("{name} is not valid UTF-8"))
+}
+
+/// Sign an Order described by a JSON [`OrderIntent`].
+fn sign_order(intent: &str, chain_name: &str, private_key: &str) -> Result<String, Error> {
+    let intent: OrderIntent = serde_json::from_str(intent).wrap_err("invalid order intent")?;
+    let signer: PrivateKeySigner = private_key
+        .parse()
+        .map_err(|e| eyre!("invalid private key: {e}"))?;
+    let sender = SendOrder::new(signer, resolve_constants(chain_name)?)?;
+    let signed = runtime()?.block_on(sender.sign_unsigned_order(intent.to_unsigned()))?;
+    Ok(serde_json::to_string(&signed)?)
+}
+
+/// Send a JSON [`SignedOrder`] to the transaction cache.
+fn send_order(signed: &str, chain_name: &str) -> Result<(), Error> {
+    let signed: SignedOrder = serde_json::from_str(signed).wrap_err("invalid signed order")?;
+    // sending needs no signature, so any signer will do
+    let sender = SendOrder::new(PrivateKeySigner::random(), resolve_constants(chain_name)?)?;
+    runtime()?.block_on(sender.send_order(signed))
+}
+
+/// Sign an Order for the named chain with a hex-encoded private key.
+///
+/// `intent_json` is an [`OrderIntent`] as JSON. Returns the signed Order as JSON, to be
+/// released with [`signet_orders_string_free`], or null on error; see
+/// [`signet_orders_last_error`].
+///
+/// # Safety
+///
+/// Each argument must be null or point to a NUL-terminated string valid for the duration of
+/// the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn signet_orders_sign_order(
+    intent_json: *const c_char,
+    chain_name: *const c_char,
+    private_key: *const c_char,
+) -> *mut c_char {
+    // SAFETY: the caller upholds the string requirements of each argument
+    let result = unsafe {
+        read_str(intent_json, "intent_json").and_then(|intent| {
+            sign_order(
+                intent,
+                read_str(chain_name, "chain_name")?,
+                read_str(private_key, "private_key")?,
+            )
+        })
+    };
+    match result.and_then(|signed| Ok(CString::new(signed)?)) {
+        Ok(signed) => signed.into_raw(),
+        Err(err) => {
+            set_last_error(&err);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Send a signed Order, as JSON, to the named chain's transaction cache.
+///
+/// Returns zero on success, or non-zero on error; see [`signet_orders_last_error`].
+///
+/// # Safety
+///
+/// Each argument must be null or point to a NUL-terminated string valid for the duration of
+/// the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn signet_orders_send_order(
+    signed_json: *const c_char,
+    chain_name: *const c_char,
+) -> c_int {
+    // SAFETY: the caller upholds the string requirements of each argument
+    let result = unsafe {
+        read_str(signed_json, "signed_json")
+            .and_then(|signed| send_order(signed, read_str(chain_name, "chain_name")?))
+    };
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            set_last_error(&err);
+            -1
+        }
+    }
+}
+
+/// The message of the last error on the calling thread, or null if there has been none.
+///
+/// The returned string must be released with [`signet_orders_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn signet_orders_last_error() -> *mut c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .clone()
+            .map_or(ptr::null_mut(), CString::into_raw)
+    })
+}
+
+/// Release a string returned by this library.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that has not already been released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn signet_orders_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees `s` was returned by `CString::into_raw`
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{
+    clock::chain_time, contracts::permit2_nonce_used, ids::OrderHash, provider::TxSenderProvider,
+};
+use alloy::primitives::{Address, U256};
+use eyre::Error;
+use init4_bin_base::{
+    deps::{
+        metrics::counter,
+        tracing::{error, info, instrument, warn},
+    },
+    utils::from_env::FromEnv,
+};
+use serde::Serialize;
+use signet_types::SignedOrder;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// Default time after submission at which an unfilled Order is reported as stuck.
+const DEFAULT_STUCK_AFTER: Duration = Duration::from_secs(120);
+
+/// Configuration for a [`FillMonitor`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct FillMonitorConfig {
+    /// Interval between fill checks, in milliseconds. Monitoring is disabled if unset.
+    #[from_env(
+        var = "FILL_MONITOR_INTERVAL_MS",
+        desc = "Interval between checks of submitted Orders for fills, in ms",
+        optional
+    )]
+    pub poll_interval_ms: Option<u64>,
+    /// Report an unfilled Order as stuck this long after submission, in seconds.
+    #[from_env(
+        var = "FILL_STUCK_AFTER_SECS",
+        desc = "Report an unfilled Order as stuck this long after submission, in seconds",
+        optional
+    )]
+    pub stuck_after_secs: Option<u64>,
+    /// URL notified of each fill outcome. Outcomes are only logged if unset.
+    #[from_env(
+        var = "FILL_NOTIFY_WEBHOOK_URL",
+        desc = "URL notified of each submitted Order's fill outcome",
+        optional
+    )]
+    pub webhook_url: Option<String>,
+}
+
+/// The outcome of a submitted Order, reported by the [`FillMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillOutcome {
+    /// The Order was filled, or its nonce was otherwise used.
+    Filled,
+    /// The Order's deadline passed unfilled.
+    Expired,
+    /// The Order is still unfilled long after submission. Reported once per Order.
+    Stuck,
+}
+
+impl FillOutcome {
+    /// The outcome's name, used as a metric label.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Filled => "filled",
+            Self::Expired => "expired",
+            Self::Stuck => "stuck",
+        }
+    }
+}
+
+/// A notification of a submitted Order's outcome, posted to the configured webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FillNotification {
+    /// The hash of the Order.
+    pub order_hash: OrderHash,
+    /// The owner of the Order.
+    pub owner: Address,
+    /// What happened to the Order.
+    pub outcome: FillOutcome,
+    /// Chain time at which the Order was submitted, in seconds.
+    pub submitted_at: u64,
+    /// Chain time at which the outcome was observed, in seconds.
+    pub observed_at: u64,
+}
+
+/// A submitted Order watched by the [`FillMonitor`].
+#[derive(Debug, Clone, Copy)]
+struct WatchedOrder {
+    /// The owner of the Order.
+    owner: Address,
+    /// The Permit2 nonce of the Order, used once it is filled.
+    nonce: U256,
+    /// The Order deadline.
+    deadline: U256,
+    /// Chain time at which the Order was submitted, in seconds.
+    submitted_at: u64,
+    /// Whether the Order has been reported as stuck.
+    reported_stuck: bool,
+}
+
+/// Watches Orders after they are submitted, notifying the originator when each is filled,
+/// expires unfilled, or stays unfilled beyond a threshold.
+///
+/// Fills are detected by checking whether the Order's Permit2 nonce has been used. Filled and
+/// expired Orders are no longer watched; stuck Orders are reported once, and watched until
+/// they fill or expire. Each outcome is logged, increments the `order.fill_outcome` counter,
+/// labelled by `outcome`, and is posted as a [`FillNotification`] to the webhook, if one is
+/// configured.
+///
+/// The monitor is cheap to clone; clones share the watched Orders, so Orders can be watched
+/// after [`FillMonitor::spawn`] is called on a clone.
+#[derive(Debug, Clone)]
+pub struct FillMonitor {
+    /// Provider for the Rollup, used to check nonces and chain time.
+    ru_provider: TxSenderProvider,
+    /// Interval between fill checks.
+    interval: Duration,
+    /// Time after submission at which an unfilled Order is reported as stuck.
+    stuck_after: Duration,
+    /// URL notified of each outcome, if any.
+    webhook: Option<reqwest::Url>,
+    /// HTTP client, used to notify the webhook.
+    client: reqwest::Client,
+    /// The watched Orders, by hash.
+    orders: Arc<Mutex<HashMap<OrderHash, WatchedOrder>>>,
+}
+
+impl FillMonitor {
+    /// Create a new FillMonitor, checking submitted Orders on the given interval.
+    pub fn new(ru_provider: TxSenderProvider, interval: Duration) -> Result<Self, Error> {
+        Ok(Self {
+            ru_provider,
+            interval,
+            stuck_after: DEFAULT_STUCK_AFTER,
+            webhook: None,
+            client: reqwest::ClientBuilder::new().use_rustls_tls().build()?,
+            orders: Arc::default(),
+        })
+    }
+
+    /// Create a FillMonitor using the given configuration.
+    ///
+    /// Returns `None` if no poll interval is configured.
+    pub fn from_config(
+        config: &FillMonitorConfig,
+        ru_provider: TxSenderProvider,
+    ) -> Result<Option<Self>, Error> {
+        let Some(interval) = config.poll_interval_ms else {
+            return Ok(None);
+        };
+        let webhook = config
+            .webhook_url
+            .as_deref()
+            .map(str::parse::<reqwest::Url>)
+            .transpose()?;
+        let mut monitor =
+            Self::new(ru_provider, Duration::from_millis(interval))?.with_webhook(webhook);
+        if let Some(secs) = config.stuck_after_secs {
+            monitor = monitor.with_stuck_after(Duration::from_secs(secs));
+        }
+        Ok(Some(monitor))
+    }
+
+    /// Report unfilled Orders as stuck this long after submission.
+    pub const fn with_stuck_after(mut self, stuck_after: Duration) -> Self {
+        self.stuck_after = stuck_after;
+        self
+    }
+
+    /// Post each outcome to the given webhook.
+    pub fn with_webhook(mut self, webhook: Option<reqwest::Url>) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Watch an Order submitted at chain time `now`.
+    pub fn watch(&self, order: &SignedOrder, now: u64) {
+        let permit = &order.permit;
+        self.orders.lock().unwrap().insert(
+            OrderHash::from(order),
+            WatchedOrder {
+                owner: permit.owner,
+                nonce: permit.permit.nonce,
+                deadline: permit.permit.deadline,
+                submitted_at: now,
+                reported_stuck: false,
+            },
+        );
+    }
+
+    /// Number of Orders still watched.
+    pub fn watching(&self) -> usize {
+        self.orders.lock().unwrap().len()
+    }
+
+    /// Check every watched Order once at chain time `now`, returning the outcomes observed.
+    #[instrument(skip(self))]
+    pub async fn check(&self, now: u64) -> Result<Vec<FillNotification>, Error> {
+        let watched: Vec<(OrderHash, WatchedOrder)> = self
+            .orders
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(order_hash, order)| (*order_hash, *order))
+            .collect();
+
+        let mut notifications = Vec::new();
+        for (order_hash, order) in watched {
+            let outcome = if permit2_nonce_used(&self.ru_provider, order.owner, order.nonce).await?
+            {
+                FillOutcome::Filled
+            } else if order.deadline < U256::from(now) {
+                FillOutcome::Expired
+            } else if !order.reported_stuck
+                && now.saturating_sub(order.submitted_at) > self.stuck_after.as_secs()
+            {
+                FillOutcome::Stuck
+            } else {
+                continue;
+            };
+
+            {
+                let mut orders = self.orders.lock().unwrap();
+                match outcome {
+                    FillOutcome::Stuck => {
+                        if let Some(order) = orders.get_mut(&order_hash) {
+                            order.reported_stuck = true;
+                        }
+                    }
+                    FillOutcome::Filled | FillOutcome::Expired => {
+                        orders.remove(&order_hash);
+                    }
+                }
+            }
+
+            let notification = FillNotification {
+                order_hash,
+                owner: order.owner,
+                outcome,
+                submitted_at: order.submitted_at,
+                observed_at: now,
+            };
+            self.notify(&notification).await;
+            notifications.push(notification);
+        }
+        Ok(notifications)
+    }
+
+    /// Log and count an outcome, and post it to the webhook, if one is configured.
+    async fn notify(&self, notification: &FillNotification) {
+        let FillNotification {
+            order_hash,
+            outcome,
+            submitted_at,
+            observed_at,
+            ..
+        } = *notification;
+        let elapsed = observed_at.saturating_sub(submitted_at);
+        match outcome {
+            FillOutcome::Filled => info!(%order_hash, elapsed, "Submitted order filled"),
+            FillOutcome::Expired => warn!(%order_hash, elapsed, "Submitted order expired unfilled"),
+            FillOutcome::Stuck => warn!(%order_hash, elapsed, "Submitted order stuck unfilled"),
+        }
+        counter!("order.fill_outcome", "outcome" => outcome.as_str()).increment(1);
+
+        let Some(webhook) = &self.webhook else {
+            return;
+        };
+        let result = self
+            .client
+            .post(webhook.clone())
+            .json(notification)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(err) = result {
+            error!(%err, %order_hash, "Failed to notify fill outcome");
+            counter!("order.fill_notify_failures").increment(1);
+        }
+    }
+
+    /// Spawn a task that checks watched Orders on the configured interval, forever.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                let result = match chain_time(&self.ru_provider).await {
+                    Ok(now) => self.check(now).await.map(|_| ()),
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = result {
+                    error!(%err, "Failed to check submitted orders");
+                }
+            }
+        })
+    }
+}
@@ -1,28 +1,162 @@
-use crate::provider::TxSenderProvider;
+use crate::{
+    address_book::AddressBook,
+    anomaly::{AnomalyConfig, AnomalyDetector},
+    archive::SubmissionArchive,
+    clock::{chain_time, head_lag, latest_block},
+    compliance::ComplianceLog,
+    constants::resolve_constants,
+    contracts::permit2_nonce_used,
+    counters::{BUNDLES_SUBMITTED, ORDERS_FILLED, PersistentCounters},
+    ids::{BundleId, LocalBundleId, OrderHash},
+    leader::{LeaderElection, LeaderElectionConfig},
+    maintenance::{MaintenanceConfig, MaintenanceMode},
+    pause::{PairPauseConfig, PairPauses},
+    provider::{
+        RpcMetricsLayer, RpcRateLimitLayer, TxSenderProvider, latest_base_fee, orders_code_hash,
+        verify_chain,
+    },
+    quoting::StaticPrices,
+    reservation::{OrderReservations, Reservation, ReservationConfig},
+    risk::{RiskService, RiskServiceConfig},
+    runtime::offload,
+    timeout_signer::TimeoutSigner,
+    tx_cache::TxCacheError,
+    validate::validate_order,
+};
 use alloy::{
     consensus::constants::GWEI_TO_WEI,
-    eips::Encodable2718,
+    eips::{BlockNumberOrTag, Encodable2718},
     network::TransactionBuilder,
-    primitives::Bytes,
+    primitives::{Address, B256, Bytes, U256},
     providers::{Provider, SendableTx},
     rpc::types::{TransactionRequest, mev::EthSendBundle},
-    signers::Signer,
+    signers::{Signer, local::PrivateKeySigner},
 };
-use eyre::{Error, eyre};
+use eyre::{Error, WrapErr, eyre};
 use init4_bin_base::{
-    deps::tracing::{debug, info, instrument},
+    deps::{
+        metrics::{counter, gauge},
+        tracing::{debug, error, info, instrument, warn},
+    },
     utils::{from_env::FromEnv, signer::LocalOrAwsConfig},
 };
+use serde::Deserialize;
 use signet_bundle::SignetEthBundle;
 use signet_constants::SignetConstants;
 use signet_tx_cache::client::TxCache;
 use signet_types::{AggregateOrders, SignedFill, SignedOrder, UnsignedFill};
-use std::{collections::HashMap, slice::from_ref};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    slice::from_ref,
+    time::{Duration, Instant},
+};
+
+/// Fill planning: the ordered transactions submitted to fill a set of Orders.
+///
+/// These functions are pure, so the exact calldata a Filler submits can be reproduced
+/// (and snapshot tested) without providers or a transaction cache.
+pub mod plan;
+use plan::{BundleDecorator, FillDeadlinePolicy, PlanExtension, PlanExtensions};
+
+mod attribution;
+pub use attribution::{BundleAttribution, DEFAULT_ATTRIBUTION_HEADER};
+
+mod audit;
+use audit::Screening;
+
+mod diff;
+pub use diff::{BundlePlan, TxChange, TxDiff};
+
+mod encoding;
+pub use encoding::TxEncodingCache;
+
+mod external;
+pub use external::{CompiledPlan, ExternalPlan, PlanAction, PlanChain};
+
+mod fairness;
+pub use fairness::FairnessPolicy;
+
+mod filled;
+pub use filled::FilledOrders;
+
+mod inventory;
+pub use inventory::{EqualValue, OrderValue, required_outputs, select_within_inventory};
+
+mod latency;
+pub use latency::FillStage;
+
+mod pipeline;
+pub use pipeline::FillPipeline;
+
+mod pool;
+pub use pool::FillerPool;
+
+mod preview;
+pub use preview::{FillPreview, GasEstimate, PlannedTx};
+
+mod profitability;
+pub use profitability::{OrderProfit, Profitability, ProfitableFill};
+
+mod slippage;
+pub use slippage::{BoundedFill, BoundedOrder};
+
+mod router;
+pub use router::{AnyOrder, OrderFilter, PairFilter, Strategy, StrategyRouter};
+
+mod shard;
+pub use shard::OrderShard;
+
+mod signatures;
+use signatures::FillSignatureCache;
+
+mod simulate;
+pub use simulate::{
+    SimulatedTx, SimulationReport, ViewSimulation, parse_simulation_skip_thresholds,
+    parse_simulation_views,
+};
+
+mod stages;
+pub use stages::{EvaluatedFill, PlannedFill};
 
 /// Default gas limit for transactions.
 const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
 /// Default priority fee multiplier for transactions.
 const DEFAULT_PRIORITY_FEE_MULTIPLIER: u64 = 16;
+/// Expected time between Rollup blocks, in seconds.
+const RU_BLOCK_TIME_SECS: u64 = 12;
+/// Default offset of the first target block from the current rollup block.
+const DEFAULT_TARGET_OFFSET: u64 = 1;
+/// Default number of consecutive blocks targeted per fill.
+const DEFAULT_TARGET_WIDTH: u64 = 10;
+/// How long signed transaction batches are reused: the span of the default target window.
+const TX_ENCODING_CACHE_TTL: Duration =
+    Duration::from_secs(RU_BLOCK_TIME_SECS * DEFAULT_TARGET_WIDTH);
+/// Time allowed to plan, sign, and submit a fill after its Orders are reserved, before its
+/// target window starts.
+const RESERVATION_MARGIN: Duration = Duration::from_secs(60);
+/// Default maximum time for a single signing operation.
+const DEFAULT_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+/// Default window for remembering filled Orders, in seconds.
+const DEFAULT_FILLED_ORDERS_WINDOW_SECS: u64 = 86_400;
+
+/// The transaction envelope type used when signing transactions on a chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TxEnvelopeKind {
+    /// EIP-1559 dynamic-fee transactions.
+    #[default]
+    Eip1559,
+    /// Legacy transactions with a fixed gas price,
+    /// for environments that reject dynamic-fee transactions.
+    Legacy,
+}
+
+impl TxEnvelopeKind {
+    /// Legacy if `legacy` is true, otherwise EIP-1559.
+    pub const fn from_legacy_flag(legacy: bool) -> Self {
+        if legacy { Self::Legacy } else { Self::Eip1559 }
+    }
+}
 
 /// Configuration for the Filler application.
 #[derive(Debug, FromEnv)]
@@ -37,10 +171,619 @@ pub struct FillerConfig {
     /// NOTE: For the example, this key must be funded with gas on both the Host and Rollup, as well as Input/Output tokens for the Orders on the Host/Rollup.
     /// .env var: SIGNER_KEY
     pub signer_config: LocalOrAwsConfig,
-    /// The Signet constants.
+    /// The Signet chain name.
+    /// Names other than known networks select a custom deployment, configured via explicit env vars.
     /// .env var: CHAIN_NAME
-    #[from_env(var = "CHAIN_NAME", desc = "Signet chain name")]
-    pub constants: SignetConstants,
+    #[from_env(
+        var = "CHAIN_NAME",
+        desc = "Signet chain name, or any other name for a custom deployment"
+    )]
+    pub chain_name: String,
+    /// Whether to sign legacy transactions on the Rollup, rather than EIP-1559 transactions.
+    /// .env var: RU_LEGACY_TXS
+    #[from_env(
+        var = "RU_LEGACY_TXS",
+        desc = "Sign legacy transactions on the Rollup",
+        optional
+    )]
+    pub ru_legacy_txs: Option<bool>,
+    /// Whether to sign legacy transactions on the Host, rather than EIP-1559 transactions.
+    /// .env var: HOST_LEGACY_TXS
+    #[from_env(
+        var = "HOST_LEGACY_TXS",
+        desc = "Sign legacy transactions on the Host",
+        optional
+    )]
+    pub host_legacy_txs: Option<bool>,
+    /// Whether to skip Orders owned by the Filler's own signer.
+    /// .env var: SELF_TRADE_PREVENTION
+    #[from_env(
+        var = "SELF_TRADE_PREVENTION",
+        desc = "Skip Orders owned by the Filler's own signer",
+        optional
+    )]
+    pub self_trade_prevention: Option<bool>,
+    /// Comma-separated addresses or `ADDRESS_BOOK` names of affiliates, whose Orders the
+    /// Filler skips.
+    /// .env var: AFFILIATE_ADDRESSES
+    #[from_env(
+        var = "AFFILIATE_ADDRESSES",
+        desc = "Comma-separated addresses or names whose Orders the Filler skips",
+        optional
+    )]
+    pub affiliate_addresses: Option<String>,
+    /// Comma-separated `name=address` or `chain:name=address` entries, so addresses can be
+    /// given and logged by name.
+    /// .env var: ADDRESS_BOOK
+    #[from_env(
+        var = "ADDRESS_BOOK",
+        desc = "Comma-separated name=address or chain:name=address entries",
+        optional
+    )]
+    pub address_book: Option<String>,
+    /// The acknowledged code hash of the Rollup Orders contract.
+    /// .env var: RU_ORDERS_CODE_HASH
+    #[from_env(
+        var = "RU_ORDERS_CODE_HASH",
+        desc = "Acknowledged code hash of the Rollup Orders contract",
+        optional
+    )]
+    pub ru_orders_code_hash: Option<B256>,
+    /// The acknowledged code hash of the Host Orders contract.
+    /// .env var: HOST_ORDERS_CODE_HASH
+    #[from_env(
+        var = "HOST_ORDERS_CODE_HASH",
+        desc = "Acknowledged code hash of the Host Orders contract",
+        optional
+    )]
+    pub host_orders_code_hash: Option<B256>,
+    /// RPC calls slower than this are logged, in milliseconds.
+    /// .env var: RPC_SLOW_CALL_MS
+    #[from_env(
+        var = "RPC_SLOW_CALL_MS",
+        desc = "Log RPC calls slower than this, in ms",
+        optional
+    )]
+    pub rpc_slow_call_ms: Option<u64>,
+    /// Maximum lag of the Rollup head behind the wall clock or the Host head, in seconds.
+    /// .env var: MAX_HEAD_LAG_SECS
+    #[from_env(
+        var = "MAX_HEAD_LAG_SECS",
+        desc = "Refuse to target blocks when the Rollup head lags by more than this, in seconds",
+        optional
+    )]
+    pub max_head_lag_secs: Option<u64>,
+    /// Comma-separated state views to simulate fills against before sending, e.g. `latest,pending`.
+    /// .env var: SIMULATION_VIEWS
+    #[from_env(
+        var = "SIMULATION_VIEWS",
+        desc = "Comma-separated block tags or numbers to simulate fills against",
+        optional
+    )]
+    pub simulation_views: Option<String>,
+    /// Comma-separated `token=amount` thresholds, in raw token units. Fills whose Outputs are
+    /// all at most the threshold for their token skip simulation.
+    /// .env var: SIMULATION_SKIP_THRESHOLDS
+    #[from_env(
+        var = "SIMULATION_SKIP_THRESHOLDS",
+        desc = "Comma-separated token=amount thresholds below which fills skip simulation",
+        optional
+    )]
+    pub simulation_skip_thresholds: Option<String>,
+    /// Pause fills while the Rollup base fee is above this ceiling, in wei.
+    /// .env var: MAX_RU_BASE_FEE_WEI
+    #[from_env(
+        var = "MAX_RU_BASE_FEE_WEI",
+        desc = "Pause fills while the Rollup base fee is above this, in wei",
+        optional
+    )]
+    pub max_ru_base_fee_wei: Option<u64>,
+    /// Pause fills while the Host base fee is above this ceiling, in wei.
+    /// .env var: MAX_HOST_BASE_FEE_WEI
+    #[from_env(
+        var = "MAX_HOST_BASE_FEE_WEI",
+        desc = "Pause fills while the Host base fee is above this, in wei",
+        optional
+    )]
+    pub max_host_base_fee_wei: Option<u64>,
+    /// Maximum RPC requests per second to the Rollup RPC. Unlimited if unset.
+    /// .env var: RU_RPC_RATE_LIMIT
+    #[from_env(
+        var = "RU_RPC_RATE_LIMIT",
+        desc = "Maximum requests per second to the Rollup RPC",
+        optional
+    )]
+    pub ru_rpc_rate_limit: Option<u32>,
+    /// Maximum RPC requests per second to the Host RPC. Unlimited if unset.
+    /// .env var: HOST_RPC_RATE_LIMIT
+    #[from_env(
+        var = "HOST_RPC_RATE_LIMIT",
+        desc = "Maximum requests per second to the Host RPC",
+        optional
+    )]
+    pub host_rpc_rate_limit: Option<u32>,
+    /// Maximum burst of RPC requests to either RPC. Defaults to the per-second limit.
+    /// .env var: RPC_RATE_LIMIT_BURST
+    #[from_env(
+        var = "RPC_RATE_LIMIT_BURST",
+        desc = "Maximum burst of requests to either RPC",
+        optional
+    )]
+    pub rpc_rate_limit_burst: Option<u32>,
+    /// Skip Orders whose deadline is less than this many seconds away.
+    /// .env var: FILL_DEADLINE_MARGIN_SECS
+    #[from_env(
+        var = "FILL_DEADLINE_MARGIN_SECS",
+        desc = "Skip Orders whose deadline is less than this many seconds away",
+        optional
+    )]
+    pub fill_deadline_margin_secs: Option<u64>,
+    /// File persisting recently filled Order hashes, which are never filled again.
+    /// .env var: FILLED_ORDERS_PATH
+    #[from_env(
+        var = "FILLED_ORDERS_PATH",
+        desc = "File persisting recently filled Order hashes",
+        optional
+    )]
+    pub filled_orders_path: Option<String>,
+    /// How long filled Order hashes are remembered, in seconds. Defaults to one day.
+    /// .env var: FILLED_ORDERS_WINDOW_SECS
+    #[from_env(
+        var = "FILLED_ORDERS_WINDOW_SECS",
+        desc = "How long filled Order hashes are remembered, in seconds",
+        optional
+    )]
+    pub filled_orders_window_secs: Option<u64>,
+    /// File of the append-only, hash-chained compliance log. No log is kept if unset.
+    /// .env var: COMPLIANCE_LOG_PATH
+    #[from_env(
+        var = "COMPLIANCE_LOG_PATH",
+        desc = "File of the hash-chained compliance log of Orders considered and fills executed",
+        optional
+    )]
+    pub compliance_log_path: Option<String>,
+    /// File in which cumulative counters are persisted across restarts. Counters are not
+    /// persisted if unset.
+    /// .env var: COUNTERS_PATH
+    #[from_env(
+        var = "COUNTERS_PATH",
+        desc = "File in which cumulative fill counters are persisted across restarts",
+        optional
+    )]
+    pub counters_path: Option<String>,
+    /// Whether to fill Orders individually in round-robin order across token pairs.
+    /// .env var: FAIR_PAIR_ORDERING
+    #[from_env(
+        var = "FAIR_PAIR_ORDERING",
+        desc = "Fill Orders individually in round-robin order across token pairs",
+        optional
+    )]
+    pub fair_pair_ordering: Option<bool>,
+    /// Maximum time for a single signing operation, in milliseconds. Defaults to 30 seconds.
+    /// .env var: SIGNER_TIMEOUT_MS
+    #[from_env(
+        var = "SIGNER_TIMEOUT_MS",
+        desc = "Maximum time for a single signing operation, in ms",
+        optional
+    )]
+    pub signer_timeout_ms: Option<u64>,
+    /// Number of times to retry a signing operation that times out.
+    /// .env var: SIGNER_RETRIES
+    #[from_env(
+        var = "SIGNER_RETRIES",
+        desc = "Number of times to retry a signing operation that times out",
+        optional
+    )]
+    pub signer_retries: Option<u32>,
+    /// Offset of the first targeted Rollup block from the current block. Defaults to 1.
+    /// .env var: TARGET_BLOCK_OFFSET
+    #[from_env(
+        var = "TARGET_BLOCK_OFFSET",
+        desc = "Offset of the first targeted Rollup block from the current block",
+        optional
+    )]
+    pub target_block_offset: Option<u64>,
+    /// Number of consecutive Rollup blocks targeted per fill. Defaults to 10.
+    /// .env var: TARGET_BLOCK_COUNT
+    #[from_env(
+        var = "TARGET_BLOCK_COUNT",
+        desc = "Number of consecutive Rollup blocks targeted per fill",
+        optional
+    )]
+    pub target_block_count: Option<u64>,
+    /// Seconds subtracted from the earliest Order deadline to set the Fill deadline.
+    /// .env var: FILL_DEADLINE_SAFETY_SECS
+    #[from_env(
+        var = "FILL_DEADLINE_SAFETY_SECS",
+        desc = "Seconds subtracted from the earliest Order deadline to set the Fill deadline",
+        optional
+    )]
+    pub fill_deadline_safety_secs: Option<u64>,
+    /// If set, Fills expire this many seconds after chain time, capped at the earliest Order
+    /// deadline, instead of using `FILL_DEADLINE_SAFETY_SECS`.
+    /// .env var: FILL_DEADLINE_TTL_SECS
+    #[from_env(
+        var = "FILL_DEADLINE_TTL_SECS",
+        desc = "Seconds after chain time at which Fills expire",
+        optional
+    )]
+    pub fill_deadline_ttl_secs: Option<u64>,
+    /// Log a per-stage latency breakdown for fills taking longer than this, in milliseconds.
+    /// .env var: FILL_LATENCY_BUDGET_MS
+    #[from_env(
+        var = "FILL_LATENCY_BUDGET_MS",
+        desc = "Log a per-stage breakdown for fills slower than this, in ms",
+        optional
+    )]
+    pub fill_latency_budget_ms: Option<u64>,
+    /// Private key attributing Bundle submissions to this Filler, separate from the signer.
+    /// Submissions are unattributed if unset.
+    /// .env var: ATTRIBUTION_KEY
+    #[from_env(
+        var = "ATTRIBUTION_KEY",
+        desc = "Private key signing the Bundle attribution header",
+        optional
+    )]
+    pub attribution_key: Option<String>,
+    /// The header carrying the Bundle attribution signature. Defaults to
+    /// `X-Flashbots-Signature`.
+    /// .env var: ATTRIBUTION_HEADER
+    #[from_env(
+        var = "ATTRIBUTION_HEADER",
+        desc = "Header carrying the Bundle attribution signature",
+        optional
+    )]
+    pub attribution_header: Option<String>,
+    /// Directory archiving the signed payloads of every Bundle submitted. Submissions are not
+    /// archived if unset.
+    /// .env var: ARCHIVE_DIR
+    #[from_env(
+        var = "ARCHIVE_DIR",
+        desc = "Directory archiving the signed payloads of every Bundle submitted",
+        optional
+    )]
+    pub archive_dir: Option<String>,
+    /// Hex-encoded 32-byte key encrypting archived submissions. Required with `ARCHIVE_DIR`.
+    /// .env var: ARCHIVE_KEY
+    #[from_env(
+        var = "ARCHIVE_KEY",
+        desc = "Hex-encoded 32-byte key encrypting archived submissions",
+        optional
+    )]
+    pub archive_key: Option<String>,
+    /// How long archived submissions are kept, in days. Kept forever if unset.
+    /// .env var: ARCHIVE_RETENTION_DAYS
+    #[from_env(
+        var = "ARCHIVE_RETENTION_DAYS",
+        desc = "How long archived submissions are kept, in days",
+        optional
+    )]
+    pub archive_retention_days: Option<u64>,
+    /// Number of replicas the Order flow is split across by Order hash. Orders are not split if
+    /// unset.
+    /// .env var: SHARD_COUNT
+    #[from_env(
+        var = "SHARD_COUNT",
+        desc = "Number of replicas the Order flow is split across by Order hash",
+        optional
+    )]
+    pub shard_count: Option<u64>,
+    /// This replica's shard, from 0 to `SHARD_COUNT - 1`. Required with `SHARD_COUNT`.
+    /// .env var: SHARD_ID
+    #[from_env(
+        var = "SHARD_ID",
+        desc = "This replica's shard of the Order flow, below SHARD_COUNT",
+        optional
+    )]
+    pub shard_id: Option<u64>,
+    /// Comma-separated `chain_id:token=price` entries pricing a raw unit of each token in a
+    /// common unit, with `Address::ZERO` for a chain's native token. The Host native token must
+    /// be priced. Fills are not restricted to profitable Orders if unset.
+    /// .env var: TOKEN_PRICES
+    #[from_env(
+        var = "TOKEN_PRICES",
+        desc = "Comma-separated chain_id:token=price entries valuing Orders for profitability",
+        optional
+    )]
+    pub token_prices: Option<String>,
+    /// Minimum margin of a fill on its cost, in basis points, when `TOKEN_PRICES` is set.
+    /// Defaults to 0.
+    /// .env var: MIN_MARGIN_BPS
+    #[from_env(
+        var = "MIN_MARGIN_BPS",
+        desc = "Minimum margin of a fill on its cost, in basis points",
+        optional
+    )]
+    pub min_margin_bps: Option<u64>,
+}
+
+impl FillerConfig {
+    /// Cross-check the configuration, returning every problem found at once rather than
+    /// failing on the first.
+    ///
+    /// Checks that the RPC URLs parse, the chain name resolves to constants (either a known
+    /// network or a complete custom deployment), fee ceilings and rate limits are non-zero, and
+    /// list-valued settings parse.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut problems = Vec::new();
+
+        for (var, url) in [
+            ("RU_RPC_URL", &self.ru_rpc_url),
+            ("HOST_RPC_URL", &self.host_rpc_url),
+        ] {
+            if let Err(err) = url.parse::<reqwest::Url>() {
+                problems.push(format!("{var}: invalid URL {url}: {err}"));
+            }
+        }
+        if let Err(err) = self.constants() {
+            problems.push(format!("CHAIN_NAME: {err}"));
+        }
+        for (var, value) in [
+            ("MAX_RU_BASE_FEE_WEI", self.max_ru_base_fee_wei),
+            ("MAX_HOST_BASE_FEE_WEI", self.max_host_base_fee_wei),
+            ("FILLED_ORDERS_WINDOW_SECS", self.filled_orders_window_secs),
+            ("SIGNER_TIMEOUT_MS", self.signer_timeout_ms),
+            ("TARGET_BLOCK_OFFSET", self.target_block_offset),
+            ("TARGET_BLOCK_COUNT", self.target_block_count),
+            ("FILL_DEADLINE_TTL_SECS", self.fill_deadline_ttl_secs),
+            ("FILL_LATENCY_BUDGET_MS", self.fill_latency_budget_ms),
+        ] {
+            if value == Some(0) {
+                problems.push(format!("{var}: must be greater than zero"));
+            }
+        }
+        for (var, value) in [
+            ("RU_RPC_RATE_LIMIT", self.ru_rpc_rate_limit),
+            ("HOST_RPC_RATE_LIMIT", self.host_rpc_rate_limit),
+            ("RPC_RATE_LIMIT_BURST", self.rpc_rate_limit_burst),
+        ] {
+            if value == Some(0) {
+                problems.push(format!("{var}: must be greater than zero"));
+            }
+        }
+        if let Err(err) = self.simulation_views() {
+            problems.push(format!("SIMULATION_VIEWS: {err}"));
+        }
+        if let Err(err) = self.simulation_skip_thresholds() {
+            problems.push(format!("SIMULATION_SKIP_THRESHOLDS: {err}"));
+        }
+        if let Err(err) = self.affiliates() {
+            problems.push(format!("AFFILIATE_ADDRESSES: {err}"));
+        }
+        if let Err(err) = self.address_book() {
+            problems.push(format!("ADDRESS_BOOK: {err}"));
+        }
+        if let Err(err) = self.bundle_attribution() {
+            problems.push(format!("ATTRIBUTION_KEY: {err}"));
+        }
+        if let Err(err) = self.submission_archive() {
+            problems.push(format!("ARCHIVE_KEY: {err}"));
+        }
+        if let Err(err) = self.order_shard() {
+            problems.push(format!("SHARD_ID: {err}"));
+        }
+        if let Err(err) = self.profitability() {
+            problems.push(format!("TOKEN_PRICES: {err}"));
+        }
+        if self.min_margin_bps.is_some() && self.token_prices.is_none() {
+            problems.push("MIN_MARGIN_BPS: requires TOKEN_PRICES".to_string());
+        }
+
+        if !problems.is_empty() {
+            eyre::bail!("invalid configuration:\n  {}", problems.join("\n  "));
+        }
+        Ok(())
+    }
+
+    /// Resolve the Signet constants for the configured chain.
+    ///
+    /// See [`resolve_constants`] for how custom deployments are configured.
+    pub fn constants(&self) -> Result<SignetConstants, Error> {
+        resolve_constants(&self.chain_name)
+    }
+
+    /// The transaction envelope type to use on the Rollup.
+    pub fn ru_tx_kind(&self) -> TxEnvelopeKind {
+        TxEnvelopeKind::from_legacy_flag(self.ru_legacy_txs.unwrap_or_default())
+    }
+
+    /// The transaction envelope type to use on the Host.
+    pub fn host_tx_kind(&self) -> TxEnvelopeKind {
+        TxEnvelopeKind::from_legacy_flag(self.host_legacy_txs.unwrap_or_default())
+    }
+
+    /// The RPC metrics layer for the named chain.
+    pub fn rpc_metrics(&self, chain: &'static str) -> RpcMetricsLayer {
+        RpcMetricsLayer::new(chain)
+            .with_slow_call_threshold(self.rpc_slow_call_ms.map(Duration::from_millis))
+    }
+
+    /// The RPC rate limit layer for the Rollup.
+    pub fn ru_rpc_rate_limit(&self) -> RpcRateLimitLayer {
+        self.rpc_rate_limit("rollup", self.ru_rpc_rate_limit)
+    }
+
+    /// The RPC rate limit layer for the Host.
+    pub fn host_rpc_rate_limit(&self) -> RpcRateLimitLayer {
+        self.rpc_rate_limit("host", self.host_rpc_rate_limit)
+    }
+
+    /// The RPC rate limit layer for the named chain, given its per-second limit.
+    fn rpc_rate_limit(&self, chain: &'static str, limit: Option<u32>) -> RpcRateLimitLayer {
+        match limit {
+            Some(limit) => {
+                RpcRateLimitLayer::new(chain, limit, self.rpc_rate_limit_burst.unwrap_or(limit))
+            }
+            None => RpcRateLimitLayer::unlimited(chain),
+        }
+    }
+
+    /// Parse the configured simulation views.
+    pub fn simulation_views(&self) -> Result<Vec<BlockNumberOrTag>, Error> {
+        self.simulation_views
+            .as_deref()
+            .map_or_else(|| Ok(vec![]), parse_simulation_views)
+    }
+
+    /// Parse the configured simulation skip thresholds.
+    pub fn simulation_skip_thresholds(&self) -> Result<HashMap<Address, U256>, Error> {
+        self.simulation_skip_thresholds
+            .as_deref()
+            .map_or_else(|| Ok(HashMap::new()), parse_simulation_skip_thresholds)
+    }
+
+    /// The configured target block window, as `(offset, width)`.
+    pub fn target_window(&self) -> (u64, u64) {
+        (
+            self.target_block_offset.unwrap_or(DEFAULT_TARGET_OFFSET),
+            self.target_block_count.unwrap_or(DEFAULT_TARGET_WIDTH),
+        )
+    }
+
+    /// The configured latency budget for a single fill, if any.
+    pub fn fill_latency_budget(&self) -> Option<Duration> {
+        self.fill_latency_budget_ms.map(Duration::from_millis)
+    }
+
+    /// The configured policy for choosing Fill deadlines.
+    pub fn fill_deadline_policy(&self) -> FillDeadlinePolicy {
+        match self.fill_deadline_ttl_secs {
+            Some(ttl) => FillDeadlinePolicy::FromNow(ttl),
+            None => FillDeadlinePolicy::Earliest {
+                margin: self.fill_deadline_safety_secs.unwrap_or_default(),
+            },
+        }
+    }
+
+    /// Bound the signer's operations by the configured timeout and retries.
+    pub fn timeout_signer<S>(&self, signer: S) -> TimeoutSigner<S> {
+        let timeout = self
+            .signer_timeout_ms
+            .map_or(DEFAULT_SIGNER_TIMEOUT, Duration::from_millis);
+        TimeoutSigner::new(signer, timeout).with_retries(self.signer_retries.unwrap_or_default())
+    }
+
+    /// The configured fairness policy across token pairs, if any.
+    pub fn fairness(&self) -> Option<FairnessPolicy> {
+        self.fair_pair_ordering
+            .unwrap_or_default()
+            .then_some(FairnessPolicy::RoundRobin)
+    }
+
+    /// Open the configured window of recently filled Orders, if a file is configured.
+    pub fn filled_orders(&self) -> Result<Option<FilledOrders>, Error> {
+        let window = self
+            .filled_orders_window_secs
+            .unwrap_or(DEFAULT_FILLED_ORDERS_WINDOW_SECS);
+        self.filled_orders_path
+            .as_deref()
+            .map(|path| FilledOrders::open(path, window))
+            .transpose()
+    }
+
+    /// Open the configured compliance log, if a file is configured.
+    pub fn compliance_log(&self) -> Result<Option<ComplianceLog>, Error> {
+        self.compliance_log_path
+            .as_deref()
+            .map(ComplianceLog::open)
+            .transpose()
+    }
+
+    /// Open the configured persistent counters, if a file is configured.
+    pub fn persistent_counters(&self) -> Result<Option<PersistentCounters>, Error> {
+        self.counters_path
+            .as_deref()
+            .map(PersistentCounters::open)
+            .transpose()
+    }
+
+    /// The configured Bundle attribution, if an attribution key is set.
+    pub fn bundle_attribution(&self) -> Result<Option<BundleAttribution>, Error> {
+        let Some(key) = &self.attribution_key else {
+            return Ok(None);
+        };
+        let signer: PrivateKeySigner = key.parse().wrap_err("invalid attribution key")?;
+        let mut attribution = BundleAttribution::new(signer)?;
+        if let Some(header) = &self.attribution_header {
+            attribution = attribution.with_header(header);
+        }
+        Ok(Some(attribution))
+    }
+
+    /// This replica's configured shard of the Order flow, if a shard count is set.
+    pub fn order_shard(&self) -> Result<Option<OrderShard>, Error> {
+        let Some(count) = self.shard_count else {
+            return Ok(None);
+        };
+        let id = self
+            .shard_id
+            .ok_or_else(|| eyre!("SHARD_ID is required with SHARD_COUNT"))?;
+        OrderShard::new(id, count).map(Some)
+    }
+
+    /// The configured valuation of Orders, in the Host native token, if token prices are set.
+    /// See [`Filler::fill_profitable`].
+    pub fn profitability(&self) -> Result<Option<Profitability<StaticPrices>>, Error> {
+        let Some(prices) = &self.token_prices else {
+            return Ok(None);
+        };
+        let prices = StaticPrices::parse(prices)?;
+        let numeraire = (self.constants()?.host().chain_id(), Address::ZERO);
+        if !prices.contains(numeraire) {
+            eyre::bail!(
+                "the Host native token (chain {}) must be priced",
+                numeraire.0
+            );
+        }
+        Ok(Some(Profitability::new(prices, numeraire)))
+    }
+
+    /// The configured minimum margin of a fill, in basis points.
+    pub fn min_margin_bps(&self) -> u64 {
+        self.min_margin_bps.unwrap_or_default()
+    }
+
+    /// Open the configured submission archive, if an archive directory is set.
+    pub fn submission_archive(&self) -> Result<Option<SubmissionArchive>, Error> {
+        let Some(dir) = &self.archive_dir else {
+            return Ok(None);
+        };
+        let key = self
+            .archive_key
+            .as_deref()
+            .ok_or_else(|| eyre!("ARCHIVE_KEY is required with ARCHIVE_DIR"))?;
+        let key = alloy::hex::decode(key).wrap_err("invalid archive key")?;
+        let retention = self.archive_retention_days.map(|days| days * 24 * 60 * 60);
+        Ok(Some(
+            SubmissionArchive::open(dir, &key)?.with_retention(retention),
+        ))
+    }
+
+    /// Parse the configured address book.
+    pub fn address_book(&self) -> Result<AddressBook, Error> {
+        self.address_book
+            .as_deref()
+            .map_or_else(|| Ok(AddressBook::default()), AddressBook::parse)
+    }
+
+    /// Parse the configured affiliate addresses, resolving names through the address book.
+    ///
+    /// Affiliates own Orders on the Rollup, so names scoped to the Rollup are used.
+    pub fn affiliates(&self) -> Result<Vec<Address>, Error> {
+        let Some(addresses) = &self.affiliate_addresses else {
+            return Ok(vec![]);
+        };
+        let book = self.address_book()?;
+        addresses
+            .split(',')
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .map(|address| {
+                book.resolve_on("rollup", address)
+                    .map_err(|e| eyre!("invalid affiliate {address}: {e}"))
+            })
+            .collect()
+    }
 }
 
 /// Example code demonstrating API usage and patterns for Signet Fillers.
@@ -54,8 +797,81 @@ pub struct Filler<S: Signer> {
     host_provider: TxSenderProvider,
     /// The transaction cache endpoint.
     tx_cache: TxCache,
+    /// The transaction cache URL, used to submit attributed Bundles.
+    tx_cache_url: reqwest::Url,
+    /// Signs Bundle submissions for builder attribution, if configured.
+    attribution: Option<BundleAttribution>,
     /// The system constants.
     constants: SignetConstants,
+    /// The transaction envelope type to use on the Rollup.
+    ru_tx_kind: TxEnvelopeKind,
+    /// The transaction envelope type to use on the Host.
+    host_tx_kind: TxEnvelopeKind,
+    /// Owners whose Orders are never filled, to prevent self-trades.
+    excluded_owners: HashSet<Address>,
+    /// The acknowledged code hash of the Rollup Orders contract, if pinned.
+    ru_orders_code_hash: Option<B256>,
+    /// The acknowledged code hash of the Host Orders contract, if pinned.
+    host_orders_code_hash: Option<B256>,
+    /// Maximum tolerated lag of the Rollup head, in seconds, if checked.
+    max_head_lag: Option<u64>,
+    /// State views to simulate fills against before sending.
+    simulation_views: Vec<BlockNumberOrTag>,
+    /// Output amount thresholds, by token, at or below which fills skip simulation.
+    simulation_skip_below: HashMap<Address, U256>,
+    /// Rollup base fee ceiling, in wei, above which fills are paused.
+    ru_max_base_fee: Option<u64>,
+    /// Host base fee ceiling, in wei, above which fills are paused.
+    host_max_base_fee: Option<u64>,
+    /// Orders whose deadline is less than this many seconds away are skipped.
+    deadline_margin: u64,
+    /// Recently filled Orders, which are never filled again, if tracked.
+    filled_orders: Option<FilledOrders>,
+    /// Log of every Order considered and fill executed, if kept.
+    compliance_log: Option<ComplianceLog>,
+    /// Archive of the signed payloads of every Bundle submitted, if kept.
+    archive: Option<SubmissionArchive>,
+    /// Cumulative counters persisted across restarts, if kept.
+    counters: Option<PersistentCounters>,
+    /// Strategy hooks attaching extra transactions around each fill.
+    plan_extensions: Vec<Box<dyn PlanExtension>>,
+    /// How Orders filled individually are ordered across token pairs, if at all.
+    fairness: Option<FairnessPolicy>,
+    /// Hooks setting builder-specific parameters on each Bundle.
+    bundle_decorators: Vec<Box<dyn BundleDecorator>>,
+    /// Offset of the first target block from the current rollup block.
+    target_offset: u64,
+    /// Number of consecutive blocks targeted per fill.
+    target_width: u64,
+    /// How the deadline of each Fill is chosen.
+    fill_deadline_policy: FillDeadlinePolicy,
+    /// Signed Fills, reused when the same Orders are filled again.
+    fill_signatures: FillSignatureCache,
+    /// Signed, encoded transaction batches, reused within a submission burst.
+    tx_encodings: TxEncodingCache,
+    /// Named addresses, used to label Order owners in logs.
+    address_book: AddressBook,
+    /// External risk policy service consulted before each Order is filled, if any.
+    risk: Option<RiskService>,
+    /// Token pairs whose fills are paused, if any.
+    pair_pauses: Option<PairPauses>,
+    /// Maintenance windows during which no new fills are initiated, if configured.
+    maintenance: Option<MaintenanceMode>,
+    /// Leader election across replicas, if configured; only the leader fills.
+    leader: Option<LeaderElection>,
+    /// Reservations of Orders shared with other replicas, if configured.
+    reservations: Option<OrderReservations>,
+    /// This replica's shard of the Order flow, if the flow is split.
+    shard: Option<OrderShard>,
+    /// Detector quarantining anomalous Orders for manual review, if any.
+    anomaly_detector: Option<AnomalyDetector>,
+    /// Fills taking longer than this log a per-stage latency breakdown, if set.
+    latency_budget: Option<Duration>,
+    /// How Orders are valued when inventory can't cover them all, if inventory is checked.
+    inventory_selection: Option<Box<dyn OrderValue>>,
+    /// Faults injected into transaction cache calls, if any.
+    #[cfg(feature = "chaos")]
+    faults: Option<crate::chaos::Faults>,
 }
 
 impl<S> Filler<S>
@@ -81,14 +897,653 @@ where
             signer,
             ru_provider,
             host_provider,
-            tx_cache: TxCache::new_with_client(tx_cache_url, client),
+            tx_cache: TxCache::new_with_client(tx_cache_url.clone(), client),
+            tx_cache_url,
+            attribution: None,
             constants,
+            ru_tx_kind: TxEnvelopeKind::default(),
+            host_tx_kind: TxEnvelopeKind::default(),
+            excluded_owners: HashSet::new(),
+            ru_orders_code_hash: None,
+            host_orders_code_hash: None,
+            max_head_lag: None,
+            simulation_views: Vec::new(),
+            simulation_skip_below: HashMap::new(),
+            ru_max_base_fee: None,
+            host_max_base_fee: None,
+            deadline_margin: 0,
+            filled_orders: None,
+            compliance_log: None,
+            archive: None,
+            counters: None,
+            plan_extensions: Vec::new(),
+            fairness: None,
+            bundle_decorators: Vec::new(),
+            target_offset: DEFAULT_TARGET_OFFSET,
+            target_width: DEFAULT_TARGET_WIDTH,
+            fill_deadline_policy: FillDeadlinePolicy::default(),
+            fill_signatures: FillSignatureCache::default(),
+            tx_encodings: TxEncodingCache::new(TX_ENCODING_CACHE_TTL),
+            address_book: AddressBook::default(),
+            risk: None,
+            pair_pauses: None,
+            maintenance: None,
+            leader: None,
+            reservations: None,
+            shard: None,
+            anomaly_detector: None,
+            latency_budget: None,
+            inventory_selection: None,
+            #[cfg(feature = "chaos")]
+            faults: None,
         })
     }
 
+    /// Create a new Filler configured by `config`, and by the optional services configured
+    /// from environment variables: the risk service, pair pauses, maintenance windows, leader
+    /// election, Order reservations, and anomaly detection. This is how the Filler binaries
+    /// and bindings build their Filler.
+    ///
+    /// Verifies that the providers match the configured chain constants.
+    pub async fn from_config(
+        config: &FillerConfig,
+        signer: S,
+        ru_provider: TxSenderProvider,
+        host_provider: TxSenderProvider,
+    ) -> Result<Self, Error> {
+        let (target_offset, target_width) = config.target_window();
+        let mut filler = Self::new(signer, ru_provider, host_provider, config.constants()?)?
+            .with_tx_kinds(config.ru_tx_kind(), config.host_tx_kind())
+            .with_excluded_owners(config.affiliates()?)
+            .with_orders_code_hashes(config.ru_orders_code_hash, config.host_orders_code_hash)
+            .with_max_head_lag(config.max_head_lag_secs)
+            .with_simulation_views(config.simulation_views()?)
+            .with_simulation_skip_thresholds(config.simulation_skip_thresholds()?)
+            .with_max_base_fees(config.max_ru_base_fee_wei, config.max_host_base_fee_wei)
+            .with_deadline_margin(config.fill_deadline_margin_secs.unwrap_or_default())
+            .with_filled_orders(config.filled_orders()?)
+            .with_compliance_log(config.compliance_log()?)
+            .with_submission_archive(config.submission_archive()?)
+            .with_order_shard(config.order_shard()?)
+            .with_persistent_counters(config.persistent_counters()?)
+            .with_fairness(config.fairness())
+            .with_target_window(target_offset, target_width)
+            .with_fill_deadline_policy(config.fill_deadline_policy())
+            .with_address_book(config.address_book()?)
+            .with_risk_service(RiskServiceConfig::from_env()?.connect()?)
+            .with_pair_pauses(PairPauseConfig::from_env()?.build()?)
+            .with_maintenance_mode(MaintenanceConfig::from_env()?.build())
+            .with_leader_election(LeaderElectionConfig::from_env()?.connect()?)
+            .with_order_reservations(ReservationConfig::from_env()?.connect()?)
+            .with_anomaly_detector(AnomalyConfig::from_env()?.build()?)
+            .with_bundle_attribution(config.bundle_attribution()?)
+            .with_latency_budget(config.fill_latency_budget());
+        if config.self_trade_prevention.unwrap_or_default() {
+            filler = filler.with_self_trade_prevention();
+        }
+        filler.verify_constants().await?;
+        Ok(filler)
+    }
+
+    /// Pin the acknowledged code hashes of the Rollup and Host Orders contracts.
+    ///
+    /// The Filler refuses to fill if a pinned contract's code changes, e.g. after a release that
+    /// changes the Orders contract address or interface, or an onchain proxy upgrade, since its
+    /// fill and initiate calldata encodings may be stale. Operators acknowledge the change by
+    /// updating the pinned hash.
+    pub const fn with_orders_code_hashes(mut self, ru: Option<B256>, host: Option<B256>) -> Self {
+        self.ru_orders_code_hash = ru;
+        self.host_orders_code_hash = host;
+        self
+    }
+
+    /// Refuse to target blocks while the Rollup head lags behind the wall clock or the Host
+    /// head by more than `max_lag` seconds.
+    pub const fn with_max_head_lag(mut self, max_lag: Option<u64>) -> Self {
+        self.max_head_lag = max_lag;
+        self
+    }
+
+    /// Pause fills while the Rollup or Host base fee is above its ceiling, in wei.
+    ///
+    /// Fill margins assume normal gas conditions, and Order amounts are fixed when the Order is
+    /// signed, so fills that would be profitable normally can turn negative during a gas spike.
+    pub const fn with_max_base_fees(mut self, ru: Option<u64>, host: Option<u64>) -> Self {
+        self.ru_max_base_fee = ru;
+        self.host_max_base_fee = host;
+        self
+    }
+
+    /// Target `width` consecutive rollup blocks per fill, starting `offset` blocks after the
+    /// current rollup block.
+    ///
+    /// Deployments where Bundles take longer to propagate through the cache than one rollup
+    /// block should use a larger offset, since Bundles for the next block would arrive too late.
+    pub const fn with_target_window(mut self, offset: u64, width: u64) -> Self {
+        self.target_offset = offset;
+        self.target_width = width;
+        self
+    }
+
+    /// Choose the deadline of each Fill by `policy`. Defaults to the earliest deadline of the
+    /// Orders filled.
+    pub const fn with_fill_deadline_policy(mut self, policy: FillDeadlinePolicy) -> Self {
+        self.fill_deadline_policy = policy;
+        self
+    }
+
+    /// Skip Orders whose deadline is less than `margin` seconds away.
+    ///
+    /// Signing and submitting a fill takes time, so Orders close to their deadline may expire
+    /// before the fill lands. Fillers with slow signers, such as a
+    /// [`RemoteSigner`](crate::remote_signer::RemoteSigner), should set a margin at least as
+    /// long as their signing timeout.
+    pub const fn with_deadline_margin(mut self, margin: u64) -> Self {
+        self.deadline_margin = margin;
+        self
+    }
+
+    /// Track recently filled Orders, refusing to fill any Order seen filled within the window.
+    ///
+    /// The transaction cache may re-serve filled Orders after a rollback, or if it is poisoned.
+    /// Before filling, each Order's Permit2 nonce is checked onchain; Orders whose nonce is used
+    /// are recorded as filled, and skipped from then on without further RPC calls.
+    pub fn with_filled_orders(mut self, filled_orders: Option<FilledOrders>) -> Self {
+        self.filled_orders = filled_orders;
+        self
+    }
+
+    /// Attach extra transactions around each fill, such as an oracle poke or a flash loan.
+    ///
+    /// Extensions added first wrap those added later. See [`PlanExtension`].
+    pub fn with_plan_extension(mut self, extension: impl PlanExtension + 'static) -> Self {
+        self.plan_extensions.push(Box::new(extension));
+        self
+    }
+
+    /// Set builder-specific parameters on each Bundle before it is sent, e.g.
+    /// [`BundleHints`](plan::BundleHints). Decorators are applied in the order they were added.
+    pub fn with_bundle_decorator(mut self, decorator: impl BundleDecorator + 'static) -> Self {
+        self.bundle_decorators.push(Box::new(decorator));
+        self
+    }
+
+    /// Check the signer's inventory before each fill, and when the Orders' Outputs exceed it,
+    /// fill only the most valuable subset that fits rather than failing the whole Bundle.
+    ///
+    /// Orders are valued by `value`; use [`EqualValue`] to fill as many Orders as possible.
+    /// See [`select_within_inventory`] for how the subset is chosen.
+    pub fn with_inventory_selection(mut self, value: impl OrderValue + 'static) -> Self {
+        self.inventory_selection = Some(Box::new(value));
+        self
+    }
+
+    /// Record every Order considered, with the decision and the reason for any skip, and every
+    /// fill executed, in an append-only, hash-chained compliance log.
+    ///
+    /// Decisions are recorded before anything is signed; a fill fails if they can't be.
+    pub fn with_compliance_log(mut self, compliance_log: Option<ComplianceLog>) -> Self {
+        self.compliance_log = compliance_log;
+        self
+    }
+
+    /// Archive the exact signed transactions and Fill signatures of every Bundle submitted,
+    /// encrypted at rest. See [`SubmissionArchive`].
+    pub fn with_submission_archive(mut self, archive: Option<SubmissionArchive>) -> Self {
+        self.archive = archive;
+        self
+    }
+
+    /// Persist cumulative counts of Orders filled and Bundles submitted across restarts. See
+    /// [`PersistentCounters`].
+    pub fn with_persistent_counters(mut self, counters: Option<PersistentCounters>) -> Self {
+        self.counters = counters;
+        self
+    }
+
+    /// Fill Orders individually in the order given by the fairness policy, so one busy token
+    /// pair doesn't consume all inventory before Orders in other pairs are reached.
+    pub fn with_fairness(mut self, fairness: Option<FairnessPolicy>) -> Self {
+        self.fairness = fairness;
+        self
+    }
+
+    /// Simulate fills against each of the given state views before sending them, refusing to
+    /// fill if any view fails.
+    ///
+    /// Simulating against `pending` as well as `latest` catches fills that would fail because a
+    /// competing fill is already queued.
+    pub fn with_simulation_views(
+        mut self,
+        views: impl IntoIterator<Item = BlockNumberOrTag>,
+    ) -> Self {
+        self.simulation_views = views.into_iter().collect();
+        self
+    }
+
+    /// Skip simulation for fills whose Outputs are all at most the threshold for their token,
+    /// in raw token units, relying on Builder simulation instead. Large fills, and fills with
+    /// Outputs in tokens without a threshold, are always simulated.
+    pub fn with_simulation_skip_thresholds(
+        mut self,
+        thresholds: impl IntoIterator<Item = (Address, U256)>,
+    ) -> Self {
+        self.simulation_skip_below = thresholds.into_iter().collect();
+        self
+    }
+
+    /// Skip Orders owned by the Filler's own signer, to prevent self-trades when the same
+    /// operator both sends and fills Orders.
+    pub fn with_self_trade_prevention(mut self) -> Self {
+        self.excluded_owners.insert(self.signer.address());
+        self
+    }
+
+    /// Consult an external risk policy service before filling each Order, skipping Orders it
+    /// denies. See [`RiskService`].
+    pub fn with_risk_service(mut self, risk: Option<RiskService>) -> Self {
+        self.risk = risk;
+        self
+    }
+
+    /// Skip Orders in paused tokens or pairs, e.g. during a depeg. See [`PairPauses`].
+    pub fn with_pair_pauses(mut self, pair_pauses: Option<PairPauses>) -> Self {
+        self.pair_pauses = pair_pauses;
+        self
+    }
+
+    /// Initiate no new fills during maintenance windows, e.g. deploys and chain upgrades. See
+    /// [`MaintenanceMode`].
+    pub fn with_maintenance_mode(mut self, maintenance: Option<MaintenanceMode>) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
+    /// Fill only while this replica is the elected leader, so replicas don't fill the same
+    /// Orders twice. See [`LeaderElection`].
+    pub fn with_leader_election(mut self, leader: Option<LeaderElection>) -> Self {
+        self.leader = leader;
+        self
+    }
+
+    /// Fill only the Orders in this replica's shard of the Order flow. See [`OrderShard`].
+    pub const fn with_order_shard(mut self, shard: Option<OrderShard>) -> Self {
+        self.shard = shard;
+        self
+    }
+
+    /// Reserve Orders before filling them, leaving Orders reserved by other replicas to those
+    /// replicas. See [`OrderReservations`].
+    pub fn with_order_reservations(mut self, reservations: Option<OrderReservations>) -> Self {
+        self.reservations = reservations;
+        self
+    }
+
+    /// Quarantine anomalous Orders for manual review instead of filling them. See
+    /// [`AnomalyDetector`].
+    pub fn with_anomaly_detector(mut self, anomaly_detector: Option<AnomalyDetector>) -> Self {
+        self.anomaly_detector = anomaly_detector;
+        self
+    }
+
+    /// Attach a signed attribution header to every Bundle submission, so builders and caches
+    /// can attribute and prioritize this Filler's Bundles.
+    pub fn with_bundle_attribution(mut self, attribution: Option<BundleAttribution>) -> Self {
+        self.attribution = attribution;
+        self
+    }
+
+    /// Log a per-stage latency breakdown for fills taking longer than `budget`.
+    ///
+    /// Every fill records the time spent in each [`FillStage`] in the
+    /// `filler.stage_duration_ms` histogram, and its total in `filler.fill_duration_ms`.
+    pub const fn with_latency_budget(mut self, budget: Option<Duration>) -> Self {
+        self.latency_budget = budget;
+        self
+    }
+
+    /// Inject `faults` into transaction cache calls, to test failure handling.
+    #[cfg(feature = "chaos")]
+    pub fn with_faults(mut self, faults: Option<crate::chaos::Faults>) -> Self {
+        self.faults = faults;
+        self
+    }
+
+    /// Delay or fail a transaction cache call, if faults are configured.
+    #[cfg(feature = "chaos")]
+    async fn inject_fault(&self, operation: &'static str) -> Result<(), Error> {
+        if let Some(faults) = &self.faults {
+            faults
+                .inject("tx_cache")
+                .await
+                .map_err(|fault| TxCacheError::classify(operation, fault.into()))?;
+        }
+        Ok(())
+    }
+
+    /// Label Order owners in logs by their names in the address book.
+    pub fn with_address_book(mut self, address_book: AddressBook) -> Self {
+        self.address_book = address_book;
+        self
+    }
+
+    /// Label an Order's owner for logs, by name if it is in the address book.
+    fn owner_label(&self, order: &SignedOrder) -> String {
+        self.address_book.label("rollup", order.permit.owner)
+    }
+
+    /// Skip Orders owned by any of the given affiliate addresses.
+    ///
+    /// Order senders can keep their own Filler instances from filling their Orders
+    /// by listing the sending address as an affiliate.
+    pub fn with_excluded_owners(mut self, owners: impl IntoIterator<Item = Address>) -> Self {
+        self.excluded_owners.extend(owners);
+        self
+    }
+
+    /// Whether the Order is owned by the Filler or an affiliate, and must not be filled.
+    pub fn is_self_trade(&self, order: &SignedOrder) -> bool {
+        self.excluded_owners.contains(&order.permit.owner)
+    }
+
+    /// Whether the Order is valid at chain time `now`, allowing for the deadline margin,
+    /// and not a self-trade, logging the reason if it should be skipped.
+    fn should_fill(&self, order: &SignedOrder, now: u64) -> bool {
+        if let Err(err) = validate_order(
+            order,
+            &self.constants,
+            now.saturating_add(self.deadline_margin),
+        ) {
+            warn!(%err, "Skipping invalid order");
+            return false;
+        }
+        if self.is_self_trade(order) {
+            warn!(owner = %self.owner_label(order), "Skipping self-trade order");
+            return false;
+        }
+        true
+    }
+
+    /// Add a submitted fill to the persistent counters, if kept.
+    ///
+    /// The Bundles have already been sent, so a failure to count the fill is logged rather than
+    /// failing the fill, and increments the `filler.counter_failures` counter.
+    fn count_fill(&self, orders: &[SignedOrder], bundle_ids: &[BundleId]) {
+        let Some(counters) = &self.counters else {
+            return;
+        };
+        let counted = counters
+            .increment(ORDERS_FILLED, orders.len() as u64)
+            .and_then(|()| counters.increment(BUNDLES_SUBMITTED, bundle_ids.len() as u64));
+        if let Err(err) = counted {
+            error!(%err, "Failed to count submitted fill in the persistent counters");
+            counter!("filler.counter_failures").increment(1);
+        }
+    }
+
+    /// Remove Orders in paused tokens or pairs, if any pauses are configured.
+    fn skip_paused(&self, orders: Vec<SignedOrder>) -> Result<Vec<SignedOrder>, Error> {
+        match &self.pair_pauses {
+            Some(pair_pauses) => pair_pauses.filter(orders),
+            None => Ok(orders),
+        }
+    }
+
+    /// Error if a maintenance window is in effect at time `now`, so no new fill is initiated.
+    fn check_maintenance(&self, now: u64) -> Result<(), Error> {
+        let Some(maintenance) = &self.maintenance else {
+            return Ok(());
+        };
+        if let Some(window) = maintenance.current(now)? {
+            eyre::bail!(
+                "in maintenance until {}, not filling: {}",
+                window.until,
+                window.reason
+            );
+        }
+        Ok(())
+    }
+
+    /// Error if leader election is configured and this replica is not the leader.
+    async fn check_leader(&self) -> Result<(), Error> {
+        if let Some(leader) = &self.leader
+            && !leader.check().await
+        {
+            eyre::bail!("{} is not the leader, not filling", leader.name());
+        }
+        Ok(())
+    }
+
+    /// Reserve the Orders for this replica, if reservations are configured, leaving out those
+    /// reserved by other replicas.
+    ///
+    /// The Orders are held until the fill's last target block has passed, so no other replica
+    /// fills them while this replica's Bundles are live in the transaction cache.
+    async fn reserve(&self, orders: Vec<SignedOrder>) -> Result<Reservation, Error> {
+        match &self.reservations {
+            Some(reservations) => reservations.reserve(orders, self.reservation_hold()).await,
+            None => Ok(Reservation {
+                orders,
+                lease: None,
+            }),
+        }
+    }
+
+    /// How long to hold reserved Orders: the span of the target window, plus a margin for
+    /// planning, signing, and submitting the fill.
+    const fn reservation_hold(&self) -> Duration {
+        let window_blocks = self.target_offset.saturating_add(self.target_width);
+        Duration::from_secs(RU_BLOCK_TIME_SECS.saturating_mul(window_blocks))
+            .saturating_add(RESERVATION_MARGIN)
+    }
+
+    /// Release a reservation taken by [`Filler::reserve`], if any.
+    async fn release(&self, reservation: &Reservation) {
+        if let (Some(reservations), Some(lease)) = (&self.reservations, reservation.lease) {
+            reservations.release(lease).await;
+        }
+    }
+
+    /// Remove Orders quarantined for review, if anomaly detection is configured, quarantining
+    /// any newly detected anomalies at chain time `now`.
+    fn skip_quarantined(
+        &self,
+        orders: Vec<SignedOrder>,
+        now: u64,
+    ) -> Result<Vec<SignedOrder>, Error> {
+        match &self.anomaly_detector {
+            Some(detector) => detector.screen(orders, now),
+            None => Ok(orders),
+        }
+    }
+
+    /// Remove Orders denied by the risk service, if one is configured.
+    async fn skip_denied(&self, orders: Vec<SignedOrder>) -> Vec<SignedOrder> {
+        let Some(risk) = &self.risk else {
+            return orders;
+        };
+
+        let mut allowed = Vec::with_capacity(orders.len());
+        for order in orders {
+            if risk.allows(&order).await {
+                allowed.push(order);
+            }
+        }
+        allowed
+    }
+
+    /// Remove Orders already filled, if filled Orders are tracked, recording any newly seen
+    /// fills at chain time `now`.
+    async fn skip_filled(
+        &self,
+        orders: Vec<SignedOrder>,
+        now: u64,
+    ) -> Result<Vec<SignedOrder>, Error> {
+        let Some(filled_orders) = &self.filled_orders else {
+            return Ok(orders);
+        };
+
+        let mut unfilled = Vec::with_capacity(orders.len());
+        for order in orders {
+            let order_hash = OrderHash::from(&order);
+            if filled_orders.contains(&order_hash) {
+                warn!(%order_hash, "Skipping order already filled");
+                counter!("filler.refill_refused").increment(1);
+                continue;
+            }
+            let permit = &order.permit;
+            if permit2_nonce_used(&self.ru_provider, permit.owner, permit.permit.nonce).await? {
+                warn!(%order_hash, "Skipping order whose nonce is already used");
+                counter!("filler.refill_refused").increment(1);
+                filled_orders.record(order_hash, now)?;
+                continue;
+            }
+            unfilled.push(order);
+        }
+        Ok(unfilled)
+    }
+
+    /// Set the transaction envelope types to use on the Rollup and Host.
+    pub const fn with_tx_kinds(mut self, ru: TxEnvelopeKind, host: TxEnvelopeKind) -> Self {
+        self.ru_tx_kind = ru;
+        self.host_tx_kind = host;
+        self
+    }
+
+    /// Verify that the Rollup and Host providers are connected to the chains described by the
+    /// constants, and that the Orders contracts are deployed on each chain.
+    ///
+    /// Fillers should call this at startup, to fail fast on misconfiguration
+    /// rather than producing Bundles that can never be valid.
+    #[instrument(skip_all)]
+    pub async fn verify_constants(&self) -> Result<(), Error> {
+        let ru_code_hash = verify_chain(
+            &self.ru_provider,
+            self.constants.rollup().chain_id(),
+            self.constants.rollup().orders(),
+        )
+        .await
+        .wrap_err("rollup provider does not match constants")?;
+        check_code_hash("rollup", self.ru_orders_code_hash, ru_code_hash)?;
+
+        let host_code_hash = verify_chain(
+            &self.host_provider,
+            self.constants.host().chain_id(),
+            self.constants.host().orders(),
+        )
+        .await
+        .wrap_err("host provider does not match constants")?;
+        check_code_hash("host", self.host_orders_code_hash, host_code_hash)?;
+
+        debug!("Providers match constants");
+        Ok(())
+    }
+
+    /// Ensure the code of any pinned Orders contract has not changed since it was acknowledged.
+    #[instrument(skip_all)]
+    pub async fn check_orders_code(&self) -> Result<(), Error> {
+        if let Some(pinned) = self.ru_orders_code_hash {
+            let code_hash =
+                orders_code_hash(&self.ru_provider, self.constants.rollup().orders()).await?;
+            check_code_hash("rollup", Some(pinned), code_hash)?;
+        }
+        if let Some(pinned) = self.host_orders_code_hash {
+            let code_hash =
+                orders_code_hash(&self.host_provider, self.constants.host().orders()).await?;
+            check_code_hash("host", Some(pinned), code_hash)?;
+        }
+        Ok(())
+    }
+
+    /// Ensure the Rollup and Host base fees are below their configured ceilings, if any.
+    #[instrument(skip_all)]
+    pub async fn check_base_fees(&self) -> Result<(), Error> {
+        for (chain, provider, ceiling) in [
+            ("rollup", &self.ru_provider, self.ru_max_base_fee),
+            ("host", &self.host_provider, self.host_max_base_fee),
+        ] {
+            let Some(ceiling) = ceiling else { continue };
+            let base_fee = latest_base_fee(provider).await?;
+            gauge!("filler.base_fee", "chain" => chain).set(base_fee as f64);
+            if base_fee > ceiling {
+                warn!(
+                    chain,
+                    base_fee, ceiling, "Base fee above ceiling; pausing fills"
+                );
+                counter!("filler.base_fee_exceeded", "chain" => chain).increment(1);
+                eyre::bail!("{chain} base fee {base_fee} is above the ceiling {ceiling}");
+            }
+        }
+        Ok(())
+    }
+
     /// Query the transaction cache to get all possible orders.
     pub async fn get_orders(&self) -> Result<Vec<SignedOrder>, Error> {
-        self.tx_cache.get_orders().await
+        let started = Instant::now();
+        #[cfg(feature = "chaos")]
+        self.inject_fault("get_orders").await?;
+        let orders = self
+            .tx_cache
+            .get_orders()
+            .await
+            .map_err(|err| TxCacheError::classify("get_orders", err))?;
+        FillStage::Fetch.record(started.elapsed());
+        Ok(orders)
+    }
+
+    /// Fill the Orders in a JSON fixture file in a single Bundle, instead of Orders from the
+    /// transaction cache.
+    ///
+    /// See [`load_orders`] for the file format, and [`Filler::fill`].
+    #[instrument(skip_all, fields(path = %path.as_ref().display()))]
+    pub async fn fill_from_file(&self, path: impl AsRef<Path>) -> Result<Vec<BundleId>, Error> {
+        let orders = load_orders(path)?;
+        self.fill(&orders).await
+    }
+
+    /// Screen the Orders at chain time `now`, skipping those that are invalid, expiring, or
+    /// self-trades, in another shard, paused, quarantined, already filled, or denied by the risk
+    /// service.
+    ///
+    /// Returns the screening, for later stages to extend, and the Orders left to fill.
+    async fn screen(
+        &self,
+        orders: &[SignedOrder],
+        now: u64,
+    ) -> Result<(Screening, Vec<SignedOrder>), Error> {
+        // skip orders that are invalid, or owned by the Filler or its affiliates
+        let mut screening = Screening::new(orders);
+        let orders: Vec<SignedOrder> = orders
+            .iter()
+            .filter(|order| self.should_fill(order, now))
+            .cloned()
+            .collect();
+        screening.stage(&orders, "invalid, expiring, or self-trade");
+
+        // skip orders in other replicas' shards
+        let orders = match &self.shard {
+            Some(shard) => shard.filter(orders),
+            None => orders,
+        };
+        screening.stage(&orders, "in another shard");
+
+        // skip orders in paused tokens or pairs
+        let orders = self.skip_paused(orders)?;
+        screening.stage(&orders, "pair paused");
+
+        // hold anomalous orders for review instead of evaluating them
+        let orders = self.skip_quarantined(orders, now)?;
+        screening.stage(&orders, "quarantined for review");
+
+        // skip orders already filled, even if the transaction cache re-serves them
+        let orders = self.skip_filled(orders, now).await?;
+        screening.stage(&orders, "already filled");
+
+        // skip orders the risk service denies
+        let orders = self.skip_denied(orders).await;
+        screening.stage(&orders, "denied by risk service");
+
+        Ok((screening, orders))
     }
 
     /// Fills Orders individually, by submitting a separate Bundle for each Order.
@@ -102,16 +1557,51 @@ where
     /// without simulating to check whether it has already been filled, because they can rely on Builder simulation.
     /// Order `initiate` transactions will revert if the Order has already been filled,
     /// in which case the entire Bundle would simply be discarded by the Builder.
+    ///
+    /// An Order whose fill fails does not stop the Orders after it: the failure is logged and
+    /// increments the `filler.individual_fill_failures` counter.
+    ///
+    /// Returns the ids of all submitted Bundles.
     #[instrument(skip_all)]
-    pub async fn fill_individually(&self, orders: &[SignedOrder]) -> Result<(), Error> {
+    pub async fn fill_individually(&self, orders: &[SignedOrder]) -> Result<Vec<BundleId>, Error> {
         debug!(orders_count = orders.len(), "Filling orders individually");
 
+        // take turns across token pairs, if a fairness policy is set
+        let arranged;
+        let orders = match &self.fairness {
+            Some(fairness) => {
+                arranged = fairness.arrange(orders);
+                &arranged
+            }
+            None => orders,
+        };
+
         // submit one bundle per individual order
+        let mut bundle_ids = Vec::new();
+        let mut failures = 0;
         for order in orders {
-            self.fill(from_ref(order)).await?;
+            if self.is_self_trade(order) {
+                warn!(owner = %self.owner_label(order), "Skipping self-trade order");
+                continue;
+            }
+            match self.fill(from_ref(order)).await {
+                Ok(ids) => bundle_ids.extend(ids),
+                Err(err) => {
+                    error!(%err, order_hash = %OrderHash::from(order), "Failed to fill order");
+                    counter!("filler.individual_fill_failures").increment(1);
+                    failures += 1;
+                }
+            }
+        }
+        if failures > 0 {
+            warn!(
+                failures,
+                bundles_count = bundle_ids.len(),
+                "Some orders failed to fill individually"
+            );
         }
 
-        Ok(())
+        Ok(bundle_ids)
     }
 
     /// Fills one or more Order(s) in a single, atomic Bundle.
@@ -127,52 +1617,74 @@ where
     /// If a single Order is passed to this fn,
     /// Filling Orders individually ensures that even if some Orders are not fillable, others may still mine;
     /// however, it is less gas efficient.
+    ///
+    /// Runs [`Filler::evaluate`], [`Filler::plan_fill`], and [`Filler::submit_fill`] in turn;
+    /// a [`FillPipeline`] runs the same stages with separate concurrency limits.
+    ///
+    /// Returns the ids of the submitted Bundles, one per target block, or none if every Order
+    /// was screened out.
     #[instrument(skip_all)]
-    pub async fn fill(&self, orders: &[SignedOrder]) -> Result<(), Error> {
+    pub async fn fill(&self, orders: &[SignedOrder]) -> Result<Vec<BundleId>, Error> {
         info!(orders_count = orders.len(), "Filling orders in bundle");
 
-        // if orders is empty, error out
-        if orders.is_empty() {
-            eyre::bail!("no orders to fill")
-        }
-
-        // sign a SignedFill for the orders
-        let signed_fills: HashMap<u64, SignedFill> = self.sign_fills(orders).await?;
-        debug!(?signed_fills, "Signed fills for orders");
-        info!("Successfully signed fills");
-
-        // get the transaction requests for the rollup
-        let tx_requests = self.rollup_txn_requests(&signed_fills, orders).await?;
-        debug!(?tx_requests, "Rollup transaction requests");
-
-        // sign & encode the rollup transactions for the Bundle
-        let txs: Vec<Bytes> = self
-            .sign_and_encode_txns(&self.ru_provider, tx_requests)
-            .await?;
-        debug!(?txs, "Rollup encoded transactions");
-
-        // get the transaction requests for the host
-        let host_tx_requests = self.host_txn_requests(&signed_fills).await?;
-        debug!(?host_tx_requests, "Host transaction requests");
-
-        // sign & encode the host transactions for the Bundle
-        let host_txs = self
-            .sign_and_encode_txns(&self.host_provider, host_tx_requests)
-            .await?;
-        debug!(?host_txs, "Host encoded transactions");
-
-        // get current rollup block to determine the subsequent target block(s) for Bundle
-        let latest_ru_block_number = self.ru_provider.get_block_number().await?;
+        // nothing left to fill is not an error, so one screened-out Order never aborts a batch
+        // filled individually
+        let Some(evaluated) = self.evaluate(orders).await? else {
+            return Ok(Vec::new());
+        };
+        // box the plan stage, whose future nests deeply enough to overflow the compiler's
+        // recursion limit in callers awaiting `fill`
+        let planned = Box::pin(self.plan_fill(evaluated)).await?;
+        self.submit_fill(planned).await
+    }
 
-        // send the Bundle to the transaction cache
-        // targeting the next 10 blocks to increase chances of mining
-        // NOTE: this is a naive approach; production Fillers should implement more robust bundle resubmission logic
-        for i in 1..11 {
-            self.send_bundle(txs.clone(), host_txs.clone(), latest_ru_block_number + i)
-                .await?;
+    /// Determine the rollup block numbers that Bundles for Orders expiring at `deadline` should
+    /// target.
+    ///
+    /// By default, targets the next 10 blocks after the current rollup block, to increase chances
+    /// of mining; see [`Filler::with_target_window`]. Blocks expected at or after the deadline
+    /// are trimmed, since Bundles targeting them can never be valid.
+    /// NOTE: this is a naive approach; production Fillers should implement more robust bundle resubmission logic
+    ///
+    /// If a maximum head lag is configured, refuses to target blocks from a stale rollup head,
+    /// since Bundles targeting blocks that have already passed never land.
+    async fn target_blocks(&self, deadline: u64) -> Result<Vec<u64>, Error> {
+        let (latest_number, latest_timestamp) = match self.max_head_lag {
+            Some(max_lag) => self.check_head_lag(max_lag).await?,
+            None => latest_block(&self.ru_provider).await?,
+        };
+        let first = latest_number + self.target_offset;
+        let targets = plan::trim_target_blocks(
+            first..first + self.target_width,
+            latest_number,
+            latest_timestamp,
+            RU_BLOCK_TIME_SECS,
+            deadline,
+        );
+        if targets.len() < self.target_width as usize {
+            debug!(
+                deadline,
+                trimmed = self.target_width as usize - targets.len(),
+                "Trimmed target blocks past the deadline"
+            );
         }
+        Ok(targets)
+    }
 
-        Ok(())
+    /// Ensure the rollup head is not lagging by more than `max_lag` seconds,
+    /// returning the latest rollup block number and timestamp.
+    async fn check_head_lag(&self, max_lag: u64) -> Result<(u64, u64), Error> {
+        let lag = head_lag(&self.ru_provider, &self.host_provider).await?;
+        gauge!("filler.ru_head_lag_secs").set(lag.max_lag() as f64);
+        if lag.max_lag() > max_lag as i64 {
+            error!(?lag, max_lag, "Rollup head is lagging; pausing fills");
+            counter!("filler.ru_head_lag_exceeded").increment(1);
+            eyre::bail!(
+                "rollup head lags by {}s, more than the maximum {max_lag}s",
+                lag.max_lag()
+            );
+        }
+        Ok((lag.ru_block_number, lag.ru_timestamp))
     }
 
     async fn send_bundle(
@@ -180,9 +1692,9 @@ where
         ru_txs: Vec<Bytes>,
         host_txs: Vec<Bytes>,
         target_ru_block_number: u64,
-    ) -> Result<(), Error> {
+    ) -> Result<BundleId, Error> {
         // construct a Bundle containing the Rollup transactions and the Host fill (if any)
-        let bundle = SignetEthBundle {
+        let mut bundle = SignetEthBundle {
             host_txs,
             bundle: EthSendBundle {
                 txs: ru_txs,
@@ -190,19 +1702,40 @@ where
                 ..Default::default()
             },
         };
-        debug!(?bundle, "bundle contents");
+        for decorator in &self.bundle_decorators {
+            decorator.decorate(&mut bundle);
+        }
+        let local_id = LocalBundleId::from(&bundle);
+        debug!(?bundle, %local_id, "bundle contents");
         info!(
+            %local_id,
             ru_tx_count = bundle.bundle.txs.len(),
             host_tx_count = bundle.host_txs.len(),
             target_ru_block_number,
             "forwarding bundle to transaction cache"
         );
 
-        // submit the Bundle to the transaction cache
-        let response = self.tx_cache.forward_bundle(bundle).await?;
-        debug!(bundle_id = response.id.to_string(), "Bundle sent to cache");
+        // submit the Bundle to the transaction cache, attributed if configured
+        #[cfg(feature = "chaos")]
+        self.inject_fault("forward_bundle").await?;
+        let id = match &self.attribution {
+            Some(attribution) => {
+                attribution
+                    .forward_bundle(&self.tx_cache_url, &bundle)
+                    .await?
+            }
+            None => {
+                self.tx_cache
+                    .forward_bundle(bundle)
+                    .await
+                    .map_err(|err| TxCacheError::classify("forward_bundle", err))?
+                    .id
+            }
+        };
+        let bundle_id = BundleId::from(id);
+        info!(%bundle_id, %local_id, "Bundle sent to cache");
 
-        Ok(())
+        Ok(bundle_id)
     }
 
     /// Aggregate the given orders into a SignedFill, sign it, and
@@ -215,19 +1748,71 @@ where
     ///
     /// If filling multiple Orders, they may wish to utilize one Order's Outputs to provide another Order's rollup Inputs.
     /// In this case, the Filler would wish to split up the Fills for each Order,
-    /// rather than signing a single, aggregate a Fill for each chain, as is done here.
+    /// rather than signing a single, aggregate a Fill for each chain, as is done here;
+    /// see [`Filler::sign_fills_individually`].
+    ///
+    /// The Fill deadline is chosen by the Filler's [`FillDeadlinePolicy`] from the earliest
+    /// deadline of the Orders, so the Fill never outlives any Order it fills.
     #[instrument(skip_all, fields(orders_count = orders.len()))]
     async fn sign_fills(&self, orders: &[SignedOrder]) -> Result<HashMap<u64, SignedFill>, Error> {
         if orders.is_empty() {
             eyre::bail!("no orders to fill");
         }
-        let deadline = orders[0]
-            .permit
-            .permit
-            .deadline
-            .to_string()
-            .parse::<u64>()
-            .map_err(|e| eyre!("invalid deadline in orders: {e}"))?;
+        let now = chain_time(&self.ru_provider).await?;
+        let deadline = self.fill_deadline(orders, now)?;
+        self.sign_fill(orders, deadline, now).await
+    }
+
+    /// Sign a separate SignedFill for each Order, returning the SignedFills for each
+    /// destination chain in the same order as the Orders.
+    ///
+    /// This supports interleaved plans, such as `[fill_1, initiate_1, fill_2, initiate_2]`,
+    /// where one Order's Outputs provide another Order's rollup Inputs; this is impossible with
+    /// a single aggregate Fill. Each Fill's deadline is chosen from its own Order's deadline.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn sign_fills_individually(
+        &self,
+        orders: &[SignedOrder],
+    ) -> Result<Vec<HashMap<u64, SignedFill>>, Error> {
+        if orders.is_empty() {
+            eyre::bail!("no orders to fill");
+        }
+        let now = chain_time(&self.ru_provider).await?;
+        let mut signed_fills = Vec::with_capacity(orders.len());
+        for order in orders {
+            let order = std::slice::from_ref(order);
+            let deadline = self.fill_deadline(order, now)?;
+            signed_fills.push(self.sign_fill(order, deadline, now).await?);
+        }
+        Ok(signed_fills)
+    }
+
+    /// Choose the deadline of a Fill of the Orders at chain time `now`, by the Filler's
+    /// [`FillDeadlinePolicy`].
+    fn fill_deadline(&self, orders: &[SignedOrder], now: u64) -> Result<u64, Error> {
+        let earliest = earliest_deadline(orders, [])?;
+        let deadline = self.fill_deadline_policy.deadline(earliest, now);
+        if deadline <= now {
+            eyre::bail!("fill deadline {deadline} has passed at chain time {now}");
+        }
+        debug!(earliest, deadline, "Chose fill deadline");
+        Ok(deadline)
+    }
+
+    /// Aggregate the Orders into a single Fill with the given deadline, and sign it for each
+    /// destination chain.
+    ///
+    /// If the same Orders were already signed with the same deadline, the cached signatures are
+    /// reused, so retries neither call the signer again nor change the Permit2 nonces.
+    async fn sign_fill(
+        &self,
+        orders: &[SignedOrder],
+        deadline: u64,
+        now: u64,
+    ) -> Result<HashMap<u64, SignedFill>, Error> {
+        if let Some(signed_fills) = self.fill_signatures.get(orders, deadline) {
+            return Ok(signed_fills);
+        }
         //  create an AggregateOrder from the SignedOrders they want to fill
         let agg: AggregateOrders = orders.iter().collect();
         debug!(?agg, "Aggregated orders for fill");
@@ -240,109 +1825,206 @@ where
             .with_chain(self.constants.system().clone());
         debug!(?unsigned_fill, "Unsigned fill created");
         // sign the UnsignedFill, producing a SignedFill for each target chain
-        Ok(unsigned_fill.sign(&self.signer).await?)
+        let signed_fills = unsigned_fill.sign(&self.signer).await?;
+        self.fill_signatures
+            .insert(orders, deadline, signed_fills.clone(), now);
+        Ok(signed_fills)
+    }
+
+    /// Collect the extra transactions from each plan extension, in the order they were added.
+    fn plan_extensions(&self, orders: &[SignedOrder]) -> PlanExtensions {
+        let mut extensions = PlanExtensions::default();
+        for extension in &self.plan_extensions {
+            extensions.nest(extension.extend(&self.constants, orders));
+        }
+        extensions
     }
 
     /// Construct a set of transaction requests to be submitted on the rollup.
     ///
-    /// Perform a single, aggregate Fill upfront, then Initiate each Order.
-    /// Transaction requests look like [`fill_aggregate`, `initiate_1`, `initiate_2`].
-    ///
-    /// This is the simplest, minimally viable way to get a set of Orders mined;
-    /// Fillers may wish to implement more complex strategies.
-    ///
-    /// For example, Fillers might utilize one Order's Inputs to fill subsequent Orders' Outputs.
-    /// In this case, the rollup transactions should look like [`fill_1`, `inititate_1`, `fill_2`, `initiate_2`].
+    /// See [`plan::rollup_txn_requests`] for the transaction ordering. Transactions from any
+    /// plan extensions are placed around it.
     #[instrument(skip_all)]
     async fn rollup_txn_requests(
         &self,
         signed_fills: &HashMap<u64, SignedFill>,
         orders: &[SignedOrder],
     ) -> Result<Vec<TransactionRequest>, Error> {
-        // construct the transactions to be submitted to the Rollup
-        let mut tx_requests = Vec::new();
-
-        // first, if there is a SignedFill for the Rollup, add a transaction to submit the fill
-        // Note that `fill` transactions MUST be mined *before* the corresponding Order(s) `initiate` transactions in order to count
-        // Host `fill` transactions are always considered to be mined "before" the rollup block is processed,
-        // but Rollup `fill` transactions MUST take care to be ordered before the Orders are `initiate`d
-        if let Some(rollup_fill) = signed_fills.get(&self.constants.rollup().chain_id()) {
-            debug!(?rollup_fill, "Rollup fill");
-            // add the fill tx to the rollup txns
-            let ru_fill_tx = rollup_fill.to_fill_tx(self.constants.rollup().orders());
-            tx_requests.push(ru_fill_tx);
-        }
-
-        // next, add a transaction to initiate each SignedOrder
-        for signed_order in orders {
-            // add the initiate tx to the rollup txns
-            let ru_initiate_tx = signed_order
-                .to_initiate_tx(self.signer.address(), self.constants.rollup().orders());
-            tx_requests.push(ru_initiate_tx);
-        }
-
-        Ok(tx_requests)
+        let planned =
+            plan::rollup_txn_requests(&self.constants, self.signer.address(), signed_fills, orders);
+        Ok(self.plan_extensions(orders).wrap_rollup(planned))
     }
 
     /// Construct a set of transaction requests to be submitted on the host.
     ///
-    /// This example only includes one Host transaction,
-    /// which performs a single, aggregate Fill on the Host chain.
-    ///
-    /// This is the simplest, minimally viable way to get a set of Orders mined;
-    /// Fillers may wish to implement more complex strategies.
-    ///
-    /// For example, Fillers might wish to include swaps on Host AMMs to source liquidity as part of their filling strategy.
+    /// See [`plan::host_txn_requests`] for the transaction ordering. Transactions from any
+    /// plan extensions are placed around it.
     #[instrument(skip_all)]
     async fn host_txn_requests(
         &self,
         signed_fills: &HashMap<u64, SignedFill>,
+        orders: &[SignedOrder],
     ) -> Result<Vec<TransactionRequest>, Error> {
-        // If there is a SignedFill for the Host, add a transaction to submit the fill
-        if let Some(host_fill) = signed_fills.get(&self.constants.host().chain_id()) {
-            debug!(?host_fill, "Host fill");
-            // add the fill tx to the host txns
-            let host_fill_tx = host_fill.to_fill_tx(self.constants.host().orders());
-            Ok(vec![host_fill_tx])
+        let planned = plan::host_txn_requests(&self.constants, signed_fills);
+        Ok(self.plan_extensions(orders).wrap_host(planned))
+    }
+
+    /// The transaction envelope type configured for a chain.
+    const fn tx_kind(&self, chain_id: u64) -> TxEnvelopeKind {
+        if chain_id == self.constants.host().chain_id() {
+            self.host_tx_kind
         } else {
-            Ok(vec![])
+            self.ru_tx_kind
         }
     }
 
+    /// Sign and encode the transactions filling Orders, as [`Filler::sign_and_encode_txns`],
+    /// reusing a batch identical to one signed shortly before at the same nonce, e.g. when the
+    /// same Orders are resubmitted. See [`TxEncodingCache`].
+    async fn sign_and_encode_fill_txns(
+        &self,
+        provider: &TxSenderProvider,
+        tx_requests: Vec<TransactionRequest>,
+    ) -> Result<Vec<Bytes>, Error> {
+        let chain_id = provider.get_chain_id().await?;
+        let nonce = provider
+            .get_transaction_count(self.signer.address())
+            .pending()
+            .await?;
+        let request_hash =
+            TxEncodingCache::request_hash(chain_id, self.tx_kind(chain_id), nonce, &tx_requests)?;
+        if let Some(txs) = self.tx_encodings.get(request_hash) {
+            return Ok(txs);
+        }
+        let txs = self.sign_and_encode_txns(provider, tx_requests).await?;
+        self.tx_encodings.insert(request_hash, txs.clone());
+        Ok(txs)
+    }
+
     /// Given an ordered set of Transaction Requests,
     /// Sign them and encode them for inclusion in a Bundle.
+    ///
+    /// Transactions are signed as legacy or EIP-1559 transactions,
+    /// according to the envelope type configured for the provider's chain.
+    /// The signed batch is encoded on the blocking thread pool.
     #[instrument(skip_all)]
     pub async fn sign_and_encode_txns(
         &self,
         provider: &TxSenderProvider,
         tx_requests: Vec<TransactionRequest>,
     ) -> Result<Vec<Bytes>, Error> {
-        let mut encoded_txs: Vec<Bytes> = Vec::new();
+        let chain_id = provider.get_chain_id().await?;
+        let tx_kind = self.tx_kind(chain_id);
+        let priority_fee = (GWEI_TO_WEI * DEFAULT_PRIORITY_FEE_MULTIPLIER) as u128;
+
+        let mut signed_txs = Vec::with_capacity(tx_requests.len());
         for mut tx in tx_requests {
             // fill out the transaction fields
             tx = tx
                 .with_from(self.signer.address())
-                .with_gas_limit(DEFAULT_GAS_LIMIT)
-                .with_max_priority_fee_per_gas(
-                    (GWEI_TO_WEI * DEFAULT_PRIORITY_FEE_MULTIPLIER) as u128,
-                );
+                .with_gas_limit(DEFAULT_GAS_LIMIT);
+            tx = match tx_kind {
+                TxEnvelopeKind::Eip1559 => tx.with_max_priority_fee_per_gas(priority_fee),
+                // setting the gas price causes the provider to fill a legacy transaction
+                TxEnvelopeKind::Legacy => {
+                    tx.with_gas_price(provider.get_gas_price().await? + priority_fee)
+                }
+            };
 
             // sign the transaction
             let SendableTx::Envelope(filled) = provider.fill(tx).await? else {
                 eyre::bail!("Failed to fill transaction")
             };
 
-            // encode it
-            let encoded = filled.encoded_2718();
             info!(
                 tx_hash = filled.hash().to_string(),
-                chain_id = provider.get_chain_id().await?,
-                "Transaction signed and encoded"
+                chain_id, "Transaction signed"
             );
 
             // add to array
-            encoded_txs.push(Bytes::from(encoded));
+            signed_txs.push(filled);
+        }
+
+        // encode the batch
+        let txs: Vec<Bytes> = offload(move || {
+            signed_txs
+                .iter()
+                .map(|tx| Bytes::from(tx.encoded_2718()))
+                .collect()
+        })
+        .await?;
+        Ok(txs)
+    }
+}
+
+/// The contents of an Orders fixture file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OrdersFile {
+    /// An array of Orders.
+    Many(Vec<SignedOrder>),
+    /// A single Order.
+    One(Box<SignedOrder>),
+}
+
+/// Load Orders from a JSON file containing a single [`SignedOrder`] or an array of them.
+///
+/// Useful for reproducing issues with specific Orders reported by users, without relying on the
+/// transaction cache still serving them.
+pub fn load_orders(path: impl AsRef<Path>) -> Result<Vec<SignedOrder>, Error> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read orders file {}", path.display()))?;
+    let orders = serde_json::from_str(&contents)
+        .wrap_err_with(|| format!("invalid orders file {}", path.display()))?;
+    Ok(match orders {
+        OrdersFile::Many(orders) => orders,
+        OrdersFile::One(order) => vec![*order],
+    })
+}
+
+/// The deadline of an Order, in seconds.
+fn order_deadline(order: &SignedOrder) -> Result<u64, Error> {
+    order
+        .permit
+        .permit
+        .deadline
+        .to_string()
+        .parse::<u64>()
+        .map_err(|e| eyre!("invalid deadline in orders: {e}"))
+}
+
+/// The earliest of the Orders' deadlines and the Fills' deadlines, in seconds.
+fn earliest_deadline<'a>(
+    orders: &[SignedOrder],
+    fills: impl IntoIterator<Item = &'a SignedFill>,
+) -> Result<u64, Error> {
+    let fill_deadlines = fills.into_iter().map(|fill| {
+        fill.permit
+            .permit
+            .deadline
+            .to_string()
+            .parse::<u64>()
+            .map_err(|e| eyre!("invalid deadline in fill: {e}"))
+    });
+    orders
+        .iter()
+        .map(order_deadline)
+        .chain(fill_deadlines)
+        .try_fold(u64::MAX, |earliest, deadline| Ok(earliest.min(deadline?)))
+}
+
+/// Compare an Orders contract code hash against its pinned value, if any.
+fn check_code_hash(chain: &str, pinned: Option<B256>, code_hash: B256) -> Result<(), Error> {
+    match pinned {
+        Some(pinned) if pinned != code_hash => Err(eyre!(
+            "{chain} Orders contract code changed: acknowledged {pinned}, found {code_hash}; \
+             verify fill and initiate encodings, then update the acknowledged code hash"
+        )),
+        Some(_) => Ok(()),
+        None => {
+            warn!(chain, %code_hash, "Orders contract code hash is not pinned");
+            Ok(())
         }
-        Ok(encoded_txs)
     }
 }
@@ -0,0 +1,93 @@
+use crate::tx_cache::TxCacheError;
+use alloy::{
+    primitives::{Address, keccak256},
+    signers::{Signer, local::PrivateKeySigner},
+};
+use eyre::Error;
+use serde::Deserialize;
+use signet_bundle::SignetEthBundle;
+use uuid::Uuid;
+
+/// The default header carrying the attribution signature.
+pub const DEFAULT_ATTRIBUTION_HEADER: &str = "X-Flashbots-Signature";
+
+/// The transaction cache's response to a submitted Bundle.
+#[derive(Debug, Deserialize)]
+struct SendBundleResponse {
+    /// The id assigned to the Bundle by the cache.
+    id: Uuid,
+}
+
+/// Signs Bundle submissions with an attribution key, so builders and caches can attribute and
+/// prioritize Bundles from trusted Fillers.
+///
+/// Each submission carries a header of the form `<address>:<signature>`, where the signature is
+/// an EIP-191 signature over the hex-encoded keccak256 hash of the request body, as in
+/// Flashbots' `X-Flashbots-Signature`. The attribution key is separate from the fill signer, so
+/// a Filler's reputation is not tied to the key holding its inventory.
+#[derive(Debug, Clone)]
+pub struct BundleAttribution {
+    /// The attribution key.
+    signer: PrivateKeySigner,
+    /// The header carrying the signature.
+    header: String,
+    /// HTTP client used to submit attributed Bundles.
+    client: reqwest::Client,
+}
+
+impl BundleAttribution {
+    /// Attribute Bundles to `signer`, in the [`DEFAULT_ATTRIBUTION_HEADER`].
+    pub fn new(signer: PrivateKeySigner) -> Result<Self, Error> {
+        Ok(Self {
+            signer,
+            header: DEFAULT_ATTRIBUTION_HEADER.to_string(),
+            client: reqwest::ClientBuilder::new().use_rustls_tls().build()?,
+        })
+    }
+
+    /// Carry the signature in `header` instead of the [`DEFAULT_ATTRIBUTION_HEADER`].
+    pub fn with_header(mut self, header: impl Into<String>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    /// The address Bundles are attributed to.
+    pub fn address(&self) -> Address {
+        Signer::address(&self.signer)
+    }
+
+    /// Sign a request body, returning the header value.
+    pub async fn sign(&self, body: &[u8]) -> Result<String, Error> {
+        let digest = keccak256(body).to_string();
+        let signature = self.signer.sign_message(digest.as_bytes()).await?;
+        Ok(format!("{}:{signature}", self.address()))
+    }
+
+    /// Submit a Bundle to the transaction cache at `tx_cache_url` with an attribution header,
+    /// returning the id assigned by the cache.
+    pub(super) async fn forward_bundle(
+        &self,
+        tx_cache_url: &reqwest::Url,
+        bundle: &SignetEthBundle,
+    ) -> Result<Uuid, Error> {
+        let body = serde_json::to_vec(bundle)?;
+        let signature = self.sign(&body).await?;
+        let response: SendBundleResponse = async {
+            Ok::<_, Error>(
+                self.client
+                    .post(tx_cache_url.join("bundles")?)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .header(self.header.as_str(), signature)
+                    .body(body)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?,
+            )
+        }
+        .await
+        .map_err(|err| TxCacheError::classify("forward_bundle", err))?;
+        Ok(response.id)
+    }
+}
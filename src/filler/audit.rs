@@ -0,0 +1,136 @@
+use super::Filler;
+use crate::{
+    archive::ArchivedSubmission,
+    compliance::{ComplianceEvent, Decision},
+    ids::{BundleId, OrderHash},
+};
+use alloy::{
+    primitives::{Address, Bytes},
+    signers::Signer,
+};
+use eyre::Error;
+use init4_bin_base::deps::{metrics::counter, tracing::error};
+use signet_types::{SignedFill, SignedOrder};
+use std::collections::{HashMap, HashSet};
+
+/// Tracks why each Order considered for a fill was dropped, stage by stage.
+#[derive(Debug)]
+pub(super) struct Screening {
+    /// Each Order considered, and its owner, in the order given.
+    considered: Vec<(OrderHash, Address)>,
+    /// Why each dropped Order was dropped.
+    reasons: HashMap<OrderHash, &'static str>,
+}
+
+impl Screening {
+    /// Start screening the Orders.
+    pub(super) fn new(orders: &[SignedOrder]) -> Self {
+        Self {
+            considered: orders
+                .iter()
+                .map(|order| (OrderHash::from(order), order.permit.owner))
+                .collect(),
+            reasons: HashMap::new(),
+        }
+    }
+
+    /// Finish a screening stage: every Order still in play but not `remaining` was dropped for
+    /// `reason`.
+    pub(super) fn stage(&mut self, remaining: &[SignedOrder], reason: &'static str) {
+        let remaining: HashSet<OrderHash> = remaining.iter().map(OrderHash::from).collect();
+        for (order_hash, _) in &self.considered {
+            if !remaining.contains(order_hash) {
+                self.reasons.entry(*order_hash).or_insert(reason);
+            }
+        }
+    }
+
+    /// The first Order dropped, in the order given, and why it was dropped.
+    pub(super) fn first_dropped(&self) -> Option<(OrderHash, &'static str)> {
+        self.considered.iter().find_map(|(order_hash, _)| {
+            self.reasons
+                .get(order_hash)
+                .map(|&reason| (*order_hash, reason))
+        })
+    }
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Record the decision on each screened Order in the compliance log, if one is kept.
+    pub(super) fn record_screening(&self, screening: &Screening, now: u64) -> Result<(), Error> {
+        let Some(log) = &self.compliance_log else {
+            return Ok(());
+        };
+        for &(order_hash, owner) in &screening.considered {
+            let reason = screening.reasons.get(&order_hash);
+            let event = ComplianceEvent::OrderConsidered {
+                order_hash,
+                owner,
+                decision: if reason.is_some() {
+                    Decision::Skipped
+                } else {
+                    Decision::Accepted
+                },
+                reason: reason.map(|reason| reason.to_string()),
+            };
+            log.record(self.signer.address(), now, event)?;
+        }
+        Ok(())
+    }
+
+    /// Record a submitted fill in the compliance log, if one is kept.
+    ///
+    /// The Bundles have already been sent, so a failure to record the fill is logged rather
+    /// than failing the fill, and increments the `filler.compliance_failures` counter.
+    pub(super) fn record_fill(&self, orders: &[SignedOrder], bundle_ids: &[BundleId], now: u64) {
+        let Some(log) = &self.compliance_log else {
+            return;
+        };
+        let event = ComplianceEvent::FillExecuted {
+            order_hashes: orders.iter().map(OrderHash::from).collect(),
+            bundle_ids: bundle_ids.to_vec(),
+        };
+        if let Err(err) = log.record(self.signer.address(), now, event) {
+            error!(%err, "Failed to record submitted fill in the compliance log");
+            counter!("filler.compliance_failures").increment(1);
+        }
+    }
+
+    /// Archive exactly what was submitted in each Bundle, by target block, if an archive is
+    /// kept.
+    ///
+    /// The Bundles have already been sent, so a failure to archive one is logged rather than
+    /// failing the fill, and increments the `filler.archive_failures` counter.
+    pub(super) fn archive_submissions(
+        &self,
+        orders: &[SignedOrder],
+        signed_fills: &[SignedFill],
+        ru_txs: &[Bytes],
+        host_txs: &[Bytes],
+        bundles: impl IntoIterator<Item = (u64, BundleId)>,
+        now: u64,
+    ) {
+        let Some(archive) = &self.archive else {
+            return;
+        };
+        let order_hashes: Vec<OrderHash> = orders.iter().map(OrderHash::from).collect();
+        for (target_block, bundle_id) in bundles {
+            let submission = ArchivedSubmission {
+                bundle_id,
+                target_block,
+                submitted_at: now,
+                order_hashes: order_hashes.clone(),
+                fills: signed_fills.to_vec(),
+                ru_txs: ru_txs.to_vec(),
+                host_txs: host_txs.to_vec(),
+            };
+            if let Err(err) = archive.record(&submission) {
+                error!(%err, %bundle_id, "Failed to archive submitted bundle");
+                counter!("filler.archive_failures").increment(1);
+            }
+        }
+    }
+}
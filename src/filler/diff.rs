@@ -0,0 +1,169 @@
+use super::Filler;
+use crate::output::Render;
+use alloy::{rpc::types::TransactionRequest, signers::Signer};
+use eyre::Error;
+use init4_bin_base::deps::tracing::instrument;
+use serde::{Deserialize, Serialize};
+use signet_types::SignedOrder;
+
+/// The transactions planned to fill a set of Orders, before they are signed and encoded.
+///
+/// Plans are serializable, so a plan produced before a planner change can be diffed against one
+/// produced after it with [`BundlePlan::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BundlePlan {
+    /// The planned Rollup transactions, in Bundle order.
+    pub ru_txs: Vec<TransactionRequest>,
+    /// The planned Host transactions, in Bundle order.
+    pub host_txs: Vec<TransactionRequest>,
+}
+
+/// How a planned transaction differs between two [`BundlePlan`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TxChange {
+    /// The transaction is only in the later plan.
+    Added,
+    /// The transaction is only in the earlier plan.
+    Removed,
+    /// The transaction at this position differs between the plans.
+    Changed,
+    /// The same transaction is at a different position in the later plan.
+    Moved {
+        /// The transaction's position in the earlier plan.
+        from: usize,
+    },
+}
+
+/// A difference in one planned transaction between two [`BundlePlan`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TxDiff {
+    /// The chain the transaction is sent on, `rollup` or `host`.
+    pub chain: &'static str,
+    /// The transaction's position in the later plan, or in the earlier plan if removed.
+    pub index: usize,
+    /// How the transaction differs.
+    pub change: TxChange,
+    /// The fields that differ, for changed transactions: `to`, `input`, `value`, or `gas`.
+    pub fields: Vec<&'static str>,
+}
+
+impl Render for TxDiff {
+    fn id(&self) -> String {
+        format!("{}:{}", self.chain, self.index)
+    }
+
+    fn table_row(&self) -> String {
+        match self.change {
+            TxChange::Changed => format!(
+                "{} tx {} changed: {}",
+                self.chain,
+                self.index,
+                self.fields.join(", ")
+            ),
+            change => format!("{} tx {} {change:?}", self.chain, self.index),
+        }
+    }
+}
+
+impl BundlePlan {
+    /// Diff this plan against a later plan for the same Orders, listing each transaction that
+    /// was added, removed, changed, or moved.
+    pub fn diff(&self, after: &Self) -> Vec<TxDiff> {
+        let mut diffs = diff_txs("rollup", &self.ru_txs, &after.ru_txs);
+        diffs.extend(diff_txs("host", &self.host_txs, &after.host_txs));
+        diffs
+    }
+}
+
+/// The fields that differ between two transactions.
+fn changed_fields(before: &TransactionRequest, after: &TransactionRequest) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if before.to != after.to {
+        fields.push("to");
+    }
+    if before.input.input() != after.input.input() {
+        fields.push("input");
+    }
+    if before.value != after.value {
+        fields.push("value");
+    }
+    if before.gas != after.gas {
+        fields.push("gas");
+    }
+    fields
+}
+
+/// Diff the transactions planned on one chain.
+fn diff_txs(
+    chain: &'static str,
+    before: &[TransactionRequest],
+    after: &[TransactionRequest],
+) -> Vec<TxDiff> {
+    let same = |a: &TransactionRequest, b: &TransactionRequest| changed_fields(a, b).is_empty();
+    let mut matched = vec![false; before.len()];
+    let mut diffs = Vec::new();
+
+    // transactions unchanged in place
+    for (index, tx) in after.iter().enumerate() {
+        if before.get(index).is_some_and(|prev| same(prev, tx)) {
+            matched[index] = true;
+        }
+    }
+
+    for (index, tx) in after.iter().enumerate() {
+        if before.get(index).is_some_and(|prev| same(prev, tx)) {
+            continue;
+        }
+        // the same transaction elsewhere in the earlier plan was moved
+        let moved_from = (0..before.len()).find(|&from| !matched[from] && same(&before[from], tx));
+        let (change, fields) = match (moved_from, before.get(index)) {
+            (Some(from), _) => {
+                matched[from] = true;
+                (TxChange::Moved { from }, vec![])
+            }
+            (None, Some(prev)) if !matched[index] => {
+                matched[index] = true;
+                (TxChange::Changed, changed_fields(prev, tx))
+            }
+            (None, _) => (TxChange::Added, vec![]),
+        };
+        diffs.push(TxDiff {
+            chain,
+            index,
+            change,
+            fields,
+        });
+    }
+
+    diffs.extend(
+        matched
+            .iter()
+            .enumerate()
+            .filter(|&(_, matched)| !matched)
+            .map(|(index, _)| TxDiff {
+                chain,
+                index,
+                change: TxChange::Removed,
+                fields: vec![],
+            }),
+    );
+    diffs
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Plan the transactions that [`Filler::fill`] would send for the Orders, without sending
+    /// anything.
+    ///
+    /// This signs Fills for the Orders, so the planned calldata matches a real fill.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn plan(&self, orders: &[SignedOrder]) -> Result<BundlePlan, Error> {
+        let signed_fills = self.sign_fills(orders).await?;
+        Ok(BundlePlan {
+            ru_txs: self.rollup_txn_requests(&signed_fills, orders).await?,
+            host_txs: self.host_txn_requests(&signed_fills, orders).await?,
+        })
+    }
+}
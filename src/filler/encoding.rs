@@ -0,0 +1,80 @@
+use super::TxEnvelopeKind;
+use alloy::{
+    primitives::{B256, Bytes, keccak256},
+    rpc::types::TransactionRequest,
+};
+use init4_bin_base::deps::{metrics::counter, tracing::debug};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Signed, encoded transaction batches cached by the requests they were built from.
+///
+/// Within a submission burst, the same Orders are often filled repeatedly, e.g. on retries or
+/// when resubmitted for later target blocks. With the Fill signatures cached, those attempts
+/// produce identical transaction requests, so the cached batch is reused instead of filling
+/// and signing the transactions again. Batches are keyed by the signer's nonce as well as the
+/// requests, so once a batch lands, an identical batch is signed afresh rather than replaying
+/// transactions that already executed. Entries expire after the cache TTL, so transactions are
+/// re-signed at current gas prices once a burst ends. Cache hits increment the
+/// `filler.tx_encoding_cache_hits` counter.
+#[derive(Debug)]
+pub struct TxEncodingCache {
+    /// How long a batch is reused.
+    ttl: Duration,
+    /// Encoded batches, and when they were signed, by request hash.
+    batches: Mutex<HashMap<B256, (Instant, Vec<Bytes>)>>,
+}
+
+impl TxEncodingCache {
+    /// Create a cache reusing batches for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            batches: Mutex::default(),
+        }
+    }
+
+    /// Hash a batch of transaction requests for a chain, signed as `tx_kind` with nonces from
+    /// `nonce`.
+    pub fn request_hash(
+        chain_id: u64,
+        tx_kind: TxEnvelopeKind,
+        nonce: u64,
+        tx_requests: &[TransactionRequest],
+    ) -> Result<B256, serde_json::Error> {
+        let requests = serde_json::to_vec(tx_requests)?;
+        Ok(keccak256(
+            [
+                chain_id.to_be_bytes().as_slice(),
+                &[tx_kind as u8],
+                &nonce.to_be_bytes(),
+                &requests,
+            ]
+            .concat(),
+        ))
+    }
+
+    /// The cached batch for the request hash, if signed within the TTL.
+    pub fn get(&self, request_hash: B256) -> Option<Vec<Bytes>> {
+        let batches = self.batches.lock().unwrap();
+        let txs = batches
+            .get(&request_hash)
+            .filter(|(signed_at, _)| signed_at.elapsed() < self.ttl)
+            .map(|(_, txs)| txs.clone());
+        if txs.is_some() {
+            debug!(%request_hash, "Reusing cached encoded transactions");
+            counter!("filler.tx_encoding_cache_hits").increment(1);
+        }
+        txs
+    }
+
+    /// Cache the batch for the request hash, pruning expired entries.
+    pub fn insert(&self, request_hash: B256, txs: Vec<Bytes>) {
+        let mut batches = self.batches.lock().unwrap();
+        batches.retain(|_, (signed_at, _)| signed_at.elapsed() < self.ttl);
+        batches.insert(request_hash, (Instant::now(), txs));
+    }
+}
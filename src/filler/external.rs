@@ -0,0 +1,332 @@
+use super::{Filler, audit::Screening, earliest_deadline};
+use crate::{
+    clock::chain_time,
+    contracts::IERC20,
+    ids::{BundleId, OrderHash},
+};
+use alloy::{
+    network::TransactionBuilder,
+    primitives::{Address, Bytes, U256},
+    rpc::types::TransactionRequest,
+    signers::Signer,
+    sol_types::SolCall,
+};
+use eyre::{Error, eyre};
+use init4_bin_base::deps::tracing::{debug, info, instrument};
+use serde::{Deserialize, Serialize};
+use signet_types::{SignedFill, SignedOrder};
+use std::{
+    collections::{HashMap, HashSet},
+    slice::from_ref,
+};
+
+/// The chain a [`PlanAction`] executes on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanChain {
+    /// The Rollup.
+    Rollup,
+    /// The Host.
+    Host,
+}
+
+/// One step of an [`ExternalPlan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PlanAction {
+    /// An aggregate Fill of the Orders' Outputs on a chain.
+    Fill {
+        /// The chain the Fill is submitted on.
+        chain: PlanChain,
+        /// The Orders filled.
+        orders: Vec<OrderHash>,
+    },
+    /// Initiation of an Order on the Rollup.
+    Initiate {
+        /// The Order initiated.
+        order: OrderHash,
+    },
+    /// A call to a swap router or other contract, with opaque calldata.
+    Swap {
+        /// The chain the call is made on.
+        chain: PlanChain,
+        /// The contract called.
+        to: Address,
+        /// Native value sent with the call.
+        #[serde(default)]
+        value: U256,
+        /// The calldata.
+        data: Bytes,
+    },
+    /// An ERC20 approval.
+    Approve {
+        /// The chain the approval is made on.
+        chain: PlanChain,
+        /// The token approved.
+        token: Address,
+        /// The approved spender.
+        spender: Address,
+        /// The approved amount.
+        amount: U256,
+    },
+}
+
+/// A fill plan produced outside this crate: the Orders to fill, and the ordered actions filling
+/// them.
+///
+/// Actions on each chain are submitted in the order given. Host transactions are always
+/// considered mined before the Rollup block, but a Rollup Fill must precede the Initiate of
+/// every Order it fills.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalPlan {
+    /// The Orders referenced by the actions.
+    pub orders: Vec<SignedOrder>,
+    /// The actions, in order.
+    pub actions: Vec<PlanAction>,
+}
+
+impl ExternalPlan {
+    /// Check that every action references an Order in the plan, that each Order is filled at
+    /// most once on each chain and initiated at most once, that Orders with Rollup Outputs are
+    /// filled on the Rollup before they are initiated, and that Orders with Outputs on the Host
+    /// are filled on the Host.
+    pub fn validate(&self, rollup_chain_id: u64) -> Result<(), Error> {
+        let orders: HashMap<OrderHash, &SignedOrder> = self
+            .orders
+            .iter()
+            .map(|order| (OrderHash::from(order), order))
+            .collect();
+        let lookup = |order_hash: &OrderHash| {
+            orders
+                .get(order_hash)
+                .copied()
+                .ok_or_else(|| eyre!("plan references order {order_hash} it does not include"))
+        };
+
+        let mut filled_on_rollup = HashSet::new();
+        let mut filled_on_host = HashSet::new();
+        let mut initiated = HashSet::new();
+        for (index, action) in self.actions.iter().enumerate() {
+            match action {
+                PlanAction::Fill { chain, orders } => {
+                    if orders.is_empty() {
+                        eyre::bail!("fill at action {index} fills no orders");
+                    }
+                    let filled = match chain {
+                        PlanChain::Rollup => &mut filled_on_rollup,
+                        PlanChain::Host => &mut filled_on_host,
+                    };
+                    for order_hash in orders {
+                        lookup(order_hash)?;
+                        if !filled.insert(*order_hash) {
+                            eyre::bail!(
+                                "order {order_hash} is filled more than once on the {chain:?}"
+                            );
+                        }
+                    }
+                }
+                PlanAction::Initiate { order } => {
+                    let signed = lookup(order)?;
+                    if !initiated.insert(*order) {
+                        eyre::bail!("order {order} is initiated more than once");
+                    }
+                    let rollup_outputs = signed
+                        .outputs
+                        .iter()
+                        .any(|output| u64::from(output.chainId) == rollup_chain_id);
+                    if rollup_outputs && !filled_on_rollup.contains(order) {
+                        eyre::bail!(
+                            "order {order} is initiated at action {index} before its rollup fill"
+                        );
+                    }
+                }
+                PlanAction::Swap { .. } | PlanAction::Approve { .. } => {}
+            }
+        }
+
+        // Host transactions are mined before the Rollup block, so a Host fill may come anywhere
+        for order in &self.orders {
+            let order_hash = OrderHash::from(order);
+            let host_outputs = order
+                .outputs
+                .iter()
+                .any(|output| u64::from(output.chainId) != rollup_chain_id);
+            if host_outputs && !filled_on_host.contains(&order_hash) {
+                eyre::bail!("order {order_hash} has host outputs but no host fill");
+            }
+        }
+        Ok(())
+    }
+
+    /// The Orders referenced by the given hashes, in the given order.
+    fn select(&self, order_hashes: &[OrderHash]) -> Vec<SignedOrder> {
+        order_hashes
+            .iter()
+            .filter_map(|order_hash| {
+                self.orders
+                    .iter()
+                    .find(|order| OrderHash::from(*order) == *order_hash)
+                    .cloned()
+            })
+            .collect()
+    }
+}
+
+/// An [`ExternalPlan`] compiled into signed, encoded transactions ready for a Bundle.
+#[derive(Debug, Clone)]
+pub struct CompiledPlan {
+    /// The signed Rollup transactions, in order.
+    pub ru_txs: Vec<Bytes>,
+    /// The signed Host transactions, in order.
+    pub host_txs: Vec<Bytes>,
+    /// The signed Fills, in the order of the plan's Fill actions.
+    pub fills: Vec<SignedFill>,
+    /// The earliest deadline of the Orders and Fills; Bundles must land before it.
+    pub deadline: u64,
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Compile an externally produced plan into signed, encoded Bundle transactions, using the
+    /// Filler's signer, transaction settings, and Fill deadline policy.
+    ///
+    /// The plan is validated first; see [`ExternalPlan::validate`]. Each Fill action signs an
+    /// aggregate Fill of its Orders, so the same Orders filled on both chains share one
+    /// signature. Nothing is submitted.
+    #[instrument(skip_all, fields(orders_count = plan.orders.len(), actions_count = plan.actions.len()))]
+    pub async fn compile_plan(&self, plan: &ExternalPlan) -> Result<CompiledPlan, Error> {
+        plan.validate(self.constants.rollup().chain_id())?;
+
+        let now = chain_time(&self.ru_provider).await?;
+        let mut ru_requests = Vec::new();
+        let mut host_requests = Vec::new();
+        let mut signed_fills: Vec<SignedFill> = Vec::new();
+        for action in &plan.actions {
+            let (chain, tx) = match action {
+                PlanAction::Fill { chain, orders } => {
+                    let orders = plan.select(orders);
+                    let deadline = self.fill_deadline(&orders, now)?;
+                    let fills = self.sign_fill(&orders, deadline, now).await?;
+                    let (chain_id, contract) = match chain {
+                        PlanChain::Rollup => (
+                            self.constants.rollup().chain_id(),
+                            self.constants.rollup().orders(),
+                        ),
+                        PlanChain::Host => (
+                            self.constants.host().chain_id(),
+                            self.constants.host().orders(),
+                        ),
+                    };
+                    let fill = fills.get(&chain_id).ok_or_else(|| {
+                        eyre!("orders have no outputs on chain {chain_id} to fill")
+                    })?;
+                    let tx = fill.to_fill_tx(contract);
+                    signed_fills.push(fill.clone());
+                    (*chain, tx)
+                }
+                PlanAction::Initiate { order } => {
+                    let order = plan.select(from_ref(order)).remove(0);
+                    let tx = order
+                        .to_initiate_tx(self.signer.address(), self.constants.rollup().orders());
+                    (PlanChain::Rollup, tx)
+                }
+                PlanAction::Swap {
+                    chain,
+                    to,
+                    value,
+                    data,
+                } => (
+                    *chain,
+                    TransactionRequest::default()
+                        .with_to(*to)
+                        .with_value(*value)
+                        .with_input(data.clone()),
+                ),
+                PlanAction::Approve {
+                    chain,
+                    token,
+                    spender,
+                    amount,
+                } => (
+                    *chain,
+                    TransactionRequest::default().with_to(*token).with_input(
+                        IERC20::approveCall {
+                            spender: *spender,
+                            amount: *amount,
+                        }
+                        .abi_encode(),
+                    ),
+                ),
+            };
+            match chain {
+                PlanChain::Rollup => ru_requests.push(tx),
+                PlanChain::Host => host_requests.push(tx),
+            }
+        }
+        debug!(
+            ?ru_requests,
+            ?host_requests,
+            "Compiled plan transaction requests"
+        );
+
+        let deadline = earliest_deadline(&plan.orders, &signed_fills)?;
+        let ru_txs = self
+            .sign_and_encode_txns(&self.ru_provider, ru_requests)
+            .await?;
+        let host_txs = self
+            .sign_and_encode_txns(&self.host_provider, host_requests)
+            .await?;
+        Ok(CompiledPlan {
+            ru_txs,
+            host_txs,
+            fills: signed_fills,
+            deadline,
+        })
+    }
+
+    /// Compile an externally produced plan and submit it as a Bundle, once per target block.
+    ///
+    /// Orders are screened as in [`Filler::fill`]: the plan is refused during a maintenance
+    /// window, when this replica is not the leader, if any Order is screened out or reserved by
+    /// another replica, or if the Orders contracts have changed or gas is spiking. Inventory and
+    /// simulation checks are left to the external planner, whose plan may source its own
+    /// inventory.
+    #[instrument(skip_all, fields(orders_count = plan.orders.len()))]
+    pub async fn submit_plan(&self, plan: &ExternalPlan) -> Result<Vec<BundleId>, Error> {
+        let now = chain_time(&self.ru_provider).await?;
+        self.check_maintenance(now)?;
+        self.check_leader().await?;
+
+        let (mut screening, orders) = self.screen(&plan.orders, now).await?;
+        let reservation = self.reserve(orders).await?;
+        screening.stage(&reservation.orders, "reserved by another replica");
+
+        // if the plan is refused or fails before any Bundle is sent, release the reservation so
+        // other replicas can take the orders without waiting for it to expire
+        let result = self.submit_reserved(plan, &screening, now).await;
+        self.release_on_error(&reservation, result).await
+    }
+
+    /// Submit a screened plan whose Orders are reserved for this replica, recording the
+    /// screening first.
+    async fn submit_reserved(
+        &self,
+        plan: &ExternalPlan,
+        screening: &Screening,
+        now: u64,
+    ) -> Result<Vec<BundleId>, Error> {
+        // the plan fills every Order or none, so refuse it if any Order is screened out
+        self.record_screening(screening, now)?;
+        if let Some((order_hash, reason)) = screening.first_dropped() {
+            eyre::bail!("plan includes order {order_hash} that was screened out: {reason}");
+        }
+        self.check_chains().await?;
+
+        let compiled = self.compile_plan(plan).await?;
+        let bundle_ids = self.submit_compiled(&plan.orders, &compiled, now).await?;
+        info!(bundles_count = bundle_ids.len(), "Submitted external plan");
+        Ok(bundle_ids)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::analytics::TokenPair;
+use signet_types::SignedOrder;
+use std::collections::{HashMap, VecDeque};
+
+/// How Orders competing for limited inventory and block space are ordered across token pairs.
+///
+/// Without a policy, Orders are filled in the order the transaction cache returns them, so a
+/// single busy pair can consume all inventory before Orders in other pairs are reached.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FairnessPolicy {
+    /// Take one Order from each pair in turn.
+    #[default]
+    RoundRobin,
+    /// Take up to `weight` Orders from each pair in turn. Pairs without a configured weight
+    /// have weight 1, and pairs with weight 0 are taken only once all other pairs are drained.
+    Weighted(HashMap<TokenPair, u32>),
+}
+
+impl FairnessPolicy {
+    /// The number of Orders taken from a pair in each turn.
+    fn weight(&self, pair: Option<&TokenPair>) -> u32 {
+        match (self, pair) {
+            (Self::Weighted(weights), Some(pair)) => weights.get(pair).copied().unwrap_or(1),
+            _ => 1,
+        }
+    }
+
+    /// Reorder the Orders across token pairs according to the policy.
+    ///
+    /// Pairs take turns in the order they first appear, and Orders within a pair keep their
+    /// relative order. Orders without both an Input and an Output are treated as one pair.
+    pub fn arrange(&self, orders: &[SignedOrder]) -> Vec<SignedOrder> {
+        let mut queues: Vec<(Option<TokenPair>, VecDeque<&SignedOrder>)> = Vec::new();
+        for order in orders {
+            let pair = TokenPair::of_order(order);
+            match queues.iter_mut().find(|(queued, _)| *queued == pair) {
+                Some((_, queue)) => queue.push_back(order),
+                None => queues.push((pair, VecDeque::from([order]))),
+            }
+        }
+
+        let mut arranged = Vec::with_capacity(orders.len());
+        while arranged.len() < orders.len() {
+            let before = arranged.len();
+            for (pair, queue) in &mut queues {
+                let take = (self.weight(pair.as_ref()) as usize).min(queue.len());
+                arranged.extend(queue.drain(..take).cloned());
+            }
+            // only zero-weight pairs remain; drain them in turn
+            if arranged.len() == before {
+                for (_, queue) in &mut queues {
+                    arranged.extend(queue.pop_front().cloned());
+                }
+            }
+        }
+        arranged
+    }
+}
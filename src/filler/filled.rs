@@ -0,0 +1,71 @@
+use crate::ids::OrderHash;
+use eyre::{Error, WrapErr};
+use init4_bin_base::deps::tracing::debug;
+use std::{collections::HashMap, fs, path::PathBuf, sync::Mutex};
+
+/// A window of recently filled Order hashes, optionally persisted to a JSON file.
+///
+/// The Filler refuses to fill any Order in the window, even if the transaction cache serves it
+/// again, protecting against cache rollback or poisoning. Entries older than the window are
+/// pruned as new entries are recorded.
+#[derive(Debug)]
+pub struct FilledOrders {
+    /// The file the window is persisted to, if any.
+    path: Option<PathBuf>,
+    /// How long Order hashes are remembered, in seconds.
+    window: u64,
+    /// When each Order was seen filled, by Order hash.
+    filled: Mutex<HashMap<OrderHash, u64>>,
+}
+
+impl FilledOrders {
+    /// Create an in-memory window, remembering filled Orders for `window` seconds.
+    pub fn in_memory(window: u64) -> Self {
+        Self {
+            path: None,
+            window,
+            filled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Open a window persisted to the given file, loading any Order hashes recorded there.
+    pub fn open(path: impl Into<PathBuf>, window: u64) -> Result<Self, Error> {
+        let path = path.into();
+        let filled = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .wrap_err_with(|| format!("invalid filled orders file {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => {
+                return Err(err).wrap_err_with(|| format!("failed to read {}", path.display()));
+            }
+        };
+        Ok(Self {
+            path: Some(path),
+            window,
+            filled: Mutex::new(filled),
+        })
+    }
+
+    /// Whether the Order has been seen filled within the window.
+    pub fn contains(&self, order_hash: &OrderHash) -> bool {
+        self.filled.lock().unwrap().contains_key(order_hash)
+    }
+
+    /// Record an Order as filled at time `now`, pruning entries older than the window and
+    /// persisting the result.
+    pub fn record(&self, order_hash: OrderHash, now: u64) -> Result<(), Error> {
+        let mut filled = self.filled.lock().unwrap();
+        filled.insert(order_hash, now);
+        filled.retain(|_, filled_at| *filled_at + self.window >= now);
+        debug!(%order_hash, window_len = filled.len(), "Recorded filled order");
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        // write to a temporary file and rename it, so a crash never leaves a truncated file
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string(&*filled)?)
+            .wrap_err_with(|| format!("failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).wrap_err_with(|| format!("failed to write {}", path.display()))
+    }
+}
@@ -0,0 +1,292 @@
+use super::Filler;
+use crate::{analytics::ChainToken, contracts::IERC20, ids::OrderHash};
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+    signers::Signer,
+};
+use eyre::Error;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{debug, info, instrument},
+};
+use signet_types::SignedOrder;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// The value to the Filler of filling an Order, used to choose which Orders to fill when
+/// inventory can't cover them all.
+pub trait OrderValue: fmt::Debug + Send + Sync {
+    /// The profit of filling the Order, in any consistent unit. Orders valued at zero or less
+    /// are never selected when inventory is short.
+    fn value(&self, order: &SignedOrder) -> f64;
+}
+
+/// Values every Order equally, so as many Orders as possible are filled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EqualValue;
+
+impl OrderValue for EqualValue {
+    fn value(&self, _order: &SignedOrder) -> f64 {
+        1.0
+    }
+}
+
+/// The Output amounts the Filler must provide to fill the Orders, by chain and token.
+pub fn required_outputs(orders: &[SignedOrder]) -> HashMap<ChainToken, U256> {
+    let mut required: HashMap<ChainToken, U256> = HashMap::new();
+    for output in orders.iter().flat_map(|order| &order.outputs) {
+        let amount = required
+            .entry((u64::from(output.chainId), output.token))
+            .or_default();
+        *amount = amount.saturating_add(output.amount);
+    }
+    required
+}
+
+/// Select the most valuable subset of the Orders whose Outputs fit within `inventory`.
+///
+/// This is a multi-dimensional knapsack, solved greedily: Orders are taken in decreasing order
+/// of value per unit of their scarcest inventory, skipping any that no longer fit. If a single
+/// Order is worth more than the whole greedy selection, it is taken alone instead. The selected
+/// Orders keep their relative order.
+pub fn select_within_inventory(
+    orders: &[SignedOrder],
+    inventory: &HashMap<ChainToken, U256>,
+    value: &dyn OrderValue,
+) -> Vec<SignedOrder> {
+    let fits = |order: &SignedOrder, remaining: &HashMap<ChainToken, U256>| {
+        required_outputs(std::slice::from_ref(order))
+            .iter()
+            .all(|(token, amount)| remaining.get(token).is_some_and(|held| amount <= held))
+    };
+
+    // each candidate is (index, value, density), skipping Orders that can never fit
+    let mut candidates: Vec<(usize, f64, f64)> = orders
+        .iter()
+        .enumerate()
+        .filter(|(_, order)| fits(order, inventory))
+        .filter_map(|(index, order)| {
+            let value = value.value(order);
+            if value <= 0.0 {
+                return None;
+            }
+            // the largest share of any one held token the Order uses
+            let weight = required_outputs(std::slice::from_ref(order))
+                .iter()
+                .map(|(token, amount)| f64::from(*amount) / f64::from(inventory[token]))
+                .fold(0.0, f64::max);
+            let density = if weight > 0.0 {
+                value / weight
+            } else {
+                f64::INFINITY
+            };
+            Some((index, value, density))
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut remaining = inventory.clone();
+    let mut selected = Vec::new();
+    let mut selected_value = 0.0;
+    for &(index, value, _) in &candidates {
+        let order = &orders[index];
+        if !fits(order, &remaining) {
+            continue;
+        }
+        for (token, amount) in required_outputs(std::slice::from_ref(order)) {
+            if let Some(held) = remaining.get_mut(&token) {
+                *held -= amount;
+            }
+        }
+        selected.push(index);
+        selected_value += value;
+    }
+
+    // greedy selection can be beaten by the single most valuable Order
+    if let Some(&(best, best_value, _)) = candidates.iter().max_by(|a, b| a.1.total_cmp(&b.1))
+        && best_value > selected_value
+    {
+        selected = vec![best];
+    }
+
+    selected.sort_unstable();
+    selected
+        .into_iter()
+        .map(|index| orders[index].clone())
+        .collect()
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// The signer's balance of each token, by chain and token. Tokens on chains other than the
+    /// Rollup and Host are held in zero amount.
+    pub(super) async fn inventory(
+        &self,
+        tokens: impl IntoIterator<Item = ChainToken>,
+    ) -> Result<HashMap<ChainToken, U256>, Error> {
+        let owner = self.signer.address();
+        let mut inventory = HashMap::new();
+        for (chain_id, token) in tokens {
+            let provider = if chain_id == self.constants.rollup().chain_id() {
+                &self.ru_provider
+            } else if chain_id == self.constants.host().chain_id() {
+                &self.host_provider
+            } else {
+                inventory.insert((chain_id, token), U256::ZERO);
+                continue;
+            };
+            let balance = if token == Address::ZERO {
+                provider.get_balance(owner).await?
+            } else {
+                IERC20::new(token, provider).balanceOf(owner).call().await?
+            };
+            inventory.insert((chain_id, token), balance);
+        }
+        Ok(inventory)
+    }
+
+    /// If inventory selection is configured and the Orders' Outputs exceed the signer's
+    /// inventory, keep only the most valuable subset that fits.
+    ///
+    /// Deferred Orders increment the `filler.inventory_deferred` counter; they remain in the
+    /// transaction cache and can be filled once inventory is replenished.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub(super) async fn fit_inventory(
+        &self,
+        orders: Vec<SignedOrder>,
+    ) -> Result<Vec<SignedOrder>, Error> {
+        let Some(value) = &self.inventory_selection else {
+            return Ok(orders);
+        };
+
+        let required = required_outputs(&orders);
+        let inventory = self.inventory(required.keys().copied()).await?;
+        let short = required
+            .iter()
+            .any(|(token, amount)| inventory.get(token).is_none_or(|held| amount > held));
+        if !short {
+            return Ok(orders);
+        }
+
+        let selected = select_within_inventory(&orders, &inventory, value.as_ref());
+        let selected_hashes: HashSet<OrderHash> = selected.iter().map(OrderHash::from).collect();
+        for order in &orders {
+            let order_hash = OrderHash::from(order);
+            if !selected_hashes.contains(&order_hash) {
+                debug!(%order_hash, "Deferring order; inventory short");
+            }
+        }
+        let deferred = orders.len() - selected.len();
+        info!(
+            selected = selected.len(),
+            deferred, "Inventory short; filling the most valuable subset of orders"
+        );
+        counter!("filler.inventory_deferred").increment(deferred as u64);
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Bytes;
+    use signet_zenith::RollupOrders::{
+        Output, Permit2Batch, PermitBatchTransferFrom, TokenPermissions,
+    };
+
+    const CHAIN_ID: u64 = 1;
+    const WETH: Address = Address::repeat_byte(1);
+    const USDC: Address = Address::repeat_byte(2);
+
+    /// Values each Order by its nonce, so tests can set values per Order.
+    #[derive(Debug)]
+    struct ByNonce;
+
+    impl OrderValue for ByNonce {
+        fn value(&self, order: &SignedOrder) -> f64 {
+            f64::from(order.permit.permit.nonce)
+        }
+    }
+
+    /// An unsigned Order with the given nonce and Outputs; selection never checks signatures.
+    fn order(nonce: u64, outputs: &[(Address, u64)]) -> SignedOrder {
+        SignedOrder::new(
+            Permit2Batch {
+                permit: PermitBatchTransferFrom {
+                    permitted: vec![TokenPermissions {
+                        token: WETH,
+                        amount: U256::from(1),
+                    }],
+                    nonce: U256::from(nonce),
+                    deadline: U256::MAX,
+                },
+                owner: Address::ZERO,
+                signature: Bytes::new(),
+            },
+            outputs
+                .iter()
+                .map(|&(token, amount)| Output {
+                    token,
+                    amount: U256::from(amount),
+                    recipient: Address::ZERO,
+                    chainId: CHAIN_ID as u32,
+                })
+                .collect(),
+        )
+    }
+
+    fn inventory(held: &[(Address, u64)]) -> HashMap<ChainToken, U256> {
+        held.iter()
+            .map(|&(token, amount)| ((CHAIN_ID, token), U256::from(amount)))
+            .collect()
+    }
+
+    #[test]
+    fn orders_filling_inventory_exactly_are_all_selected() {
+        let orders = [order(1, &[(WETH, 60)]), order(2, &[(WETH, 40)])];
+        let selected = select_within_inventory(&orders, &inventory(&[(WETH, 100)]), &ByNonce);
+        assert_eq!(selected, orders);
+    }
+
+    #[test]
+    fn single_valuable_order_beats_greedy_selection() {
+        // the small Orders are denser, but together worth less than the large one
+        let orders = [
+            order(2, &[(WETH, 10)]),
+            order(10, &[(WETH, 100)]),
+            order(2, &[(WETH, 10)]),
+        ];
+        let selected = select_within_inventory(&orders, &inventory(&[(WETH, 100)]), &ByNonce);
+        assert_eq!(selected, [orders[1].clone()]);
+    }
+
+    #[test]
+    fn orders_valued_at_zero_are_never_selected() {
+        let orders = [order(0, &[(WETH, 10)]), order(1, &[(WETH, 10)])];
+        let selected = select_within_inventory(&orders, &inventory(&[(WETH, 15)]), &ByNonce);
+        assert_eq!(selected, [orders[1].clone()]);
+    }
+
+    #[test]
+    fn orders_with_zero_outputs_always_fit() {
+        let orders = [order(1, &[(WETH, 100)]), order(1, &[(WETH, 0)])];
+        let selected = select_within_inventory(&orders, &inventory(&[(WETH, 100)]), &ByNonce);
+        assert_eq!(selected, orders);
+    }
+
+    #[test]
+    fn orders_needing_tokens_not_held_are_skipped() {
+        let orders = [
+            order(5, &[(USDC, 1)]),
+            order(1, &[(WETH, 10)]),
+            order(5, &[(WETH, 10), (USDC, 1)]),
+        ];
+        let selected = select_within_inventory(&orders, &inventory(&[(WETH, 100)]), &ByNonce);
+        assert_eq!(selected, [orders[1].clone()]);
+    }
+}
@@ -0,0 +1,100 @@
+use init4_bin_base::deps::{metrics::histogram, tracing::warn};
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+/// A named stage of the fill path, used to label latency metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FillStage {
+    /// Fetching Orders from the transaction cache.
+    Fetch,
+    /// Filtering Orders and running pre-fill checks.
+    Evaluate,
+    /// Signing the Fills.
+    SignFill,
+    /// Planning, and optionally simulating, the Bundle transactions.
+    Plan,
+    /// Signing and encoding the Bundle transactions.
+    SignTxs,
+    /// Submitting the Bundles to the transaction cache.
+    Submit,
+}
+
+impl FillStage {
+    /// The stage's name, as used in metric labels and logs.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Fetch => "fetch",
+            Self::Evaluate => "evaluate",
+            Self::SignFill => "sign_fill",
+            Self::Plan => "plan",
+            Self::SignTxs => "sign_txs",
+            Self::Submit => "submit",
+        }
+    }
+
+    /// Record the time spent in this stage in the `filler.stage_duration_ms` histogram.
+    pub(super) fn record(self, elapsed: Duration) {
+        histogram!("filler.stage_duration_ms", "stage" => self.as_str())
+            .record(elapsed.as_secs_f64() * 1000.0);
+    }
+}
+
+/// Times each stage of a single fill.
+#[derive(Debug)]
+pub(super) struct StageTimer {
+    /// When the fill started.
+    started: Instant,
+    /// When the current stage started.
+    stage_started: Instant,
+    /// Time spent in each finished stage, in order.
+    stages: Vec<(FillStage, Duration)>,
+}
+
+impl StageTimer {
+    /// Start timing a fill.
+    pub(super) fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            stage_started: now,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Finish the current stage, recording its duration, and start the next.
+    pub(super) fn finish(&mut self, stage: FillStage) {
+        let elapsed = self.stage_started.elapsed();
+        stage.record(elapsed);
+        self.stages.push((stage, elapsed));
+        self.stage_started = Instant::now();
+    }
+
+    /// Start the next stage now, so time spent queued between stages of a pipelined fill is
+    /// left out of the stage's duration. The total duration of the fill still includes it.
+    pub(super) fn resume(&mut self) {
+        self.stage_started = Instant::now();
+    }
+
+    /// Record the total duration of the fill in the `filler.fill_duration_ms` histogram, and
+    /// log a per-stage breakdown if it exceeded the budget.
+    pub(super) fn report(&self, budget: Option<Duration>) {
+        let total = self.started.elapsed();
+        histogram!("filler.fill_duration_ms").record(total.as_secs_f64() * 1000.0);
+
+        let Some(budget) = budget.filter(|budget| total > *budget) else {
+            return;
+        };
+        let mut breakdown = String::new();
+        for (stage, elapsed) in &self.stages {
+            let _ = write!(breakdown, "{}={}ms ", stage.as_str(), elapsed.as_millis());
+        }
+        warn!(
+            total_ms = total.as_millis() as u64,
+            budget_ms = budget.as_millis() as u64,
+            breakdown = breakdown.trim_end(),
+            "Fill exceeded its latency budget"
+        );
+    }
+}
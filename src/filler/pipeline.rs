@@ -0,0 +1,281 @@
+use super::{EvaluatedFill, Filler, PlannedFill};
+use crate::{ids::OrderHash, watcher::Watcher};
+use alloy::signers::Signer;
+use init4_bin_base::deps::{
+    metrics::{counter, gauge},
+    tracing::{error, info},
+};
+use signet_types::SignedOrder;
+use std::{collections::HashSet, future::Future, sync::Arc};
+use tokio::{
+    sync::{Semaphore, mpsc},
+    task::JoinHandle,
+};
+
+/// Default maximum number of Orders filled in one Bundle.
+const DEFAULT_BATCH_SIZE: usize = 16;
+
+/// Default number of batches evaluated at once.
+const DEFAULT_EVALUATE_CONCURRENCY: usize = 4;
+
+/// Default number of fills planned at once.
+const DEFAULT_PLAN_CONCURRENCY: usize = 4;
+
+/// Default number of fills submitted at once.
+const DEFAULT_SUBMIT_CONCURRENCY: usize = 4;
+
+/// Fills Orders as a [`Watcher`] finds them in the transaction cache, in stages connected by
+/// bounded channels, so a slow stage slows the stages before it instead of accumulating Orders
+/// in memory.
+///
+/// The stages mirror [`Filler::fill`]:
+/// - fetch: the Watcher polls the transaction cache, queueing new Orders up to its capacity.
+/// - evaluate: queued Orders are taken in batches of up to the batch size, and each batch is
+///   screened, reserved, and checked with [`Filler::evaluate`].
+/// - plan: each evaluated fill is signed, planned, and simulated with [`Filler::plan_fill`].
+/// - submit: each planned fill is sent to the transaction cache with [`Filler::submit_fill`].
+///
+/// Each stage has its own concurrency limit, and the channel feeding a stage holds at most as
+/// many items as the stage runs at once. When every slot of a stage is busy, items stay queued
+/// and the stage before it waits to hand over its results, so a slow simulation backend backs up
+/// into evaluation, and a slow transaction cache into planning, and both eventually into
+/// polling.
+///
+/// Orders are forgotten by the Watcher once their fill is submitted or fails, or once they are
+/// screened out, so Orders still in the transaction cache are queued again, as when a fill
+/// fails or an Order is deferred. The depth of each stage's queue is exported as the
+/// `pipeline.queue_depth` gauge, and failures increment the `pipeline.fill_failures` counter,
+/// both labeled by stage.
+#[derive(Debug)]
+pub struct FillPipeline<S: Signer> {
+    /// The Filler filling the Orders.
+    filler: Arc<Filler<S>>,
+    /// The Watcher finding new Orders.
+    watcher: Watcher,
+    /// Maximum number of Orders filled in one Bundle.
+    batch_size: usize,
+    /// Number of batches evaluated at once.
+    evaluate_concurrency: usize,
+    /// Number of fills planned at once.
+    plan_concurrency: usize,
+    /// Number of fills submitted at once.
+    submit_concurrency: usize,
+}
+
+impl<S> FillPipeline<S>
+where
+    S: Signer,
+{
+    /// Create a new pipeline filling the Orders the Watcher finds, with default limits.
+    pub fn new(filler: Filler<S>, watcher: Watcher) -> Self {
+        Self {
+            filler: Arc::new(filler),
+            watcher,
+            batch_size: DEFAULT_BATCH_SIZE,
+            evaluate_concurrency: DEFAULT_EVALUATE_CONCURRENCY,
+            plan_concurrency: DEFAULT_PLAN_CONCURRENCY,
+            submit_concurrency: DEFAULT_SUBMIT_CONCURRENCY,
+        }
+    }
+
+    /// Set the maximum number of Orders filled in one Bundle. Must be non-zero.
+    pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set the number of batches evaluated at once. Must be non-zero.
+    pub const fn with_evaluate_concurrency(mut self, concurrency: usize) -> Self {
+        self.evaluate_concurrency = concurrency;
+        self
+    }
+
+    /// Set the number of fills planned at once. Must be non-zero.
+    pub const fn with_plan_concurrency(mut self, concurrency: usize) -> Self {
+        self.plan_concurrency = concurrency;
+        self
+    }
+
+    /// Set the number of fills submitted at once. Must be non-zero.
+    pub const fn with_submit_concurrency(mut self, concurrency: usize) -> Self {
+        self.submit_concurrency = concurrency;
+        self
+    }
+}
+
+impl<S> FillPipeline<S>
+where
+    S: Signer + Send + Sync + 'static,
+{
+    /// Spawn the Watcher and the evaluate, plan, and submit stages, running forever.
+    pub fn spawn(self) -> JoinHandle<()> {
+        let watcher = self.watcher.clone();
+        let (queued, watch) = self.watcher.spawn();
+        let plan_concurrency = self.plan_concurrency.max(1);
+        let submit_concurrency = self.submit_concurrency.max(1);
+        let (to_plan, evaluated) = mpsc::channel::<EvaluatedFill>(plan_concurrency);
+        let (to_submit, planned) = mpsc::channel::<PlannedFill>(submit_concurrency);
+
+        let evaluate = {
+            let filler = Arc::clone(&self.filler);
+            let watcher = watcher.clone();
+            spawn_stage(
+                "evaluate",
+                queued,
+                self.evaluate_concurrency,
+                self.batch_size,
+                move |orders: Vec<SignedOrder>| {
+                    let filler = Arc::clone(&filler);
+                    let watcher = watcher.clone();
+                    let to_plan = to_plan.clone();
+                    async move {
+                        info!(orders_count = orders.len(), "Evaluating queued orders");
+                        let evaluated = match filler.evaluate(&orders).await {
+                            Ok(Some(evaluated)) => evaluated,
+                            Ok(None) => return forget(&watcher, &orders),
+                            Err(err) => {
+                                error!(%err, "Failed to evaluate queued orders");
+                                counter!("pipeline.fill_failures", "stage" => "evaluate")
+                                    .increment(1);
+                                return forget(&watcher, &orders);
+                            }
+                        };
+                        // Orders screened out of the fill are queued again while in the
+                        // transaction cache
+                        let kept: HashSet<OrderHash> =
+                            evaluated.orders().iter().map(OrderHash::from).collect();
+                        let screened_out: Vec<SignedOrder> = orders
+                            .into_iter()
+                            .filter(|order| !kept.contains(&OrderHash::from(order)))
+                            .collect();
+                        forget(&watcher, &screened_out);
+                        // waits while every plan slot is busy
+                        if let Err(unsent) = to_plan.send(evaluated).await {
+                            forget(&watcher, unsent.0.orders());
+                        }
+                    }
+                },
+            )
+        };
+
+        let plan = {
+            let filler = Arc::clone(&self.filler);
+            let watcher = watcher.clone();
+            spawn_stage(
+                "plan",
+                evaluated,
+                plan_concurrency,
+                1,
+                move |fills: Vec<EvaluatedFill>| {
+                    let filler = Arc::clone(&filler);
+                    let watcher = watcher.clone();
+                    let to_submit = to_submit.clone();
+                    async move {
+                        for evaluated in fills {
+                            let orders = evaluated.orders().to_vec();
+                            let planned = match filler.plan_fill(evaluated).await {
+                                Ok(planned) => planned,
+                                Err(err) => {
+                                    error!(%err, "Failed to plan queued fill");
+                                    counter!("pipeline.fill_failures", "stage" => "plan")
+                                        .increment(1);
+                                    forget(&watcher, &orders);
+                                    continue;
+                                }
+                            };
+                            // waits while every submit slot is busy
+                            if let Err(unsent) = to_submit.send(planned).await {
+                                forget(&watcher, unsent.0.orders());
+                            }
+                        }
+                    }
+                },
+            )
+        };
+
+        let submit = {
+            let filler = Arc::clone(&self.filler);
+            spawn_stage(
+                "submit",
+                planned,
+                submit_concurrency,
+                1,
+                move |fills: Vec<PlannedFill>| {
+                    let filler = Arc::clone(&filler);
+                    let watcher = watcher.clone();
+                    async move {
+                        for planned in fills {
+                            let orders = planned.orders().to_vec();
+                            if let Err(err) = filler.submit_fill(planned).await {
+                                error!(%err, "Failed to submit queued fill");
+                                counter!("pipeline.fill_failures", "stage" => "submit")
+                                    .increment(1);
+                            }
+                            forget(&watcher, &orders);
+                        }
+                    }
+                },
+            )
+        };
+
+        // each stage stops once the stage before it has stopped and its queue has drained
+        tokio::spawn(async move {
+            let _ = evaluate.await;
+            let _ = plan.await;
+            let _ = submit.await;
+            watch.abort();
+        })
+    }
+}
+
+/// Forget the Orders in the Watcher, so they are queued again while in the transaction cache.
+fn forget(watcher: &Watcher, orders: &[SignedOrder]) {
+    for order in orders {
+        watcher.forget(OrderHash::from(order));
+    }
+}
+
+/// Spawn a pipeline stage, taking queued items in batches of up to `batch_size` and processing
+/// each batch on its own task, with at most `concurrency` batches in progress at once. The stage
+/// stops once its input is closed and drained.
+fn spawn_stage<T, F, Fut>(
+    stage: &'static str,
+    mut input: mpsc::Receiver<T>,
+    concurrency: usize,
+    batch_size: usize,
+    process: F,
+) -> JoinHandle<()>
+where
+    T: Send + 'static,
+    F: Fn(Vec<T>) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let slots = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut batch = Vec::with_capacity(batch_size);
+        // wait for a free slot before taking a batch, so items stay queued while the stage is
+        // slow, and the stage before it waits for them to drain
+        while let Ok(slot) = Arc::clone(&slots).acquire_owned().await {
+            if input.recv_many(&mut batch, batch_size.max(1)).await == 0 {
+                break;
+            }
+            gauge!("pipeline.queue_depth", "stage" => stage).set(input.len() as f64);
+            let work = process(std::mem::take(&mut batch));
+            tokio::spawn(async move {
+                work.await;
+                drop(slot);
+            });
+        }
+    })
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Create a [`Watcher`] polling this Filler's transaction cache on the given interval, to
+    /// build a [`FillPipeline`] on.
+    pub fn watcher(&self, interval: std::time::Duration) -> eyre::Result<Watcher> {
+        Watcher::from_constants(&self.constants, self.ru_provider.clone(), interval)
+    }
+}
@@ -0,0 +1,230 @@
+use alloy::{primitives::Address, rpc::types::TransactionRequest};
+use init4_bin_base::deps::tracing::debug;
+use signet_bundle::SignetEthBundle;
+use signet_constants::SignetConstants;
+use signet_types::{SignedFill, SignedOrder};
+use std::{collections::HashMap, fmt};
+
+/// Extra transactions a strategy places around the planned fill and initiate transactions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanExtensions {
+    /// Rollup transactions placed before the Rollup fill.
+    pub ru_before: Vec<TransactionRequest>,
+    /// Rollup transactions placed after the last initiate.
+    pub ru_after: Vec<TransactionRequest>,
+    /// Host transactions placed before the Host fill.
+    pub host_before: Vec<TransactionRequest>,
+    /// Host transactions placed after the Host fill.
+    pub host_after: Vec<TransactionRequest>,
+}
+
+impl PlanExtensions {
+    /// Whether there are no extra transactions.
+    pub const fn is_empty(&self) -> bool {
+        self.ru_before.is_empty()
+            && self.ru_after.is_empty()
+            && self.host_before.is_empty()
+            && self.host_after.is_empty()
+    }
+
+    /// Add another set of extensions, placed inside this set's transactions.
+    ///
+    /// Extensions added first wrap those added later, so a flash loan opened by the first
+    /// extension is closed last.
+    pub fn nest(&mut self, inner: Self) {
+        self.ru_before.extend(inner.ru_before);
+        self.ru_after.splice(0..0, inner.ru_after);
+        self.host_before.extend(inner.host_before);
+        self.host_after.splice(0..0, inner.host_after);
+    }
+
+    /// Place the extra Rollup transactions around the planned Rollup transactions.
+    pub fn wrap_rollup(&self, planned: Vec<TransactionRequest>) -> Vec<TransactionRequest> {
+        wrap(&self.ru_before, planned, &self.ru_after)
+    }
+
+    /// Place the extra Host transactions around the planned Host transactions.
+    pub fn wrap_host(&self, planned: Vec<TransactionRequest>) -> Vec<TransactionRequest> {
+        wrap(&self.host_before, planned, &self.host_after)
+    }
+}
+
+/// Concatenate `before`, `planned`, and `after`, in order.
+fn wrap(
+    before: &[TransactionRequest],
+    planned: Vec<TransactionRequest>,
+    after: &[TransactionRequest],
+) -> Vec<TransactionRequest> {
+    before
+        .iter()
+        .cloned()
+        .chain(planned)
+        .chain(after.iter().cloned())
+        .collect()
+}
+
+/// A hook for strategies to attach extra transactions to a fill, such as an oracle poke,
+/// unwrapping tokens, or opening and closing a flash loan, without bypassing the planner.
+///
+/// Extensions only add transactions before and after the planned sequence; the fill and
+/// initiate transactions themselves, and their relative ordering, are unchanged.
+pub trait PlanExtension: fmt::Debug + Send + Sync {
+    /// The extra transactions to place around the fill of the given Orders.
+    fn extend(&self, constants: &SignetConstants, orders: &[SignedOrder]) -> PlanExtensions;
+}
+
+/// A hook for operators to set builder-specific parameters on each Bundle before it is sent,
+/// such as timestamp bounds, replacement ids, refund settings, or priority hints, without
+/// forking the Filler.
+pub trait BundleDecorator: fmt::Debug + Send + Sync {
+    /// Set parameters on a Bundle, after its transactions and target block are set.
+    fn decorate(&self, bundle: &mut SignetEthBundle);
+}
+
+/// Common Bundle parameters understood by most builders.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleHints {
+    /// The earliest timestamp at which the Bundle is valid.
+    pub min_timestamp: Option<u64>,
+    /// The latest timestamp at which the Bundle is valid.
+    pub max_timestamp: Option<u64>,
+    /// An id that later Bundles can use to replace or cancel this one.
+    pub replacement_uuid: Option<String>,
+}
+
+impl BundleDecorator for BundleHints {
+    fn decorate(&self, bundle: &mut SignetEthBundle) {
+        if self.min_timestamp.is_some() {
+            bundle.bundle.min_timestamp = self.min_timestamp;
+        }
+        if self.max_timestamp.is_some() {
+            bundle.bundle.max_timestamp = self.max_timestamp;
+        }
+        if self.replacement_uuid.is_some() {
+            bundle.bundle.replacement_uuid = self.replacement_uuid.clone();
+        }
+    }
+}
+
+/// How the deadline of an aggregate Fill is chosen from the Orders it fills.
+///
+/// The Fill's Permit2 signature must not outlive any Order it fills, since the Bundle can only
+/// land while every Order is still valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillDeadlinePolicy {
+    /// The earliest Order deadline, less a safety margin in seconds.
+    Earliest {
+        /// Seconds subtracted from the earliest Order deadline.
+        margin: u64,
+    },
+    /// A fixed number of seconds after chain time, capped at the earliest Order deadline.
+    FromNow(u64),
+}
+
+impl Default for FillDeadlinePolicy {
+    fn default() -> Self {
+        Self::Earliest { margin: 0 }
+    }
+}
+
+impl FillDeadlinePolicy {
+    /// The Fill deadline for Orders whose earliest deadline is `earliest`, at chain time `now`.
+    pub const fn deadline(self, earliest: u64, now: u64) -> u64 {
+        match self {
+            Self::Earliest { margin } => earliest.saturating_sub(margin),
+            Self::FromNow(ttl) => {
+                let deadline = now.saturating_add(ttl);
+                if deadline < earliest {
+                    deadline
+                } else {
+                    earliest
+                }
+            }
+        }
+    }
+}
+
+/// Construct a set of transaction requests to be submitted on the rollup.
+///
+/// Perform a single, aggregate Fill upfront, then Initiate each Order.
+/// Transaction requests look like [`fill_aggregate`, `initiate_1`, `initiate_2`].
+///
+/// This is the simplest, minimally viable way to get a set of Orders mined;
+/// Fillers may wish to implement more complex strategies.
+///
+/// For example, Fillers might utilize one Order's Inputs to fill subsequent Orders' Outputs.
+/// In this case, the rollup transactions should look like [`fill_1`, `inititate_1`, `fill_2`, `initiate_2`].
+pub fn rollup_txn_requests(
+    constants: &SignetConstants,
+    filler: Address,
+    signed_fills: &HashMap<u64, SignedFill>,
+    orders: &[SignedOrder],
+) -> Vec<TransactionRequest> {
+    // construct the transactions to be submitted to the Rollup
+    let mut tx_requests = Vec::new();
+
+    // first, if there is a SignedFill for the Rollup, add a transaction to submit the fill
+    // Note that `fill` transactions MUST be mined *before* the corresponding Order(s) `initiate` transactions in order to count
+    // Host `fill` transactions are always considered to be mined "before" the rollup block is processed,
+    // but Rollup `fill` transactions MUST take care to be ordered before the Orders are `initiate`d
+    if let Some(rollup_fill) = signed_fills.get(&constants.rollup().chain_id()) {
+        debug!(?rollup_fill, "Rollup fill");
+        // add the fill tx to the rollup txns
+        let ru_fill_tx = rollup_fill.to_fill_tx(constants.rollup().orders());
+        tx_requests.push(ru_fill_tx);
+    }
+
+    // next, add a transaction to initiate each SignedOrder
+    for signed_order in orders {
+        // add the initiate tx to the rollup txns
+        let ru_initiate_tx = signed_order.to_initiate_tx(filler, constants.rollup().orders());
+        tx_requests.push(ru_initiate_tx);
+    }
+
+    tx_requests
+}
+
+/// Construct a set of transaction requests to be submitted on the host.
+///
+/// This example only includes one Host transaction,
+/// which performs a single, aggregate Fill on the Host chain.
+///
+/// This is the simplest, minimally viable way to get a set of Orders mined;
+/// Fillers may wish to implement more complex strategies.
+///
+/// For example, Fillers might wish to include swaps on Host AMMs to source liquidity as part of their filling strategy.
+pub fn host_txn_requests(
+    constants: &SignetConstants,
+    signed_fills: &HashMap<u64, SignedFill>,
+) -> Vec<TransactionRequest> {
+    // If there is a SignedFill for the Host, add a transaction to submit the fill
+    if let Some(host_fill) = signed_fills.get(&constants.host().chain_id()) {
+        debug!(?host_fill, "Host fill");
+        // add the fill tx to the host txns
+        let host_fill_tx = host_fill.to_fill_tx(constants.host().orders());
+        vec![host_fill_tx]
+    } else {
+        vec![]
+    }
+}
+
+/// Trim target blocks to those that are expected to be produced strictly before `deadline`.
+///
+/// Block timestamps are projected from the latest block, at one block per `block_time`
+/// seconds. Bundles targeting blocks at or after the earliest Order deadline or the Fill
+/// deadline can never be valid, so they are not sent.
+pub fn trim_target_blocks(
+    targets: impl IntoIterator<Item = u64>,
+    latest_number: u64,
+    latest_timestamp: u64,
+    block_time: u64,
+    deadline: u64,
+) -> Vec<u64> {
+    targets
+        .into_iter()
+        .filter(|&number| {
+            let expected = latest_timestamp + number.saturating_sub(latest_number) * block_time;
+            expected < deadline
+        })
+        .collect()
+}
@@ -0,0 +1,113 @@
+use super::{Filler, required_outputs};
+use crate::{analytics::ChainToken, ids::BundleId};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use eyre::Error;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{debug, info, instrument},
+};
+use signet_types::SignedOrder;
+use std::{
+    collections::HashMap,
+    slice::from_ref,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A pool of Fillers with different funded signers, rotating between them per Bundle.
+///
+/// Filling every Bundle from the same address lets competitors fingerprint and front-run an
+/// operator's flow. The pool takes turns between its Fillers, skipping any whose signer does
+/// not hold enough of each Output token for the fill. Each Filler keeps its own providers, so
+/// nonces are tracked per address.
+#[derive(Debug)]
+pub struct FillerPool<S: Signer> {
+    /// The Fillers in the pool, each with a different signer.
+    fillers: Vec<Filler<S>>,
+    /// Index of the Filler to try first for the next fill.
+    next: AtomicUsize,
+}
+
+impl<S> FillerPool<S>
+where
+    S: Signer,
+{
+    /// Create a new pool rotating between the given Fillers.
+    pub fn new(fillers: Vec<Filler<S>>) -> Result<Self, Error> {
+        if fillers.is_empty() {
+            eyre::bail!("filler pool is empty");
+        }
+        Ok(Self {
+            fillers,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// The addresses of the Fillers in the pool.
+    pub fn addresses(&self) -> Vec<Address> {
+        self.fillers
+            .iter()
+            .map(|filler| filler.signer.address())
+            .collect()
+    }
+
+    /// Query the transaction cache to get all possible orders.
+    pub async fn get_orders(&self) -> Result<Vec<SignedOrder>, Error> {
+        self.fillers[0].get_orders().await
+    }
+
+    /// Fill the Orders in a single Bundle from the next Filler in turn that holds enough
+    /// inventory.
+    ///
+    /// See [`Filler::fill`].
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn fill(&self, orders: &[SignedOrder]) -> Result<Vec<BundleId>, Error> {
+        let filler = self.select(orders).await?;
+        let address = filler.signer.address();
+        info!(%address, "Filling from pool address");
+        counter!("filler.pool_fills", "address" => address.to_string()).increment(1);
+        filler.fill(orders).await
+    }
+
+    /// Fill each Order in its own Bundle, rotating Fillers per Bundle.
+    ///
+    /// See [`Filler::fill_individually`].
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn fill_individually(&self, orders: &[SignedOrder]) -> Result<Vec<BundleId>, Error> {
+        let mut bundle_ids = Vec::new();
+        for order in orders {
+            bundle_ids.extend(self.fill(from_ref(order)).await?);
+        }
+        Ok(bundle_ids)
+    }
+
+    /// Select the next Filler in turn whose signer holds enough of each Output token.
+    async fn select(&self, orders: &[SignedOrder]) -> Result<&Filler<S>, Error> {
+        let required = required_outputs(orders);
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.fillers.len() {
+            let filler = &self.fillers[(start + offset) % self.fillers.len()];
+            if filler.holds(&required).await? {
+                return Ok(filler);
+            }
+            debug!(address = %filler.signer.address(), "Pool address lacks inventory");
+        }
+        eyre::bail!("no address in the filler pool holds enough inventory for the fill")
+    }
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Whether the signer holds at least the given amount of each token, by (chain id, token).
+    async fn holds(&self, required: &HashMap<ChainToken, U256>) -> Result<bool, Error> {
+        let inventory = self.inventory(required.keys().copied()).await?;
+        Ok(required
+            .iter()
+            .all(|(token, amount)| inventory.get(token).is_some_and(|held| held >= amount)))
+    }
+}
@@ -0,0 +1,131 @@
+use super::{DEFAULT_GAS_LIMIT, Filler, earliest_deadline};
+use crate::{ids::OrderHash, provider::TxSenderProvider};
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+    signers::Signer,
+};
+use eyre::Error;
+use init4_bin_base::deps::tracing::{debug, instrument};
+use signet_types::{AggregateOrders, SignedOrder};
+use std::collections::HashMap;
+
+/// A transaction planned as part of a fill, before it is signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlannedTx {
+    /// An aggregate Fill of the Orders' Outputs on a chain.
+    Fill,
+    /// Initiation of an Order on the Rollup.
+    Initiate(OrderHash),
+    /// An extra transaction attached by a [`PlanExtension`](super::plan::PlanExtension).
+    Extension,
+}
+
+/// Estimated gas for the transactions on one chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasEstimate {
+    /// Total gas limit of the transactions.
+    pub gas_limit: u64,
+    /// The current gas price of the chain, in wei.
+    pub gas_price: u128,
+}
+
+impl GasEstimate {
+    /// The maximum cost of the transactions, in wei.
+    pub fn max_cost(&self) -> U256 {
+        U256::from(self.gas_limit) * U256::from(self.gas_price)
+    }
+}
+
+/// A preview of how a set of Orders would be filled, produced without signing or submitting anything.
+///
+/// Inputs and Outputs are reported as raw token amounts; valuing them against each other
+/// (and against gas costs) to determine profit requires token prices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FillPreview {
+    /// The planned Rollup transactions, in order.
+    pub ru_txs: Vec<PlannedTx>,
+    /// The planned Host transactions, in order.
+    pub host_txs: Vec<PlannedTx>,
+    /// Estimated gas for the Rollup transactions.
+    pub ru_gas: GasEstimate,
+    /// Estimated gas for the Host transactions.
+    pub host_gas: GasEstimate,
+    /// Token amounts the Filler receives on the Rollup, by token.
+    pub inputs: HashMap<Address, U256>,
+    /// Token amounts the Filler must provide, by (chain id, token).
+    pub outputs: HashMap<(u64, Address), U256>,
+    /// The Rollup blocks the Bundle would target.
+    pub target_blocks: Vec<u64>,
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Preview how the Orders would be filled by [`Filler::fill`]: the planned transactions,
+    /// estimated gas on each chain, required token amounts, and target blocks.
+    ///
+    /// Nothing is signed or submitted, so this is suitable for UIs and operator confirmation flows.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn preview(&self, orders: &[SignedOrder]) -> Result<FillPreview, Error> {
+        if orders.is_empty() {
+            eyre::bail!("no orders to fill");
+        }
+
+        let agg: AggregateOrders = orders.iter().collect();
+
+        let mut outputs: HashMap<(u64, Address), U256> = HashMap::new();
+        for (&(chain_id, token), recipients) in &agg.outputs {
+            let total = recipients
+                .values()
+                .fold(U256::ZERO, |acc, amount| acc + *amount);
+            *outputs.entry((chain_id, token)).or_default() += total;
+        }
+        let has_outputs_on = |chain_id: u64| {
+            outputs
+                .keys()
+                .any(|&(output_chain, _)| output_chain == chain_id)
+        };
+
+        // mirror the transaction ordering of `rollup_txn_requests` and `host_txn_requests`
+        let extensions = self.plan_extensions(orders);
+        let mut ru_txs = vec![PlannedTx::Extension; extensions.ru_before.len()];
+        if has_outputs_on(self.constants.rollup().chain_id()) {
+            ru_txs.push(PlannedTx::Fill);
+        }
+        ru_txs.extend(
+            orders
+                .iter()
+                .map(|order| PlannedTx::Initiate(OrderHash::from(order))),
+        );
+        ru_txs.extend(vec![PlannedTx::Extension; extensions.ru_after.len()]);
+
+        let mut host_txs = vec![PlannedTx::Extension; extensions.host_before.len()];
+        if has_outputs_on(self.constants.host().chain_id()) {
+            host_txs.push(PlannedTx::Fill);
+        }
+        host_txs.extend(vec![PlannedTx::Extension; extensions.host_after.len()]);
+
+        let preview = FillPreview {
+            ru_gas: estimate_gas(&self.ru_provider, ru_txs.len()).await?,
+            host_gas: estimate_gas(&self.host_provider, host_txs.len()).await?,
+            ru_txs,
+            host_txs,
+            inputs: agg.inputs.clone(),
+            outputs,
+            target_blocks: self.target_blocks(earliest_deadline(orders, [])?).await?,
+        };
+        debug!(?preview, "Fill preview");
+
+        Ok(preview)
+    }
+}
+
+/// Estimate gas for a number of transactions, using the gas limit the Filler sets on each.
+async fn estimate_gas(provider: &TxSenderProvider, tx_count: usize) -> Result<GasEstimate, Error> {
+    Ok(GasEstimate {
+        gas_limit: DEFAULT_GAS_LIMIT * tx_count as u64,
+        gas_price: provider.get_gas_price().await?,
+    })
+}
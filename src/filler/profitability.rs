@@ -0,0 +1,200 @@
+use super::Filler;
+use crate::{
+    analytics::ChainToken,
+    ids::{BundleId, OrderHash},
+    quoting::PriceOracle,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use eyre::Error;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{debug, instrument, warn},
+};
+use signet_types::SignedOrder;
+use std::slice::from_ref;
+
+/// Basis points in one whole.
+const BPS: f64 = 10_000.0;
+
+/// The value of filling a single Order, in raw numéraire units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderProfit {
+    /// The hash of the Order.
+    pub order_hash: OrderHash,
+    /// Value of the Inputs the Filler receives.
+    pub input_value: f64,
+    /// Value of the Outputs the Filler provides.
+    pub output_value: f64,
+    /// Estimated maximum cost of gas on both chains.
+    pub gas_cost: f64,
+}
+
+impl OrderProfit {
+    /// The profit of filling the Order: Inputs received, less Outputs provided and gas.
+    pub const fn profit(&self) -> f64 {
+        self.input_value - self.output_value - self.gas_cost
+    }
+
+    /// The profit as a margin on the cost of the fill, in basis points.
+    pub const fn margin_bps(&self) -> f64 {
+        let cost = self.output_value + self.gas_cost;
+        if cost <= 0.0 {
+            return 0.0;
+        }
+        self.profit() / cost * BPS
+    }
+}
+
+/// Values Orders in a numéraire token using a [`PriceOracle`], so fills can be restricted to
+/// profitable Orders.
+///
+/// Gas is valued as the native token of each chain, which the oracle must price as
+/// `(chain_id, Address::ZERO)`.
+#[derive(Debug, Clone)]
+pub struct Profitability<O> {
+    /// The source of prices.
+    oracle: O,
+    /// The token Orders are valued in.
+    numeraire: ChainToken,
+}
+
+impl<O> Profitability<O>
+where
+    O: PriceOracle,
+{
+    /// Value Orders in `numeraire`, using prices from `oracle`.
+    pub const fn new(oracle: O, numeraire: ChainToken) -> Self {
+        Self { oracle, numeraire }
+    }
+
+    /// The token Orders are valued in.
+    pub const fn numeraire(&self) -> ChainToken {
+        self.numeraire
+    }
+
+    /// The value of an amount of a token, in raw numéraire units.
+    pub async fn value(&self, token: ChainToken, amount: U256) -> Result<f64, Error> {
+        if amount.is_zero() {
+            return Ok(0.0);
+        }
+        let price = if token == self.numeraire {
+            1.0
+        } else {
+            self.oracle.price(token, self.numeraire).await?
+        };
+        Ok(f64::from(amount) * price)
+    }
+}
+
+/// The result of [`Filler::fill_profitable`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfitableFill {
+    /// The ids of the submitted Bundles, one per target block.
+    pub bundle_ids: Vec<BundleId>,
+    /// Orders left out for falling short of the minimum margin, with their valuation.
+    pub unprofitable: Vec<(SignedOrder, OrderProfit)>,
+    /// Orders left out because they could not be valued, e.g. for want of a price.
+    pub unscored: Vec<SignedOrder>,
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Value filling a single Order: its Inputs and Outputs at oracle prices, less the
+    /// estimated gas to fill it alone on both chains.
+    ///
+    /// Gas is estimated as in [`Filler::preview`]. Orders filled together share Fill
+    /// transactions, so this overestimates the gas of each Order in a larger Bundle.
+    pub async fn score<O: PriceOracle>(
+        &self,
+        order: &SignedOrder,
+        profitability: &Profitability<O>,
+    ) -> Result<OrderProfit, Error> {
+        let preview = self.preview(from_ref(order)).await?;
+        let ru_chain_id = self.constants.rollup().chain_id();
+        let host_chain_id = self.constants.host().chain_id();
+
+        let mut input_value = 0.0;
+        for (&token, &amount) in &preview.inputs {
+            input_value += profitability.value((ru_chain_id, token), amount).await?;
+        }
+        let mut output_value = 0.0;
+        for (&token, &amount) in &preview.outputs {
+            output_value += profitability.value(token, amount).await?;
+        }
+        let gas_cost = profitability
+            .value((ru_chain_id, Address::ZERO), preview.ru_gas.max_cost())
+            .await?
+            + profitability
+                .value((host_chain_id, Address::ZERO), preview.host_gas.max_cost())
+                .await?;
+
+        Ok(OrderProfit {
+            order_hash: OrderHash::from(order),
+            input_value,
+            output_value,
+            gas_cost,
+        })
+    }
+
+    /// Fill the Orders whose margin is at least `min_margin_bps` in a single Bundle, leaving
+    /// out the rest.
+    ///
+    /// Each Order is scored with [`Filler::score`]; unprofitable Orders are returned with their
+    /// valuation and increment the `filler.unprofitable` counter. Orders that fail to score are
+    /// left out rather than failing the batch, and increment the `filler.unscored` counter. If
+    /// every Order is left out, nothing is filled.
+    #[instrument(skip_all, fields(orders_count = orders.len(), min_margin_bps))]
+    pub async fn fill_profitable<O: PriceOracle>(
+        &self,
+        orders: &[SignedOrder],
+        profitability: &Profitability<O>,
+        min_margin_bps: u64,
+    ) -> Result<ProfitableFill, Error> {
+        let mut profitable = Vec::with_capacity(orders.len());
+        let mut unprofitable = Vec::new();
+        let mut unscored = Vec::new();
+        for order in orders {
+            let profit = match self.score(order, profitability).await {
+                Ok(profit) => profit,
+                Err(err) => {
+                    warn!(%err, order_hash = %OrderHash::from(order), "Skipping unscored order");
+                    counter!("filler.unscored").increment(1);
+                    unscored.push(order.clone());
+                    continue;
+                }
+            };
+            let margin_bps = profit.margin_bps();
+            if margin_bps < min_margin_bps as f64 {
+                warn!(
+                    order_hash = %profit.order_hash,
+                    margin_bps,
+                    profit = profit.profit(),
+                    "Skipping unprofitable order"
+                );
+                counter!("filler.unprofitable").increment(1);
+                unprofitable.push((order.clone(), profit));
+                continue;
+            }
+            debug!(order_hash = %profit.order_hash, margin_bps, "Order is profitable");
+            profitable.push(order.clone());
+        }
+
+        if profitable.is_empty() {
+            return Ok(ProfitableFill {
+                bundle_ids: vec![],
+                unprofitable,
+                unscored,
+            });
+        }
+        Ok(ProfitableFill {
+            bundle_ids: self.fill(&profitable).await?,
+            unprofitable,
+            unscored,
+        })
+    }
+}
@@ -0,0 +1,242 @@
+use super::Filler;
+use crate::{
+    analytics::TokenPair,
+    ids::{BundleId, OrderHash},
+};
+use alloy::{primitives::Address, signers::Signer};
+use eyre::Error;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{debug, error, info, instrument},
+};
+use signet_types::SignedOrder;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::task::{JoinHandle, JoinSet};
+
+/// Selects the Orders a [`Strategy`] is willing to fill.
+pub trait OrderFilter: fmt::Debug + Send + Sync {
+    /// Whether the strategy is willing to fill the Order.
+    fn accepts(&self, order: &SignedOrder) -> bool;
+}
+
+/// Accepts every Order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnyOrder;
+
+impl OrderFilter for AnyOrder {
+    fn accepts(&self, _order: &SignedOrder) -> bool {
+        true
+    }
+}
+
+/// Accepts Orders in any of a set of token pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PairFilter {
+    /// The accepted token pairs.
+    pub pairs: HashSet<TokenPair>,
+}
+
+impl OrderFilter for PairFilter {
+    fn accepts(&self, order: &SignedOrder) -> bool {
+        TokenPair::of_order(order).is_some_and(|pair| self.pairs.contains(&pair))
+    }
+}
+
+/// A named fill strategy: a filter choosing the Orders it fills, and the Filler filling them.
+#[derive(Debug)]
+pub struct Strategy<S: Signer> {
+    /// The strategy name, used to tag Orders in logs and metrics.
+    name: &'static str,
+    /// Strategies with higher priority are offered each Order first.
+    priority: u32,
+    /// The Orders the strategy is willing to fill.
+    filter: Box<dyn OrderFilter>,
+    /// The Filler filling the strategy's Orders.
+    filler: Filler<S>,
+}
+
+impl<S> Strategy<S>
+where
+    S: Signer,
+{
+    /// Create a new strategy filling the Orders accepted by `filter` with `filler`.
+    pub fn new(
+        name: &'static str,
+        priority: u32,
+        filter: impl OrderFilter + 'static,
+        filler: Filler<S>,
+    ) -> Self {
+        Self {
+            name,
+            priority,
+            filter: Box::new(filter),
+            filler,
+        }
+    }
+
+    /// The strategy name.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Runs several fill strategies in one daemon, assigning each Order to at most one of them so
+/// strategies never fill the same Order twice.
+///
+/// Each Order is offered to strategies in descending priority, ties broken by the order
+/// strategies were added, and assigned to the first whose filter accepts it. Orders no
+/// strategy accepts are left unfilled. Assignments increment the `filler.strategy_orders`
+/// counter, labelled by `strategy`.
+///
+/// Strategies whose Fillers have different signers fill concurrently. Strategies sharing a
+/// signer fill one after another, in priority order, so their transactions never race for
+/// the signer's nonces.
+#[derive(Debug)]
+pub struct StrategyRouter<S: Signer> {
+    /// The strategies, highest priority first.
+    strategies: Vec<Arc<Strategy<S>>>,
+}
+
+impl<S> Default for StrategyRouter<S>
+where
+    S: Signer,
+{
+    fn default() -> Self {
+        Self {
+            strategies: Vec::new(),
+        }
+    }
+}
+
+impl<S> StrategyRouter<S>
+where
+    S: Signer,
+{
+    /// Add a strategy to the router.
+    pub fn with_strategy(mut self, strategy: Strategy<S>) -> Self {
+        // stable, so equal priorities keep the order they were added
+        let index = self
+            .strategies
+            .partition_point(|existing| existing.priority >= strategy.priority);
+        self.strategies.insert(index, Arc::new(strategy));
+        self
+    }
+
+    /// Assign each Order to the highest-priority strategy accepting it, returning the names of
+    /// the strategies assigned any Orders, highest priority first, with their Orders.
+    pub fn route(&self, orders: &[SignedOrder]) -> Vec<(&'static str, Vec<SignedOrder>)> {
+        self.assign(orders)
+            .into_iter()
+            .map(|(strategy, orders)| (strategy.name, orders))
+            .collect()
+    }
+
+    /// Assign each Order to the highest-priority strategy accepting it, returning each
+    /// strategy assigned any Orders with its Orders.
+    fn assign(&self, orders: &[SignedOrder]) -> Vec<(&Arc<Strategy<S>>, Vec<SignedOrder>)> {
+        let mut routed: Vec<Vec<SignedOrder>> = vec![Vec::new(); self.strategies.len()];
+        for order in orders {
+            let order_hash = OrderHash::from(order);
+            match self
+                .strategies
+                .iter()
+                .position(|strategy| strategy.filter.accepts(order))
+            {
+                Some(index) => {
+                    let strategy = self.strategies[index].name;
+                    debug!(%order_hash, strategy, "Routed order");
+                    counter!("filler.strategy_orders", "strategy" => strategy).increment(1);
+                    routed[index].push(order.clone());
+                }
+                None => debug!(%order_hash, "No strategy accepts order"),
+            }
+        }
+        self.strategies
+            .iter()
+            .zip(routed)
+            .filter(|(_, orders)| !orders.is_empty())
+            .collect()
+    }
+}
+
+impl<S> StrategyRouter<S>
+where
+    S: Signer + Send + Sync + 'static,
+{
+    /// Route the Orders, and fill each strategy's Orders in one Bundle per strategy.
+    ///
+    /// Strategies with different signers fill concurrently; strategies sharing a signer fill
+    /// one after another, in priority order, so they never race for the signer's nonces.
+    ///
+    /// Returns the result of each strategy that was assigned any Orders.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn fill(
+        &self,
+        orders: &[SignedOrder],
+    ) -> Vec<(&'static str, Result<Vec<BundleId>, Error>)> {
+        // group the assigned strategies by signer, keeping priority order within each group
+        let mut by_signer: HashMap<Address, Vec<_>> = HashMap::new();
+        for (strategy, routed) in self.assign(orders) {
+            by_signer
+                .entry(strategy.filler.signer.address())
+                .or_default()
+                .push((Arc::clone(strategy), routed));
+        }
+
+        let mut fills = JoinSet::new();
+        for group in by_signer.into_values() {
+            fills.spawn(async move {
+                let mut results = Vec::with_capacity(group.len());
+                for (strategy, routed) in group {
+                    info!(
+                        strategy = strategy.name,
+                        orders_count = routed.len(),
+                        "Filling strategy orders"
+                    );
+                    results.push((strategy.name, strategy.filler.fill(&routed).await));
+                }
+                results
+            });
+        }
+
+        let mut results = Vec::new();
+        while let Some(joined) = fills.join_next().await {
+            match joined {
+                Ok(group) => results.extend(group),
+                Err(err) => error!(%err, "Strategy fill task failed"),
+            }
+        }
+        results
+    }
+
+    /// Spawn a task that fetches Orders from the transaction cache on the given interval,
+    /// forever, and fills them by strategy.
+    pub fn spawn(self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                let Some(first) = self.strategies.first() else {
+                    continue;
+                };
+                let orders = match first.filler.get_orders().await {
+                    Ok(orders) => orders,
+                    Err(err) => {
+                        error!(%err, "Failed to fetch orders");
+                        continue;
+                    }
+                };
+                for (strategy, result) in self.fill(&orders).await {
+                    if let Err(err) = result {
+                        error!(%err, strategy, "Strategy fill failed");
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use crate::ids::OrderHash;
+use alloy::primitives::U256;
+use eyre::Error;
+use init4_bin_base::deps::{metrics::counter, tracing::debug};
+use signet_types::SignedOrder;
+
+/// This replica's share of the Order flow, when the flow is split across replicas by Order
+/// hash without external coordination.
+///
+/// An Order belongs to shard `hash mod count`, so replicas configured with the same count and
+/// distinct ids fill disjoint Orders, and every Order is filled by exactly one replica. Orders
+/// belonging to other shards increment the `filler.other_shard` counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderShard {
+    /// This replica's shard.
+    id: u64,
+    /// The number of shards.
+    count: u64,
+}
+
+impl OrderShard {
+    /// Create shard `id` of `count`. Errors unless `id < count`.
+    pub fn new(id: u64, count: u64) -> Result<Self, Error> {
+        if id >= count {
+            eyre::bail!("shard id {id} must be less than the shard count {count}");
+        }
+        Ok(Self { id, count })
+    }
+
+    /// This replica's shard.
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The number of shards.
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The shard an Order belongs to.
+    pub fn shard_of(&self, order_hash: OrderHash) -> u64 {
+        let hash = U256::from_be_bytes(order_hash.into_inner().0);
+        (hash % U256::from(self.count)).to::<u64>()
+    }
+
+    /// Whether an Order belongs to this shard.
+    pub fn owns(&self, order_hash: OrderHash) -> bool {
+        self.shard_of(order_hash) == self.id
+    }
+
+    /// Remove Orders belonging to other shards.
+    pub fn filter(&self, orders: Vec<SignedOrder>) -> Vec<SignedOrder> {
+        orders
+            .into_iter()
+            .filter(|order| {
+                let order_hash = OrderHash::from(order);
+                if self.owns(order_hash) {
+                    return true;
+                }
+                let shard = self.shard_of(order_hash);
+                debug!(%order_hash, shard, "Skipping order in another shard");
+                counter!("filler.other_shard").increment(1);
+                false
+            })
+            .collect()
+    }
+}
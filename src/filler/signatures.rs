@@ -0,0 +1,57 @@
+use crate::ids::OrderHash;
+use alloy::primitives::{B256, Keccak256};
+use init4_bin_base::deps::{metrics::counter, tracing::debug};
+use signet_types::{SignedFill, SignedOrder};
+use std::{collections::HashMap, sync::Mutex};
+
+/// Signed Fills for each destination chain, by chain id.
+type SignedFills = HashMap<u64, SignedFill>;
+
+/// Signed Fills cached by the set of Orders they fill and their deadline.
+///
+/// Resubmitting a fill for the same Orders reuses the cached signatures, so retries make no
+/// further signing calls to a remote signer such as AWS KMS, and every attempt carries the
+/// same Permit2 nonces. Entries are pruned once their deadline has passed.
+#[derive(Debug, Default)]
+pub(super) struct FillSignatureCache {
+    /// Signed Fills, by Order set hash and Fill deadline.
+    fills: Mutex<HashMap<(B256, u64), SignedFills>>,
+}
+
+impl FillSignatureCache {
+    /// Hash the set of Orders, independent of their order.
+    fn order_set_hash(orders: &[SignedOrder]) -> B256 {
+        let mut hashes: Vec<OrderHash> = orders.iter().map(OrderHash::from).collect();
+        hashes.sort_unstable();
+        let mut hasher = Keccak256::new();
+        for hash in hashes {
+            hasher.update(hash.into_inner());
+        }
+        hasher.finalize()
+    }
+
+    /// The cached Fills for the Orders with the given deadline, if any.
+    pub(super) fn get(&self, orders: &[SignedOrder], deadline: u64) -> Option<SignedFills> {
+        let key = (Self::order_set_hash(orders), deadline);
+        let fills = self.fills.lock().unwrap().get(&key).cloned();
+        if fills.is_some() {
+            debug!(order_set_hash = %key.0, deadline, "Reusing cached fill signatures");
+            counter!("filler.fill_signature_cache_hits").increment(1);
+        }
+        fills
+    }
+
+    /// Cache the Fills for the Orders with the given deadline, pruning entries whose deadline
+    /// has passed at chain time `now`.
+    pub(super) fn insert(
+        &self,
+        orders: &[SignedOrder],
+        deadline: u64,
+        fills: SignedFills,
+        now: u64,
+    ) {
+        let mut cache = self.fills.lock().unwrap();
+        cache.retain(|&(_, deadline), _| deadline > now);
+        cache.insert((Self::order_set_hash(orders), deadline), fills);
+    }
+}
@@ -0,0 +1,249 @@
+use super::Filler;
+use crate::{provider::TxSenderProvider, runtime::offload};
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::TransactionBuilder,
+    primitives::{Address, U256},
+    providers::Provider,
+    rpc::types::{
+        TransactionRequest,
+        simulate::{SimBlock, SimulatePayload},
+    },
+    signers::Signer,
+};
+use eyre::{Error, eyre};
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{debug, info, instrument, warn},
+};
+use signet_types::SignedOrder;
+use std::collections::HashMap;
+
+/// The outcome of a single simulated transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedTx {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// Gas used by the transaction.
+    pub gas_used: u64,
+    /// The error reported for a failed transaction, if any.
+    pub error: Option<String>,
+}
+
+/// The outcome of simulating a fill's transactions against one state view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewSimulation {
+    /// The state view simulated against.
+    pub view: BlockNumberOrTag,
+    /// Outcomes of the Rollup transactions, in order.
+    pub ru_txs: Vec<SimulatedTx>,
+    /// Outcomes of the Host transactions, in order.
+    pub host_txs: Vec<SimulatedTx>,
+}
+
+impl ViewSimulation {
+    /// Whether every transaction succeeded in this view.
+    pub fn succeeded(&self) -> bool {
+        self.ru_txs
+            .iter()
+            .chain(&self.host_txs)
+            .all(|tx| tx.success)
+    }
+}
+
+/// The outcomes of simulating a fill's transactions against several state views.
+///
+/// Fills often pass against `latest` but fail against `pending`, where a competing fill is
+/// already queued; comparing views surfaces this before a Bundle is sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimulationReport {
+    /// The outcome in each view, in the order the views were configured.
+    pub views: Vec<ViewSimulation>,
+}
+
+impl SimulationReport {
+    /// Whether every transaction succeeded in every view.
+    pub fn succeeded(&self) -> bool {
+        self.views.iter().all(ViewSimulation::succeeded)
+    }
+
+    /// Whether the views disagree on whether the fill succeeds.
+    pub fn diverged(&self) -> bool {
+        let mut outcomes = self.views.iter().map(ViewSimulation::succeeded);
+        outcomes
+            .next()
+            .is_some_and(|first| outcomes.any(|outcome| outcome != first))
+    }
+}
+
+/// Parse a comma-separated list of simulation views: block tags (`latest`, `pending`, ...),
+/// or block numbers in decimal or `0x`-prefixed hex.
+pub fn parse_simulation_views(views: &str) -> Result<Vec<BlockNumberOrTag>, Error> {
+    views
+        .split(',')
+        .map(str::trim)
+        .filter(|view| !view.is_empty())
+        .map(|view| match view.parse::<u64>() {
+            Ok(number) => Ok(BlockNumberOrTag::Number(number)),
+            Err(_) => view
+                .parse()
+                .map_err(|e| eyre!("invalid simulation view {view}: {e}")),
+        })
+        .collect()
+}
+
+/// Parse comma-separated `token=amount` simulation skip thresholds, e.g.
+/// `0xC02a...=1000000000000000000`. Amounts are in raw token units.
+pub fn parse_simulation_skip_thresholds(thresholds: &str) -> Result<HashMap<Address, U256>, Error> {
+    thresholds
+        .split(',')
+        .map(str::trim)
+        .filter(|threshold| !threshold.is_empty())
+        .map(|threshold| {
+            let (token, amount) = threshold.split_once('=').ok_or_else(|| {
+                eyre!("invalid simulation skip threshold {threshold}: expected token=amount")
+            })?;
+            let token = token
+                .trim()
+                .parse()
+                .map_err(|e| eyre!("invalid token {token}: {e}"))?;
+            let amount = amount
+                .trim()
+                .parse()
+                .map_err(|e| eyre!("invalid amount for {token}: {e}"))?;
+            Ok((token, amount))
+        })
+        .collect()
+}
+
+/// Simulate an ordered set of transactions in a single block on top of a state view.
+async fn simulate_on(
+    provider: &TxSenderProvider,
+    from: Address,
+    tx_requests: &[TransactionRequest],
+    view: BlockNumberOrTag,
+) -> Result<Vec<SimulatedTx>, Error> {
+    if tx_requests.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimBlock {
+            calls: tx_requests
+                .iter()
+                .cloned()
+                .map(|tx| tx.with_from(from))
+                .collect(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let blocks = provider
+        .simulate(&payload)
+        .block_id(BlockId::from(view))
+        .await?;
+
+    // simulation results can be large; parse them off the reactor
+    offload(move || {
+        blocks
+            .into_iter()
+            .flat_map(|block| block.calls)
+            .map(|call| SimulatedTx {
+                success: call.status,
+                gas_used: call.gas_used,
+                error: call.error.map(|error| error.message),
+            })
+            .collect()
+    })
+    .await
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Simulate the Rollup and Host transactions of a fill against each configured state view.
+    #[instrument(skip_all, fields(views = ?self.simulation_views))]
+    async fn simulate_views(
+        &self,
+        ru_tx_requests: &[TransactionRequest],
+        host_tx_requests: &[TransactionRequest],
+    ) -> Result<SimulationReport, Error> {
+        let from = self.signer.address();
+        let mut report = SimulationReport::default();
+        for &view in &self.simulation_views {
+            let simulation = ViewSimulation {
+                view,
+                ru_txs: simulate_on(&self.ru_provider, from, ru_tx_requests, view).await?,
+                host_txs: simulate_on(&self.host_provider, from, host_tx_requests, view).await?,
+            };
+            debug!(?simulation, "Simulated fill");
+            report.views.push(simulation);
+        }
+        Ok(report)
+    }
+
+    /// Simulate the fill of a set of Orders against each configured state view, without
+    /// sending anything.
+    ///
+    /// This signs Fills for the Orders, so the Permit2 signatures used match those a real fill
+    /// would submit.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn simulate(&self, orders: &[SignedOrder]) -> Result<SimulationReport, Error> {
+        let signed_fills = self.sign_fills(orders).await?;
+        let ru_tx_requests = self.rollup_txn_requests(&signed_fills, orders).await?;
+        let host_tx_requests = self.host_txn_requests(&signed_fills, orders).await?;
+        self.simulate_views(&ru_tx_requests, &host_tx_requests)
+            .await
+    }
+
+    /// Whether simulation of a fill of the Orders can be skipped, because every Output is in a
+    /// token with a skip threshold and at most that threshold.
+    ///
+    /// Small fills rely on Builder simulation instead: a failing Bundle is discarded, and the
+    /// cost of failure is low. Outputs in tokens without a threshold are always simulated.
+    pub(super) fn skips_simulation(&self, orders: &[SignedOrder]) -> bool {
+        if self.simulation_skip_below.is_empty() {
+            return false;
+        }
+        let small = orders
+            .iter()
+            .flat_map(|order| &order.outputs)
+            .all(|output| {
+                self.simulation_skip_below
+                    .get(&output.token)
+                    .is_some_and(|threshold| output.amount <= *threshold)
+            });
+        if small {
+            debug!("Fill is below the simulation thresholds; skipping simulation");
+            counter!("filler.simulation_skipped").increment(1);
+        }
+        small
+    }
+
+    /// Simulate the fill's transactions against each configured state view, refusing to fill
+    /// if any view fails.
+    pub(super) async fn check_simulation(
+        &self,
+        ru_tx_requests: &[TransactionRequest],
+        host_tx_requests: &[TransactionRequest],
+    ) -> Result<(), Error> {
+        let report = self
+            .simulate_views(ru_tx_requests, host_tx_requests)
+            .await?;
+        if report.diverged() {
+            warn!(?report, "Fill simulation differs between state views");
+        }
+        if !report.succeeded() {
+            let failed: Vec<String> = report
+                .views
+                .iter()
+                .filter(|view| !view.succeeded())
+                .map(|view| view.view.to_string())
+                .collect();
+            eyre::bail!("fill simulation failed against {}", failed.join(", "));
+        }
+        info!(views = report.views.len(), "Fill simulation succeeded");
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use super::Filler;
+use crate::{
+    ids::{BundleId, OrderHash},
+    quoting::PriceOracle,
+};
+use alloy::signers::Signer;
+use eyre::Error;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{debug, instrument, warn},
+};
+use signet_types::SignedOrder;
+
+/// An Order to fill, with the maximum effective price the Filler accepts for it.
+///
+/// The effective price is the Order's exchange rate (Output amount per Input amount) relative
+/// to the oracle mid price of the Input in terms of the Output. An effective price below 1
+/// means the Filler receives Inputs worth more than the Outputs it provides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundedOrder {
+    /// The Order to fill.
+    pub order: SignedOrder,
+    /// The maximum acceptable effective price, e.g. `0.998` to require a 0.2% edge.
+    pub max_price: f64,
+}
+
+/// The result of [`Filler::fill_within_bounds`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoundedFill {
+    /// The ids of the submitted Bundles, one per target block.
+    pub bundle_ids: Vec<BundleId>,
+    /// Orders whose bound was violated when re-validated, to be re-evaluated later.
+    pub requeued: Vec<SignedOrder>,
+    /// Orders whose effective price could not be computed, e.g. Orders with several Inputs or
+    /// Outputs, or tokens the oracle does not price.
+    pub rejected: Vec<SignedOrder>,
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// The effective price of a single-Input, single-Output Order at the oracle's current mid
+    /// price.
+    async fn effective_price<O: PriceOracle>(
+        &self,
+        order: &SignedOrder,
+        oracle: &O,
+    ) -> Result<f64, Error> {
+        let ([input], [output]) = (&order.permit.permit.permitted[..], &order.outputs[..]) else {
+            eyre::bail!("price bounds only apply to orders with a single input and output");
+        };
+        let base = (self.constants.rollup().chain_id(), input.token);
+        let quote = (u64::from(output.chainId), output.token);
+        let mid = oracle.price(base, quote).await?;
+        let rate = f64::from(output.amount) / f64::from(input.amount);
+        Ok(rate / mid)
+    }
+
+    /// Fill Orders in a single Bundle, re-validating each Order's price bound against the
+    /// oracle immediately before signing.
+    ///
+    /// Prices may have moved since the Orders were evaluated. Orders whose bound is now
+    /// violated are left out of the fill and returned for re-evaluation. Orders whose effective
+    /// price can't be computed are left out and returned as rejected, incrementing the
+    /// `filler.price_bound_errors` counter. If every Order is left out, nothing is filled.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn fill_within_bounds<O: PriceOracle>(
+        &self,
+        orders: &[BoundedOrder],
+        oracle: &O,
+    ) -> Result<BoundedFill, Error> {
+        let mut within = Vec::with_capacity(orders.len());
+        let mut requeued = Vec::new();
+        let mut rejected = Vec::new();
+        for BoundedOrder { order, max_price } in orders {
+            let price = match self.effective_price(order, oracle).await {
+                Ok(price) => price,
+                Err(err) => {
+                    warn!(
+                        %err,
+                        order_hash = %OrderHash::from(order),
+                        "Failed to price order; rejecting it"
+                    );
+                    counter!("filler.price_bound_errors").increment(1);
+                    rejected.push(order.clone());
+                    continue;
+                }
+            };
+            if price > *max_price {
+                warn!(
+                    order_hash = %OrderHash::from(order),
+                    price,
+                    max_price,
+                    "Price bound violated; requeueing order"
+                );
+                counter!("filler.price_bound_violations").increment(1);
+                requeued.push(order.clone());
+                continue;
+            }
+            debug!(order_hash = %OrderHash::from(order), price, max_price, "Price bound holds");
+            within.push(order.clone());
+        }
+
+        if within.is_empty() {
+            return Ok(BoundedFill {
+                bundle_ids: vec![],
+                requeued,
+                rejected,
+            });
+        }
+        Ok(BoundedFill {
+            bundle_ids: self.fill(&within).await?,
+            requeued,
+            rejected,
+        })
+    }
+}
@@ -0,0 +1,282 @@
+use super::{
+    CompiledPlan, Filler, earliest_deadline,
+    latency::{FillStage, StageTimer},
+};
+use crate::{clock::chain_time, ids::BundleId, reservation::Reservation};
+use alloy::{primitives::Bytes, signers::Signer};
+use eyre::Error;
+use init4_bin_base::deps::tracing::{debug, info, instrument, warn};
+use signet_types::{SignedFill, SignedOrder};
+use std::collections::HashMap;
+
+/// Orders screened, reserved, and checked for a fill, ready to plan. See
+/// [`Filler::evaluate`].
+#[derive(Debug)]
+pub struct EvaluatedFill {
+    /// The Orders reserved for the fill.
+    reservation: Reservation,
+    /// Chain time when the Orders were evaluated.
+    now: u64,
+    /// Times the stages of the fill.
+    timer: StageTimer,
+}
+
+impl EvaluatedFill {
+    /// The Orders to fill.
+    pub fn orders(&self) -> &[SignedOrder] {
+        &self.reservation.orders
+    }
+}
+
+/// A fill whose Fills and Bundle transactions are signed, ready to submit. See
+/// [`Filler::plan_fill`].
+#[derive(Debug)]
+pub struct PlannedFill {
+    /// The Orders reserved for the fill.
+    reservation: Reservation,
+    /// The signed Fills and Bundle transactions.
+    compiled: CompiledPlan,
+    /// Chain time when the Orders were evaluated.
+    now: u64,
+    /// Times the stages of the fill.
+    timer: StageTimer,
+}
+
+impl PlannedFill {
+    /// The Orders to fill.
+    pub fn orders(&self) -> &[SignedOrder] {
+        &self.reservation.orders
+    }
+
+    /// The earliest deadline of the Orders and Fills; Bundles must land before it.
+    pub const fn deadline(&self) -> u64 {
+        self.compiled.deadline
+    }
+}
+
+impl<S> Filler<S>
+where
+    S: Signer,
+{
+    /// Evaluate Orders for a fill, the first stage of [`Filler::fill`].
+    ///
+    /// Refuses to fill during a maintenance window or unless this replica is the leader,
+    /// screens the Orders, defers those that don't fit in inventory, and reserves the rest,
+    /// recording the screening in the compliance log. Then refuses to fill if the Orders
+    /// contracts have changed or gas is spiking, releasing the reservation.
+    ///
+    /// Returns `None` if every Order was screened out.
+    #[instrument(skip_all, fields(orders_count = orders.len()))]
+    pub async fn evaluate(&self, orders: &[SignedOrder]) -> Result<Option<EvaluatedFill>, Error> {
+        let timer = StageTimer::start();
+
+        // initiate no new fills during maintenance, or unless this replica is the leader
+        let now = chain_time(&self.ru_provider).await?;
+        self.check_maintenance(now)?;
+        self.check_leader().await?;
+
+        // skip orders this replica won't fill
+        let (mut screening, orders) = self.screen(orders, now).await?;
+
+        // defer orders that don't fit in inventory
+        let orders = self.fit_inventory(orders).await?;
+        screening.stage(&orders, "deferred for inventory");
+
+        // reserve orders last, so orders this replica won't fill stay free for others
+        let reservation = self.reserve(orders).await?;
+        screening.stage(&reservation.orders, "reserved by another replica");
+        let recorded = self.record_screening(&screening, now);
+        self.release_on_error(&reservation, recorded).await?;
+
+        if reservation.orders.is_empty() {
+            info!("No orders left to fill after screening");
+            return Ok(None);
+        }
+
+        let checked = self.check_chains().await;
+        self.release_on_error(&reservation, checked).await?;
+
+        let mut evaluated = EvaluatedFill {
+            reservation,
+            now,
+            timer,
+        };
+        evaluated.timer.finish(FillStage::Evaluate);
+        Ok(Some(evaluated))
+    }
+
+    /// Plan an evaluated fill, the second stage of [`Filler::fill`]: sign the Fills, plan the
+    /// Bundle transactions, simulate them against any configured state views, and sign them.
+    ///
+    /// If planning fails, the reservation is released.
+    #[instrument(skip_all, fields(orders_count = evaluated.orders().len()))]
+    pub async fn plan_fill(&self, evaluated: EvaluatedFill) -> Result<PlannedFill, Error> {
+        let EvaluatedFill {
+            reservation,
+            now,
+            mut timer,
+        } = evaluated;
+        timer.resume();
+
+        let compiled = self.compile_fill(&reservation.orders, &mut timer).await;
+        let compiled = self.release_on_error(&reservation, compiled).await?;
+        Ok(PlannedFill {
+            reservation,
+            compiled,
+            now,
+            timer,
+        })
+    }
+
+    /// Submit a planned fill, the last stage of [`Filler::fill`]: send its Bundle to the
+    /// transaction cache once per target block, and record the fill.
+    ///
+    /// If no Bundle could be sent, the reservation is released. Returns the ids of the
+    /// submitted Bundles, one per target block sent to.
+    #[instrument(skip_all, fields(orders_count = planned.orders().len()))]
+    pub async fn submit_fill(&self, planned: PlannedFill) -> Result<Vec<BundleId>, Error> {
+        let PlannedFill {
+            reservation,
+            compiled,
+            now,
+            mut timer,
+        } = planned;
+        timer.resume();
+
+        let submitted = self
+            .submit_compiled(&reservation.orders, &compiled, now)
+            .await;
+        let bundle_ids = self.release_on_error(&reservation, submitted).await?;
+        timer.finish(FillStage::Submit);
+        timer.report(self.latency_budget);
+        Ok(bundle_ids)
+    }
+
+    /// Release the reservation if `result` is an error, so other replicas can take the Orders
+    /// without waiting for it to expire.
+    pub(super) async fn release_on_error<T>(
+        &self,
+        reservation: &Reservation,
+        result: Result<T, Error>,
+    ) -> Result<T, Error> {
+        if result.is_err() {
+            self.release(reservation).await;
+        }
+        result
+    }
+
+    /// Refuse to fill if the Orders contracts have changed since they were acknowledged, or
+    /// during a gas spike.
+    pub(super) async fn check_chains(&self) -> Result<(), Error> {
+        self.check_orders_code().await?;
+        self.check_base_fees().await
+    }
+
+    /// Sign an aggregate Fill of the Orders, and plan, simulate, and sign the Bundle
+    /// transactions filling them.
+    async fn compile_fill(
+        &self,
+        orders: &[SignedOrder],
+        timer: &mut StageTimer,
+    ) -> Result<CompiledPlan, Error> {
+        // sign a SignedFill for the orders
+        let signed_fills: HashMap<u64, SignedFill> = self.sign_fills(orders).await?;
+        debug!(?signed_fills, "Signed fills for orders");
+        info!("Successfully signed fills");
+        timer.finish(FillStage::SignFill);
+
+        // get the transaction requests for the rollup
+        let tx_requests = self.rollup_txn_requests(&signed_fills, orders).await?;
+        debug!(?tx_requests, "Rollup transaction requests");
+
+        // get the transaction requests for the host
+        let host_tx_requests = self.host_txn_requests(&signed_fills, orders).await?;
+        debug!(?host_tx_requests, "Host transaction requests");
+
+        // refuse to fill if the transactions fail against any configured state view
+        if !self.simulation_views.is_empty() && !self.skips_simulation(orders) {
+            self.check_simulation(&tx_requests, &host_tx_requests)
+                .await?;
+        }
+        timer.finish(FillStage::Plan);
+
+        // sign & encode the rollup transactions for the Bundle
+        let ru_txs: Vec<Bytes> = self
+            .sign_and_encode_fill_txns(&self.ru_provider, tx_requests)
+            .await?;
+        debug!(?ru_txs, "Rollup encoded transactions");
+
+        // sign & encode the host transactions for the Bundle
+        let host_txs = self
+            .sign_and_encode_fill_txns(&self.host_provider, host_tx_requests)
+            .await?;
+        debug!(?host_txs, "Host encoded transactions");
+        timer.finish(FillStage::SignTxs);
+
+        // Bundles must land before the earliest Order deadline and the Fill deadline
+        let deadline = earliest_deadline(orders, signed_fills.values())?;
+        Ok(CompiledPlan {
+            ru_txs,
+            host_txs,
+            fills: signed_fills.into_values().collect(),
+            deadline,
+        })
+    }
+
+    /// Send a compiled fill of the Orders to the transaction cache, once per target block,
+    /// and record it in the compliance log, the persistent counters, and the submission
+    /// archive.
+    ///
+    /// Fails only if no Bundle was sent, so callers may release the reservation on error. If
+    /// sending fails after some Bundles were sent, those Bundles are live in the transaction
+    /// cache, so the remaining target blocks are skipped and the sent Bundles are returned,
+    /// keeping the reservation until it expires.
+    pub(super) async fn submit_compiled(
+        &self,
+        orders: &[SignedOrder],
+        compiled: &CompiledPlan,
+        now: u64,
+    ) -> Result<Vec<BundleId>, Error> {
+        // target only blocks before the deadline
+        let target_blocks = self.target_blocks(compiled.deadline).await?;
+        if target_blocks.is_empty() {
+            eyre::bail!("no target blocks before the deadline {}", compiled.deadline);
+        }
+
+        // send the Bundle to the transaction cache, once per target block
+        let mut bundle_ids = Vec::new();
+        for &target_ru_block_number in &target_blocks {
+            let sent = self
+                .send_bundle(
+                    compiled.ru_txs.clone(),
+                    compiled.host_txs.clone(),
+                    target_ru_block_number,
+                )
+                .await;
+            match sent {
+                Ok(bundle_id) => bundle_ids.push(bundle_id),
+                Err(err) if bundle_ids.is_empty() => return Err(err),
+                Err(err) => {
+                    warn!(
+                        %err,
+                        target_ru_block_number,
+                        bundles_count = bundle_ids.len(),
+                        "Failed to send Bundle after others were sent; skipping remaining target blocks"
+                    );
+                    break;
+                }
+            }
+        }
+        self.record_fill(orders, &bundle_ids, now);
+        self.count_fill(orders, &bundle_ids);
+        self.archive_submissions(
+            orders,
+            &compiled.fills,
+            &compiled.ru_txs,
+            &compiled.host_txs,
+            target_blocks.into_iter().zip(bundle_ids.iter().copied()),
+            now,
+        );
+        Ok(bundle_ids)
+    }
+}
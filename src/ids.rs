@@ -0,0 +1,159 @@
+use alloy::primitives::{B256, Bytes, Keccak256};
+use serde::{Deserialize, Serialize};
+use signet_bundle::SignetEthBundle;
+use signet_types::SignedOrder;
+use std::{fmt, str::FromStr};
+use uuid::Uuid;
+
+/// The hash of a signed Order, used to identify it across the Filler, Order sender, and logs.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct OrderHash(B256);
+
+impl OrderHash {
+    /// Create a new OrderHash.
+    pub const fn new(hash: B256) -> Self {
+        Self(hash)
+    }
+
+    /// Get the underlying hash.
+    pub const fn into_inner(self) -> B256 {
+        self.0
+    }
+}
+
+impl From<B256> for OrderHash {
+    fn from(hash: B256) -> Self {
+        Self(hash)
+    }
+}
+
+impl From<&B256> for OrderHash {
+    fn from(hash: &B256) -> Self {
+        Self(*hash)
+    }
+}
+
+impl From<&SignedOrder> for OrderHash {
+    fn from(order: &SignedOrder) -> Self {
+        Self::from(order.order_hash())
+    }
+}
+
+impl fmt::Display for OrderHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for OrderHash {
+    type Err = <B256 as FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+/// The id assigned to a Bundle by the transaction cache.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct BundleId(Uuid);
+
+impl BundleId {
+    /// Create a new BundleId.
+    pub const fn new(id: Uuid) -> Self {
+        Self(id)
+    }
+
+    /// Get the underlying id.
+    pub const fn into_inner(self) -> Uuid {
+        self.0
+    }
+}
+
+impl From<Uuid> for BundleId {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl fmt::Display for BundleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for BundleId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+/// A deterministic local id for a Bundle, derived from its transactions and target block.
+///
+/// Unlike the [`BundleId`] assigned by the transaction cache, the same Bundle always has the
+/// same local id, so submissions can be correlated across resubmissions, caches, and restarts.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct LocalBundleId(B256);
+
+impl LocalBundleId {
+    /// Create a new LocalBundleId.
+    pub const fn new(id: B256) -> Self {
+        Self(id)
+    }
+
+    /// Get the underlying hash.
+    pub const fn into_inner(self) -> B256 {
+        self.0
+    }
+
+    /// Derive the id of a Bundle of Rollup and Host transactions targeting a Rollup block.
+    ///
+    /// The id is the keccak256 hash of the target block number, followed by each list of
+    /// transactions, each prefixed by its length.
+    pub fn compute(ru_txs: &[Bytes], host_txs: &[Bytes], target_ru_block_number: u64) -> Self {
+        let mut hasher = Keccak256::new();
+        hasher.update(target_ru_block_number.to_be_bytes());
+        for txs in [ru_txs, host_txs] {
+            hasher.update((txs.len() as u64).to_be_bytes());
+            for tx in txs {
+                hasher.update((tx.len() as u64).to_be_bytes());
+                hasher.update(tx);
+            }
+        }
+        Self(hasher.finalize())
+    }
+}
+
+impl From<&SignetEthBundle> for LocalBundleId {
+    fn from(bundle: &SignetEthBundle) -> Self {
+        Self::compute(
+            &bundle.bundle.txs,
+            &bundle.host_txs,
+            bundle.bundle.block_number,
+        )
+    }
+}
+
+impl fmt::Display for LocalBundleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for LocalBundleId {
+    type Err = <B256 as FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
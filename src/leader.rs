@@ -0,0 +1,235 @@
+use crate::etcd::EtcdClient;
+use eyre::Error;
+use init4_bin_base::{
+    deps::{
+        metrics::{counter, gauge},
+        tracing::{debug, info, warn},
+    },
+    utils::from_env::FromEnv,
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::Mutex, task::JoinHandle};
+
+/// Default etcd key held by the leader.
+const DEFAULT_KEY: &str = "signet-orders/filler-leader";
+
+/// Default time the leader goes silent before another replica takes over.
+const DEFAULT_LEASE_TTL: Duration = Duration::from_secs(10);
+
+/// Configuration for [`LeaderElection`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct LeaderElectionConfig {
+    /// Base URL of the etcd cluster's HTTP gateway. Leader election is disabled if unset.
+    #[from_env(
+        var = "LEADER_ETCD_URL",
+        desc = "Base URL of the etcd HTTP gateway used for leader election",
+        optional
+    )]
+    pub etcd_url: Option<String>,
+    /// The etcd key held by the leader. Replicas filling the same Orders must share it.
+    #[from_env(var = "LEADER_KEY", desc = "The etcd key held by the leader", optional)]
+    pub key: Option<String>,
+    /// Name of this replica, stored as the key's value for operators.
+    #[from_env(
+        var = "LEADER_NAME",
+        desc = "Name of this replica in leader election",
+        optional
+    )]
+    pub name: Option<String>,
+    /// Seconds the leader may go silent before another replica takes over.
+    #[from_env(
+        var = "LEADER_LEASE_SECS",
+        desc = "Seconds the leader may go silent before another replica takes over",
+        optional
+    )]
+    pub lease_secs: Option<u64>,
+}
+
+impl LeaderElectionConfig {
+    /// Connect a [`LeaderElection`] using this configuration, if an etcd URL is configured.
+    pub fn connect(&self) -> eyre::Result<Option<LeaderElection>> {
+        let Some(url) = &self.etcd_url else {
+            return Ok(None);
+        };
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("filler-{}", std::process::id()));
+        let mut election = LeaderElection::new(url.parse()?, name)?;
+        if let Some(key) = &self.key {
+            election = election.with_key(key);
+        }
+        if let Some(lease_secs) = self.lease_secs {
+            election = election.with_lease_ttl(Duration::from_secs(lease_secs));
+        }
+        Ok(Some(election))
+    }
+}
+
+/// The lease backing this replica's leadership.
+#[derive(Debug, Clone, Copy)]
+struct Lease {
+    /// The etcd lease id.
+    id: i64,
+    /// When the lease was last confirmed live.
+    renewed_at: Instant,
+}
+
+/// Leader election across Filler replicas, so replicas in different regions don't fill the
+/// same Orders twice.
+///
+/// Replicas campaign for a shared key in etcd, through its HTTP gateway. The leader holds the
+/// key under a lease, which it renews while running; if it goes silent, the lease expires after
+/// the lease TTL, etcd deletes the key, and the next replica to campaign becomes leader.
+///
+/// Leadership is only trusted for one lease TTL after the lease was last renewed, so a leader
+/// that loses contact with etcd stops filling no later than etcd lets another replica take over.
+/// The `leader.is_leader` gauge is 1 while this replica is leader, and changes of leadership
+/// increment the `leader.transitions` counter.
+#[derive(Debug, Clone)]
+pub struct LeaderElection {
+    /// Client for the etcd HTTP gateway.
+    etcd: EtcdClient,
+    /// The key held by the leader.
+    key: String,
+    /// Name of this replica.
+    name: String,
+    /// Time the leader may go silent before another replica takes over.
+    lease_ttl: Duration,
+    /// This replica's lease, while it is leader.
+    lease: Arc<Mutex<Option<Lease>>>,
+}
+
+impl LeaderElection {
+    /// Create a new election through the etcd HTTP gateway at `url`, campaigning as `name`.
+    pub fn new(url: reqwest::Url, name: impl Into<String>) -> eyre::Result<Self> {
+        Ok(Self {
+            etcd: EtcdClient::new(url)?,
+            key: DEFAULT_KEY.to_string(),
+            name: name.into(),
+            lease_ttl: DEFAULT_LEASE_TTL,
+            lease: Arc::default(),
+        })
+    }
+
+    /// Set the key held by the leader.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Set the time the leader may go silent before another replica takes over. Must be at
+    /// least one second.
+    pub const fn with_lease_ttl(mut self, lease_ttl: Duration) -> Self {
+        self.lease_ttl = lease_ttl;
+        self
+    }
+
+    /// Name of this replica.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether this replica is the leader, as of its last renewal.
+    pub async fn is_leader(&self) -> bool {
+        self.lease
+            .lock()
+            .await
+            .is_some_and(|lease| lease.renewed_at.elapsed() < self.lease_ttl)
+    }
+
+    /// Try to take the key under a new lease, returning the lease id if this replica is now
+    /// leader.
+    async fn acquire(&self) -> Result<Option<i64>, Error> {
+        let lease = self.etcd.grant(self.lease_ttl).await?;
+        if self
+            .etcd
+            .put_if_absent(&self.key, &self.name, lease)
+            .await?
+        {
+            return Ok(Some(lease));
+        }
+        self.etcd.revoke(lease).await?;
+        Ok(None)
+    }
+
+    /// Renew this replica's lease if it is leader, or try to become leader if not. Returns
+    /// whether this replica is leader.
+    pub async fn campaign(&self) -> Result<bool, Error> {
+        let mut lease = self.lease.lock().await;
+        let was_leader = lease.is_some();
+
+        if let Some(current) = *lease {
+            let started = Instant::now();
+            if self.etcd.keep_alive(current.id).await? {
+                *lease = Some(Lease {
+                    id: current.id,
+                    renewed_at: started,
+                });
+            } else {
+                warn!(name = %self.name, "Leader lease expired");
+                *lease = None;
+            }
+        }
+        if lease.is_none() {
+            let started = Instant::now();
+            *lease = self.acquire().await?.map(|id| Lease {
+                id,
+                renewed_at: started,
+            });
+        }
+
+        let is_leader = lease.is_some();
+        if is_leader != was_leader {
+            info!(name = %self.name, is_leader, "Leadership changed");
+            counter!("leader.transitions").increment(1);
+        }
+        gauge!("leader.is_leader").set(if is_leader { 1.0 } else { 0.0 });
+        Ok(is_leader)
+    }
+
+    /// Whether this replica is leader, campaigning first if its lease is due for renewal. If
+    /// etcd can't be reached, the last renewal stands until it is one lease TTL old.
+    pub async fn check(&self) -> bool {
+        let due = self
+            .lease
+            .lock()
+            .await
+            .is_none_or(|lease| lease.renewed_at.elapsed() >= self.lease_ttl / 3);
+        if due && let Err(err) = self.campaign().await {
+            warn!(%err, name = %self.name, "Failed to campaign for leadership");
+        }
+        self.is_leader().await
+    }
+
+    /// Step down, letting another replica take over immediately.
+    pub async fn resign(&self) -> Result<(), Error> {
+        let mut lease = self.lease.lock().await;
+        if let Some(current) = lease.take() {
+            self.etcd.revoke(current.id).await?;
+            info!(name = %self.name, "Resigned leadership");
+            counter!("leader.transitions").increment(1);
+            gauge!("leader.is_leader").set(0.0);
+        }
+        Ok(())
+    }
+
+    /// Campaign forever, renewing the lease three times per lease TTL, so a running leader keeps
+    /// leadership between fills.
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let election = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(election.lease_ttl / 3);
+            loop {
+                interval.tick().await;
+                match election.campaign().await {
+                    Ok(is_leader) => debug!(is_leader, "Campaigned for leadership"),
+                    Err(err) => warn!(%err, "Failed to campaign for leadership"),
+                }
+            }
+        })
+    }
+}
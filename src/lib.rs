@@ -12,16 +12,131 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+/// Named addresses, resolved from operator-facing aliases.
+pub mod address_book;
+
+/// Depth and spread analytics over sets of Orders.
+pub mod analytics;
+
+/// Detection and quarantine of anomalous Order flow.
+pub mod anomaly;
+
+/// Approval workflow for composed Orders.
+pub mod approval;
+
+/// Encrypted archive of the exact payloads in each submitted Bundle.
+pub mod archive;
+
+/// Signer balance monitoring.
+pub mod balances;
+
+/// Bridging of inventory from the Host to the Rollup.
+pub mod bridge;
+
+/// Fault injection for testing under adverse conditions.
+#[cfg(feature = "chaos")]
+pub mod chaos;
+
+/// Chain time, used as the reference clock for deadlines.
+pub mod clock;
+
+/// Hash-chained compliance logging of Orders considered and fills executed.
+pub mod compliance;
+
+/// Conditional Orders, submitted once a locally evaluated trigger holds.
+pub mod conditional;
+
+/// Signet constants for known networks and custom deployments.
+pub mod constants;
+
+/// Contract bindings used by the examples.
+pub mod contracts;
+
+/// Cumulative counters persisted across restarts.
+pub mod counters;
+
+/// Minimal client for the etcd v3 HTTP gateway.
+mod etcd;
+
+/// C-compatible API for signing and sending Orders from other languages.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// Typed identifiers for Orders and Bundles.
+pub mod ids;
+
+/// Notifications of submitted Orders filling, expiring, or getting stuck.
+pub mod fill_monitor;
+
 /// Example to Fill Orders.
 pub mod filler;
 
+/// Leader election across Filler replicas, backed by etcd.
+pub mod leader;
+
+/// Time-boxed maintenance windows during which no new fills are initiated.
+pub mod maintenance;
+
+/// Pushing of metrics to a Prometheus Pushgateway, for short-lived CLI invocations.
+pub mod metrics_push;
+
+/// Permit2 nonce usage of outstanding Orders.
+pub mod nonces;
+
 /// Example to send Orders.
 pub mod order;
 
+/// Output formats for CLI tools.
+pub mod output;
+
+/// Runtime pausing of fills in token pairs during incidents.
+pub mod pause;
+
+/// Human-readable rendering of Orders for CLI output and logs.
+pub mod pretty;
+
 /// Provider capable of filling and sending transactions.
 pub mod provider;
 
-// silence clippy
-use chrono as _;
-use clap as _;
-use tokio as _;
+/// Python bindings for fetching and filling Orders, so strategies can be prototyped in Python.
+#[cfg(feature = "python")]
+pub mod python;
+
+/// Auto-quoting of resting Orders around an oracle price.
+pub mod quoting;
+
+/// Signing through an external signing service.
+pub mod remote_signer;
+
+/// Shared reservations of Orders across Filler replicas, backed by etcd.
+pub mod reservation;
+
+/// Automatic renewal of Orders that expire unfilled.
+pub mod renewal;
+
+/// Per-Order decisions from an external risk policy service.
+pub mod risk;
+
+/// Tokio runtime configuration, and offloading of CPU-heavy work.
+pub mod runtime;
+
+/// Periodic sweeps of profit above a float to a treasury.
+pub mod sweep;
+
+/// Timeouts for signing operations.
+pub mod timeout_signer;
+
+/// Classification of transaction cache failures.
+pub mod tx_cache;
+
+/// TWAP slicing of large Orders.
+pub mod twap;
+
+/// Validation of untrusted Orders.
+pub use orders_core::validate;
+
+/// Periodic valuation of the signer's inventory.
+pub mod valuation;
+
+/// Polling of the transaction cache for new Orders, streamed over a channel.
+pub mod watcher;
@@ -0,0 +1,117 @@
+use eyre::{Error, WrapErr};
+use init4_bin_base::{
+    deps::{metrics::gauge, tracing::info},
+    utils::from_env::FromEnv,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Configuration for [`MaintenanceMode`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct MaintenanceConfig {
+    /// File holding the current maintenance window, if any, checked before every fill.
+    #[from_env(
+        var = "MAINTENANCE_FILE",
+        desc = "File holding the current maintenance window, checked before every fill",
+        optional
+    )]
+    pub maintenance_file: Option<String>,
+}
+
+impl MaintenanceConfig {
+    /// Build [`MaintenanceMode`] using this configuration, if a maintenance file is set.
+    pub fn build(&self) -> Option<MaintenanceMode> {
+        self.maintenance_file.as_ref().map(MaintenanceMode::new)
+    }
+}
+
+/// A time-boxed window during which no new fills are initiated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// Why fills are stopped, e.g. a deploy or chain upgrade.
+    pub reason: String,
+    /// When the window started, in seconds since the unix epoch.
+    pub started_at: u64,
+    /// When the window ends and fills resume, in seconds since the unix epoch.
+    pub until: u64,
+}
+
+impl MaintenanceWindow {
+    /// Whether the window is in effect at time `now`.
+    pub const fn active(&self, now: u64) -> bool {
+        self.started_at <= now && now < self.until
+    }
+
+    /// Load a maintenance window from a JSON file, or `None` if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>, Error> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read maintenance file {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .wrap_err_with(|| format!("invalid maintenance file {}", path.display()))
+    }
+
+    /// Write the maintenance window to a JSON file, via a temporary file and rename.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)
+            .and_then(|()| fs::rename(&tmp, path))
+            .wrap_err_with(|| format!("failed to write maintenance file {}", path.display()))
+    }
+
+    /// End any maintenance window in the file, by deleting it.
+    pub fn clear(path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err)
+                .wrap_err_with(|| format!("failed to delete maintenance file {}", path.display())),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Maintenance mode for safe deploys and chain upgrade windows.
+///
+/// While a maintenance window is in effect, the Filler initiates no new fills, but anything
+/// already submitted is still tracked, e.g. by a [`FillMonitor`], and read-only tools keep
+/// working. Windows are time-boxed, so fills resume on their own if an operator forgets to end
+/// one; see the `maintenance` binary. The window is read from a file before every fill, so it
+/// takes effect without a restart. The `filler.maintenance` gauge is 1 while a window is in
+/// effect, and 0 otherwise.
+///
+/// [`FillMonitor`]: crate::fill_monitor::FillMonitor
+#[derive(Debug, Clone)]
+pub struct MaintenanceMode {
+    /// The maintenance file.
+    path: PathBuf,
+}
+
+impl MaintenanceMode {
+    /// Create maintenance mode controlled by the given file. A missing file means no maintenance.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The maintenance file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The maintenance window in effect at time `now`, if any.
+    pub fn current(&self, now: u64) -> Result<Option<MaintenanceWindow>, Error> {
+        let window = MaintenanceWindow::load(&self.path)?.filter(|window| window.active(now));
+        gauge!("filler.maintenance").set(if window.is_some() { 1.0 } else { 0.0 });
+        if let Some(window) = &window {
+            info!(reason = %window.reason, until = window.until, "In maintenance window");
+        }
+        Ok(window)
+    }
+}
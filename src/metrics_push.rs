@@ -0,0 +1,132 @@
+use eyre::{Error, WrapErr};
+use init4_bin_base::{
+    deps::tracing::{debug, warn},
+    utils::from_env::FromEnv,
+};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use reqwest::{Client, Url, header::CONTENT_TYPE};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Default interval between pushes.
+const DEFAULT_PUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Content type of the Prometheus text exposition format.
+const TEXT_FORMAT: &str = "text/plain; version=0.0.4";
+
+/// Configuration for [`MetricsPusher`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct MetricsPushConfig {
+    /// URL of the Prometheus Pushgateway group to push to, e.g.
+    /// `http://pushgateway:9091/metrics/job/roundtrip`. Metrics are not pushed if unset.
+    #[from_env(
+        var = "METRICS_PUSH_URL",
+        desc = "Prometheus Pushgateway group URL to push metrics to",
+        optional
+    )]
+    pub url: Option<String>,
+    /// Milliseconds between pushes. Defaults to 10 seconds.
+    #[from_env(
+        var = "METRICS_PUSH_INTERVAL_MS",
+        desc = "Milliseconds between pushes to the Prometheus Pushgateway",
+        optional
+    )]
+    pub interval_ms: Option<u64>,
+}
+
+impl MetricsPushConfig {
+    /// Whether a Pushgateway is configured.
+    pub const fn is_enabled(&self) -> bool {
+        self.url.is_some()
+    }
+
+    /// Install a Prometheus recorder and start pushing its metrics to the configured
+    /// Pushgateway, if a URL is set.
+    ///
+    /// This installs the global metrics recorder, so it must not be combined with a scrape
+    /// endpoint, such as the one installed by `init4`. Must be called within a tokio runtime.
+    pub fn install(&self) -> Result<Option<MetricsPusher>, Error> {
+        let Some(url) = &self.url else {
+            return Ok(None);
+        };
+        let interval = self
+            .interval_ms
+            .map_or(DEFAULT_PUSH_INTERVAL, Duration::from_millis);
+        if interval.is_zero() {
+            eyre::bail!("METRICS_PUSH_INTERVAL_MS must be greater than zero");
+        }
+        let push = Push {
+            client: Client::new(),
+            url: url.parse().wrap_err("invalid metrics push URL")?,
+            handle: PrometheusBuilder::new()
+                .install_recorder()
+                .wrap_err("failed to install metrics recorder")?,
+        };
+        Ok(Some(MetricsPusher::spawn(push, interval)))
+    }
+}
+
+/// The rendered metrics of a recorder, and where to push them.
+#[derive(Debug, Clone)]
+struct Push {
+    /// Client for the Pushgateway.
+    client: Client,
+    /// The Pushgateway group URL.
+    url: Url,
+    /// Renders the recorded metrics.
+    handle: PrometheusHandle,
+}
+
+impl Push {
+    /// Replace the group's metrics in the Pushgateway with the current metrics.
+    async fn push(&self) -> Result<(), Error> {
+        self.client
+            .put(self.url.clone())
+            .header(CONTENT_TYPE, TEXT_FORMAT)
+            .body(self.handle.render())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Pushes metrics to a Prometheus Pushgateway, for short-lived CLI invocations such as
+/// roundtrip and load tests, whose metrics would be lost before a scrape endpoint is scraped.
+///
+/// Metrics are pushed on an interval while the process runs; failed pushes are logged and
+/// retried at the next interval. Call [`MetricsPusher::finish`] before exiting, so the final
+/// metrics are pushed.
+#[derive(Debug)]
+pub struct MetricsPusher {
+    /// Pushes the metrics.
+    push: Push,
+    /// The task pushing on an interval.
+    task: JoinHandle<()>,
+}
+
+impl MetricsPusher {
+    /// Start pushing on the interval.
+    fn spawn(push: Push, interval: Duration) -> Self {
+        let task = tokio::spawn({
+            let push = push.clone();
+            async move {
+                let mut interval = tokio::time::interval(interval);
+                loop {
+                    interval.tick().await;
+                    match push.push().await {
+                        Ok(()) => debug!(url = %push.url, "Pushed metrics"),
+                        Err(err) => warn!(%err, url = %push.url, "Failed to push metrics"),
+                    }
+                }
+            }
+        });
+        Self { push, task }
+    }
+
+    /// Stop pushing on the interval, and push the final metrics.
+    pub async fn finish(self) -> Result<(), Error> {
+        self.task.abort();
+        self.push.push().await
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    contracts::{permit2_nonce_bitmap, permit2_nonce_position},
+    provider::TxSenderProvider,
+};
+use alloy::primitives::{Address, U256};
+use eyre::Error;
+use init4_bin_base::deps::tracing::warn;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Number of nonces in one Permit2 nonce bitmap word.
+const NONCES_PER_WORD: u32 = 256;
+
+/// One word of an owner's Permit2 unordered nonce bitmap.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NonceWord {
+    /// The word position; the word holds nonces `word_pos * 256` to `word_pos * 256 + 255`.
+    pub word_pos: U256,
+    /// The bitmap. Each set bit is a used nonce.
+    pub bitmap: U256,
+    /// Nonces of outstanding Orders in this word that are not yet used.
+    pub outstanding: Vec<U256>,
+}
+
+impl NonceWord {
+    /// Number of used nonces in the word.
+    pub const fn used(&self) -> u32 {
+        self.bitmap.count_ones() as u32
+    }
+
+    /// Whether every nonce in the word is used, so no new Orders can be signed in it.
+    pub const fn is_exhausted(&self) -> bool {
+        self.used() == NONCES_PER_WORD
+    }
+}
+
+/// Permit2 nonce usage for an owner on one chain, for the words holding a set of outstanding
+/// Order nonces.
+///
+/// Permit2 invalidates unordered nonces one bitmap word per transaction, so cancelling
+/// outstanding Orders costs one transaction per word they are spread across. Nonces spread
+/// across many words, e.g. by gaps between batches of Orders, make cancellation expensive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NonceStatus {
+    /// The chain the bitmap was read from.
+    pub chain: &'static str,
+    /// The owner of the nonces.
+    pub owner: Address,
+    /// Words holding outstanding nonces, by ascending word position.
+    pub words: Vec<NonceWord>,
+}
+
+impl NonceStatus {
+    /// Fetch the bitmap words holding the given outstanding nonces for `owner`.
+    pub async fn fetch(
+        chain: &'static str,
+        provider: &TxSenderProvider,
+        owner: Address,
+        nonces: impl IntoIterator<Item = U256>,
+    ) -> Result<Self, Error> {
+        let mut by_word: BTreeMap<U256, Vec<U256>> = BTreeMap::new();
+        for nonce in nonces {
+            let (word_pos, _) = permit2_nonce_position(nonce);
+            by_word.entry(word_pos).or_default().push(nonce);
+        }
+
+        let mut words = Vec::with_capacity(by_word.len());
+        for (word_pos, nonces) in by_word {
+            let bitmap = permit2_nonce_bitmap(provider, owner, word_pos).await?;
+            let outstanding = nonces
+                .into_iter()
+                .filter(|&nonce| !bitmap.bit(permit2_nonce_position(nonce).1))
+                .collect();
+            words.push(NonceWord {
+                word_pos,
+                bitmap,
+                outstanding,
+            });
+        }
+
+        Ok(Self {
+            chain,
+            owner,
+            words,
+        })
+    }
+
+    /// Number of transactions needed to cancel every outstanding Order.
+    pub fn cancellation_txs(&self) -> usize {
+        self.words
+            .iter()
+            .filter(|word| !word.outstanding.is_empty())
+            .count()
+    }
+
+    /// Warn if cancelling outstanding Orders would take more than `max_cancellation_txs`
+    /// transactions, or if any word is exhausted.
+    pub fn warn_on_gaps(&self, max_cancellation_txs: usize) {
+        let cancellation_txs = self.cancellation_txs();
+        if cancellation_txs > max_cancellation_txs {
+            warn!(
+                chain = self.chain,
+                owner = %self.owner,
+                cancellation_txs,
+                "Outstanding order nonces are spread across many words; cancelling them is expensive"
+            );
+        }
+        for word in self.words.iter().filter(|word| word.is_exhausted()) {
+            warn!(
+                chain = self.chain,
+                owner = %self.owner,
+                word_pos = %word.word_pos,
+                "Permit2 nonce word is exhausted"
+            );
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::{ids::OrderHash, tx_cache::TxCacheError};
 use alloy::signers::Signer;
 use eyre::Result;
 use init4_bin_base::deps::tracing::{debug, instrument};
+use orders_core::intent::prepare_order;
 use signet_constants::SignetConstants;
 use signet_tx_cache::client::TxCache;
 use signet_types::{SignedOrder, UnsignedOrder};
@@ -38,6 +40,11 @@ where
         })
     }
 
+    /// The system constants Orders are signed for.
+    pub const fn constants(&self) -> &SignetConstants {
+        &self.constants
+    }
+
     /// Sign an Order and forward it to the transaction cache to be Filled.
     #[instrument(skip_all)]
     pub async fn sign_and_send_order(&self, order: Order) -> Result<()> {
@@ -48,25 +55,30 @@ where
     /// Sign an Order.
     #[instrument(skip_all, level = "debug")]
     pub async fn sign_order(&self, order: Order) -> Result<SignedOrder> {
-        // make an UnsignedOrder from the Order
-        let unsigned = UnsignedOrder::from(&order);
+        // make an UnsignedOrder from the Order, and sign it
+        self.sign_unsigned_order(UnsignedOrder::from(&order)).await
+    }
 
-        // sign it
-        unsigned
-            .with_chain(self.constants.system())
+    /// Sign an UnsignedOrder.
+    #[instrument(skip_all, level = "debug")]
+    pub async fn sign_unsigned_order(&self, unsigned: UnsignedOrder<'_>) -> Result<SignedOrder> {
+        prepare_order(unsigned, &self.constants)
             .sign(&self.signer)
             .await
             .map_err(Into::into)
             .inspect(|signed_order| {
-                debug!(order_hash = %signed_order.order_hash(), "Order signed");
+                debug!(order_hash = %OrderHash::from(signed_order), "Order signed");
             })
     }
 
     /// Forward a SignedOrder to the transaction cache.
-    #[instrument(skip_all, fields(order_hash = %signed.order_hash()))]
+    #[instrument(skip_all, fields(order_hash = %OrderHash::from(&signed)))]
     pub async fn send_order(&self, signed: SignedOrder) -> Result<()> {
         // send the SignedOrder to the transaction cache
         debug!("Forwarding signed order to transaction cache");
-        self.tx_cache.forward_order(signed).await
+        self.tx_cache
+            .forward_order(signed)
+            .await
+            .map_err(|err| TxCacheError::classify("forward_order", err).into())
     }
 }
@@ -0,0 +1,40 @@
+use clap::ValueEnum;
+use eyre::Error;
+use serde::Serialize;
+
+/// How CLI tools print their results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One human-readable line per result.
+    #[default]
+    Table,
+    /// A JSON array of results, for scripting.
+    Json,
+    /// Only the identifier of each result, one per line.
+    Quiet,
+}
+
+/// A result printed by a CLI tool.
+pub trait Render: Serialize {
+    /// The primary identifier of the result, e.g. an Order hash or Bundle id.
+    fn id(&self) -> String;
+
+    /// A single human-readable line describing the result.
+    fn table_row(&self) -> String;
+}
+
+impl OutputFormat {
+    /// Print the results to stdout in this format.
+    pub fn print<T: Render>(self, results: &[T]) -> Result<(), Error> {
+        match self {
+            Self::Table => results
+                .iter()
+                .for_each(|result| println!("{}", result.table_row())),
+            Self::Json => println!("{}", serde_json::to_string_pretty(results)?),
+            Self::Quiet => results
+                .iter()
+                .for_each(|result| println!("{}", result.id())),
+        }
+        Ok(())
+    }
+}
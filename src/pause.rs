@@ -0,0 +1,244 @@
+use crate::ids::OrderHash;
+use alloy::primitives::Address;
+use eyre::{Error, WrapErr, eyre};
+use init4_bin_base::{
+    deps::{
+        metrics::counter,
+        tracing::{debug, info},
+    },
+    utils::from_env::FromEnv,
+};
+use serde::{Deserialize, Serialize};
+use signet_types::SignedOrder;
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+/// Configuration for [`PairPauses`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct PairPauseConfig {
+    /// Comma-separated tokens whose Orders are never filled.
+    #[from_env(
+        var = "PAUSED_TOKENS",
+        desc = "Comma-separated tokens whose Orders are not filled",
+        optional
+    )]
+    pub paused_tokens: Option<String>,
+    /// File listing paused tokens and pairs, re-read whenever it changes, so fills can be paused
+    /// without a restart.
+    #[from_env(
+        var = "PAUSE_FILE",
+        desc = "File of paused tokens and pairs, re-read when it changes",
+        optional
+    )]
+    pub pause_file: Option<String>,
+}
+
+impl PairPauseConfig {
+    /// Build [`PairPauses`] using this configuration, if any tokens or a pause file are set.
+    pub fn build(&self) -> Result<Option<PairPauses>, Error> {
+        if self.paused_tokens.is_none() && self.pause_file.is_none() {
+            return Ok(None);
+        }
+        let tokens = self
+            .paused_tokens
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token
+                    .parse()
+                    .map_err(|e| eyre!("invalid paused token {token}: {e}"))
+            })
+            .collect::<Result<_, Error>>()?;
+        let pauses = PairPauses::new(PauseList {
+            tokens,
+            pairs: BTreeSet::new(),
+        });
+        Ok(Some(match &self.pause_file {
+            Some(path) => pauses.with_file(path),
+            None => pauses,
+        }))
+    }
+}
+
+/// A directed token pair whose fills are paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PausedPair {
+    /// The Input token, on the Rollup.
+    pub input: Address,
+    /// The chain the Output token is delivered on.
+    pub output_chain_id: u64,
+    /// The Output token.
+    pub output: Address,
+}
+
+/// Tokens and pairs whose fills are paused.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PauseList {
+    /// Tokens paused in every pair, as an Input or an Output on any chain.
+    #[serde(default)]
+    pub tokens: BTreeSet<Address>,
+    /// Individual paused pairs.
+    #[serde(default)]
+    pub pairs: BTreeSet<PausedPair>,
+}
+
+impl PauseList {
+    /// Load a pause list from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read pause file {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("invalid pause file {}", path.display()))
+    }
+
+    /// Load a pause list from a JSON file, or an empty list if the file does not exist.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Write the pause list to a JSON file, via a temporary file and rename.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)
+            .and_then(|()| fs::rename(&tmp, path))
+            .wrap_err_with(|| format!("failed to write pause file {}", path.display()))
+    }
+
+    /// Combine two pause lists, pausing everything paused by either.
+    pub fn merge(mut self, other: &Self) -> Self {
+        self.tokens.extend(&other.tokens);
+        self.pairs.extend(&other.pairs);
+        self
+    }
+
+    /// Why the Order is paused, if any of its tokens or pairs are.
+    pub fn paused(&self, order: &SignedOrder) -> Option<String> {
+        let inputs = &order.permit.permit.permitted;
+        if let Some(token) = inputs
+            .iter()
+            .map(|input| input.token)
+            .chain(order.outputs.iter().map(|output| output.token))
+            .find(|token| self.tokens.contains(token))
+        {
+            return Some(format!("token {token} paused"));
+        }
+        inputs
+            .iter()
+            .flat_map(|input| {
+                order.outputs.iter().map(|output| PausedPair {
+                    input: input.token,
+                    output_chain_id: u64::from(output.chainId),
+                    output: output.token,
+                })
+            })
+            .find(|pair| self.pairs.contains(pair))
+            .map(|pair| {
+                format!(
+                    "pair {} -> {}:{} paused",
+                    pair.input, pair.output_chain_id, pair.output
+                )
+            })
+    }
+}
+
+/// The pause file's contents, as of its last modification.
+#[derive(Debug, Default)]
+struct LoadedFile {
+    /// When the file was last modified, if it exists.
+    modified: Option<SystemTime>,
+    /// The file's pause list.
+    list: PauseList,
+}
+
+/// Token pairs paused during incidents, e.g. every pair involving a depegged stablecoin, so
+/// their Orders are not filled while unrelated pairs are.
+///
+/// Pauses come from a fixed list, typically from configuration, and optionally from a pause
+/// file that is re-read whenever it is modified, so operators can pause and resume pairs
+/// without restarting; see the `pause` binary. If the pause file can't be read, filling fails
+/// rather than ignoring pauses. Skipped Orders increment the `filler.paused` counter.
+#[derive(Debug)]
+pub struct PairPauses {
+    /// Pauses that always apply.
+    fixed: PauseList,
+    /// The pause file, if any.
+    path: Option<PathBuf>,
+    /// The pause file's contents, as of its last modification.
+    loaded: Mutex<LoadedFile>,
+}
+
+impl PairPauses {
+    /// Create pauses from a fixed list.
+    pub fn new(fixed: PauseList) -> Self {
+        Self {
+            fixed,
+            path: None,
+            loaded: Mutex::default(),
+        }
+    }
+
+    /// Also apply the pauses listed in a file, re-read whenever it changes. A missing file
+    /// pauses nothing.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// The pauses currently in effect.
+    pub fn current(&self) -> Result<PauseList, Error> {
+        let Some(path) = &self.path else {
+            return Ok(self.fixed.clone());
+        };
+        let modified = match fs::metadata(path) {
+            Ok(metadata) => Some(metadata.modified()?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(err)
+                    .wrap_err_with(|| format!("failed to read pause file {}", path.display()));
+            }
+        };
+
+        let mut loaded = self.loaded.lock().unwrap();
+        if loaded.modified != modified {
+            let list = PauseList::load_or_default(path)?;
+            info!(
+                tokens = list.tokens.len(),
+                pairs = list.pairs.len(),
+                "Reloaded pause file"
+            );
+            *loaded = LoadedFile { modified, list };
+        }
+        Ok(self.fixed.clone().merge(&loaded.list))
+    }
+
+    /// Remove Orders in paused tokens or pairs.
+    pub fn filter(&self, orders: Vec<SignedOrder>) -> Result<Vec<SignedOrder>, Error> {
+        let pauses = self.current()?;
+        Ok(orders
+            .into_iter()
+            .filter(|order| match pauses.paused(order) {
+                Some(reason) => {
+                    debug!(order_hash = %OrderHash::from(order), reason, "Skipping paused order");
+                    counter!("filler.paused").increment(1);
+                    false
+                }
+                None => true,
+            })
+            .collect())
+    }
+}
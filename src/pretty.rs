@@ -0,0 +1,135 @@
+use crate::{analytics::ChainToken, ids::OrderHash};
+use alloy::primitives::{Address, U256, utils::format_units};
+use chrono::{Local, TimeZone};
+use signet_constants::SignetConstants;
+use signet_types::SignedOrder;
+use std::collections::HashMap;
+
+/// The symbol and decimals of a token, used to print amounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TokenInfo {
+    /// The token symbol, e.g. `WETH`.
+    symbol: String,
+    /// The number of decimals in one whole token.
+    decimals: u8,
+}
+
+/// Renders Orders human-readably, for CLI output and logs.
+///
+/// Amounts of known tokens are printed in decimal units with their symbol; other tokens are
+/// printed as raw amounts with their address. Chains are printed by name, and deadlines in
+/// local time.
+#[derive(Debug, Clone)]
+pub struct OrderFormatter {
+    /// The Rollup chain id.
+    ru_chain_id: u64,
+    /// The Host chain id.
+    host_chain_id: u64,
+    /// Known tokens, by chain and address.
+    tokens: HashMap<ChainToken, TokenInfo>,
+}
+
+impl OrderFormatter {
+    /// Create a formatter for the given deployment, knowing WETH on both chains.
+    pub fn new(constants: &SignetConstants) -> Self {
+        let ru_chain_id = constants.rollup().chain_id();
+        let host_chain_id = constants.host().chain_id();
+        Self {
+            ru_chain_id,
+            host_chain_id,
+            tokens: HashMap::new(),
+        }
+        .with_token(ru_chain_id, constants.rollup().tokens().weth(), "WETH", 18)
+        .with_token(host_chain_id, constants.host().tokens().weth(), "WETH", 18)
+    }
+
+    /// Print amounts of a token on a chain with the given symbol and decimals.
+    pub fn with_token(
+        mut self,
+        chain_id: u64,
+        token: Address,
+        symbol: impl Into<String>,
+        decimals: u8,
+    ) -> Self {
+        self.tokens.insert(
+            (chain_id, token),
+            TokenInfo {
+                symbol: symbol.into(),
+                decimals,
+            },
+        );
+        self
+    }
+
+    /// The name of a chain, or its id if it is neither the Rollup nor the Host.
+    fn chain_name(&self, chain_id: u64) -> String {
+        if chain_id == self.ru_chain_id {
+            "rollup".to_string()
+        } else if chain_id == self.host_chain_id {
+            "host".to_string()
+        } else {
+            format!("chain {chain_id}")
+        }
+    }
+
+    /// Print an amount of a token, in decimal units if the token is known.
+    fn amount(&self, chain_id: u64, token: Address, amount: U256) -> String {
+        match self.tokens.get(&(chain_id, token)) {
+            Some(info) => {
+                let units =
+                    format_units(amount, info.decimals).unwrap_or_else(|_| amount.to_string());
+                let units = if units.contains('.') {
+                    units.trim_end_matches('0').trim_end_matches('.')
+                } else {
+                    &units
+                };
+                format!("{units} {}", info.symbol)
+            }
+            None => format!("{amount} of {token}"),
+        }
+    }
+
+    /// Render an Order on a single line, e.g.
+    /// `order 0x… owner 0x… deadline 2025-01-01 12:00:00 +00:00: 1 WETH on rollup for
+    /// 0.99 WETH on host to 0x…`.
+    pub fn render(&self, order: &SignedOrder) -> String {
+        let permit = &order.permit;
+        let deadline = u64::try_from(permit.permit.deadline)
+            .ok()
+            .and_then(|deadline| i64::try_from(deadline).ok())
+            .and_then(|deadline| Local.timestamp_opt(deadline, 0).single())
+            .map_or_else(
+                || permit.permit.deadline.to_string(),
+                |deadline| deadline.format("%Y-%m-%d %H:%M:%S %:z").to_string(),
+            );
+        let inputs: Vec<String> = permit
+            .permit
+            .permitted
+            .iter()
+            .map(|input| {
+                let amount = self.amount(self.ru_chain_id, input.token, input.amount);
+                format!("{amount} on rollup")
+            })
+            .collect();
+        let outputs: Vec<String> = order
+            .outputs
+            .iter()
+            .map(|output| {
+                let chain_id = u64::from(output.chainId);
+                format!(
+                    "{} on {} to {}",
+                    self.amount(chain_id, output.token, output.amount),
+                    self.chain_name(chain_id),
+                    output.recipient
+                )
+            })
+            .collect();
+        format!(
+            "order {} owner {} deadline {deadline}: {} for {}",
+            OrderHash::from(order),
+            permit.owner,
+            inputs.join(", "),
+            outputs.join(", ")
+        )
+    }
+}
@@ -1,14 +1,22 @@
 use alloy::{
-    network::{Ethereum, EthereumWallet},
+    eips::BlockNumberOrTag,
+    network::{Ethereum, EthereumWallet, TxSigner},
+    primitives::{Address, B256, Signature, keccak256},
     providers::{
-        Identity, ProviderBuilder, RootProvider,
+        Identity, Provider, ProviderBuilder, RootProvider,
         fillers::{
             BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
             WalletFiller,
         },
     },
+    rpc::client::ClientBuilder,
 };
-use init4_bin_base::utils::signer::LocalOrAws;
+
+mod metrics;
+pub use metrics::{RpcMetricsLayer, RpcMetricsService};
+
+mod rate_limit;
+pub use rate_limit::{RpcRateLimitLayer, RpcRateLimitService};
 
 /// Type alias for the provider used to sign transactions on the rollup.
 pub type TxSenderProvider = FillProvider<
@@ -24,13 +32,60 @@ pub type TxSenderProvider = FillProvider<
 >;
 
 /// Connect a provider capable of filling and sending transactions to a given chain.
+///
+/// RPC calls made through the provider are limited by the given [`RpcRateLimitLayer`], then
+/// instrumented by the given [`RpcMetricsLayer`], so recorded latency excludes time spent queued.
 pub async fn connect_provider(
-    signer: LocalOrAws,
+    signer: impl TxSigner<Signature> + Send + Sync + 'static,
     rpc_url: String,
+    rate_limit: RpcRateLimitLayer,
+    metrics: RpcMetricsLayer,
 ) -> eyre::Result<TxSenderProvider> {
-    ProviderBuilder::new()
-        .wallet(EthereumWallet::from(signer))
+    let client = ClientBuilder::default()
+        .layer(rate_limit)
+        .layer(metrics)
         .connect(&rpc_url)
-        .await
-        .map_err(Into::into)
+        .await?;
+
+    Ok(ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .connect_client(client))
+}
+
+/// Verify that a provider is connected to the expected chain,
+/// and that the Orders contract is deployed at the expected address on that chain.
+///
+/// Returns the code hash of the Orders contract.
+pub async fn verify_chain(
+    provider: &TxSenderProvider,
+    expected_chain_id: u64,
+    orders: Address,
+) -> eyre::Result<B256> {
+    let chain_id = provider.get_chain_id().await?;
+    if chain_id != expected_chain_id {
+        eyre::bail!(
+            "provider reports chain id {chain_id}, but constants expect chain id {expected_chain_id}"
+        );
+    }
+
+    let code = provider.get_code_at(orders).await?;
+    if code.is_empty() {
+        eyre::bail!("no Orders contract code found at {orders} on chain {chain_id}");
+    }
+
+    Ok(keccak256(code))
+}
+
+/// Fetch the code hash of the Orders contract.
+pub async fn orders_code_hash(provider: &TxSenderProvider, orders: Address) -> eyre::Result<B256> {
+    Ok(keccak256(provider.get_code_at(orders).await?))
+}
+
+/// Fetch the base fee of the latest block, in wei.
+pub async fn latest_base_fee(provider: &TxSenderProvider) -> eyre::Result<u64> {
+    provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .and_then(|block| block.header.base_fee_per_gas)
+        .ok_or_else(|| eyre::eyre!("latest block has no base fee"))
 }
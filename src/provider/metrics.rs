@@ -0,0 +1,118 @@
+use alloy::{
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    transports::{Transport, TransportError, TransportFut},
+};
+use init4_bin_base::deps::{
+    metrics::{counter, histogram},
+    tracing::warn,
+};
+use std::{
+    borrow::Cow,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+
+/// A transport layer recording metrics for every RPC call made through a provider.
+///
+/// Each call increments the `rpc.requests` counter and records its latency in the
+/// `rpc.duration_ms` histogram. Calls that fail, or whose response contains an error,
+/// increment the `rpc.errors` counter. All metrics are labelled by `chain` and `method`;
+/// batch requests are labelled with the method `batch`.
+///
+/// Calls slower than the slow-call threshold, if set, are logged as warnings.
+#[derive(Debug, Clone, Copy)]
+pub struct RpcMetricsLayer {
+    /// Name of the chain, used as a metric label.
+    chain: &'static str,
+    /// Calls slower than this are logged.
+    slow_call_threshold: Option<Duration>,
+}
+
+impl RpcMetricsLayer {
+    /// Create a new RpcMetricsLayer for the named chain.
+    pub const fn new(chain: &'static str) -> Self {
+        Self {
+            chain,
+            slow_call_threshold: None,
+        }
+    }
+
+    /// Log calls slower than the given threshold.
+    pub const fn with_slow_call_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_call_threshold = threshold;
+        self
+    }
+}
+
+impl<S> Layer<S> for RpcMetricsLayer {
+    type Service = RpcMetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcMetricsService {
+            inner,
+            layer: *self,
+        }
+    }
+}
+
+/// The transport service produced by [`RpcMetricsLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcMetricsService<S> {
+    /// The wrapped transport.
+    inner: S,
+    /// The layer configuration.
+    layer: RpcMetricsLayer,
+}
+
+impl<S> Service<RequestPacket> for RpcMetricsService<S>
+where
+    S: Transport + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let method: Cow<'static, str> = match &request {
+            RequestPacket::Single(request) => request.method_clone(),
+            RequestPacket::Batch(_) => Cow::Borrowed("batch"),
+        };
+        let RpcMetricsLayer {
+            chain,
+            slow_call_threshold,
+        } = self.layer;
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let start = Instant::now();
+            let result = response.await;
+            let elapsed = start.elapsed();
+
+            counter!("rpc.requests", "chain" => chain, "method" => method.clone()).increment(1);
+            histogram!("rpc.duration_ms", "chain" => chain, "method" => method.clone())
+                .record(elapsed.as_secs_f64() * 1000.0);
+            let failed = match &result {
+                Ok(response) => response.is_error(),
+                Err(_) => true,
+            };
+            if failed {
+                counter!("rpc.errors", "chain" => chain, "method" => method.clone()).increment(1);
+            }
+            if slow_call_threshold.is_some_and(|threshold| elapsed > threshold) {
+                warn!(
+                    chain,
+                    %method,
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    "Slow RPC call"
+                );
+            }
+
+            result
+        })
+    }
+}
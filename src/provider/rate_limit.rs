@@ -0,0 +1,140 @@
+use alloy::{
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    transports::{Transport, TransportError, TransportFut},
+};
+use init4_bin_base::deps::{metrics::counter, tracing::debug};
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+
+/// A token bucket, refilled continuously at a fixed rate.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens added per second.
+    rate: f64,
+    /// Maximum number of tokens.
+    burst: f64,
+    /// Currently available tokens. Negative when requests are queued.
+    tokens: f64,
+    /// When the bucket was last refilled.
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Take a token, returning how long the caller must wait before using it.
+    ///
+    /// Tokens are reserved even when none are available, so waiting callers are served in the
+    /// order they arrived.
+    fn reserve(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.refilled_at = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// A transport layer limiting the rate of RPC calls made through a provider.
+///
+/// Calls beyond the configured rate are queued rather than rejected, so bursts of order flow
+/// don't trip provider rate limits mid-fill. Each queued call increments the `rpc.throttled`
+/// counter, labelled by `chain`.
+///
+/// The limit is shared by every clone of the layer.
+#[derive(Debug, Clone)]
+pub struct RpcRateLimitLayer {
+    /// Name of the chain, used as a metric label.
+    chain: &'static str,
+    /// The shared token bucket, or `None` if calls are unlimited.
+    bucket: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl RpcRateLimitLayer {
+    /// A layer that does not limit calls.
+    pub const fn unlimited(chain: &'static str) -> Self {
+        Self {
+            chain,
+            bucket: None,
+        }
+    }
+
+    /// A layer allowing `requests_per_sec` calls per second on average, with bursts of up to
+    /// `burst` calls.
+    pub fn new(chain: &'static str, requests_per_sec: u32, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            chain,
+            bucket: Some(Arc::new(Mutex::new(TokenBucket {
+                rate: f64::from(requests_per_sec.max(1)),
+                burst,
+                tokens: burst,
+                refilled_at: Instant::now(),
+            }))),
+        }
+    }
+}
+
+impl<S> Layer<S> for RpcRateLimitLayer {
+    type Service = RpcRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRateLimitService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The transport service produced by [`RpcRateLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcRateLimitService<S> {
+    /// The wrapped transport.
+    inner: S,
+    /// The layer configuration.
+    layer: RpcRateLimitLayer,
+}
+
+impl<S> Service<RequestPacket> for RpcRateLimitService<S>
+where
+    S: Transport + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let Some(bucket) = &self.layer.bucket else {
+            return self.inner.call(request);
+        };
+        let wait = bucket.lock().unwrap().reserve();
+        if wait.is_zero() {
+            return self.inner.call(request);
+        }
+
+        let chain = self.layer.chain;
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            debug!(
+                chain,
+                wait_ms = wait.as_millis() as u64,
+                "RPC call throttled"
+            );
+            counter!("rpc.throttled", "chain" => chain).increment(1);
+            tokio::time::sleep(wait).await;
+            inner.call(request).await
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    filler::{Filler, FillerConfig},
+    provider::connect_provider,
+    runtime::RuntimeConfig,
+    timeout_signer::TimeoutSigner,
+};
+use alloy::signers::Signer;
+use eyre::{Error, WrapErr};
+use init4_bin_base::utils::{from_env::FromEnv, signer::LocalOrAws};
+use pyo3::{exceptions::PyRuntimeError, prelude::*};
+use signet_types::SignedOrder;
+use tokio::runtime::Runtime;
+
+/// Convert an error into a Python `RuntimeError`, keeping its chain of context.
+fn to_py_err(err: Error) -> PyErr {
+    PyRuntimeError::new_err(format!("{err:#}"))
+}
+
+/// Parse Orders from JSON.
+fn parse_orders(orders: &[String]) -> Result<Vec<SignedOrder>, Error> {
+    orders
+        .iter()
+        .map(|order| serde_json::from_str(order).wrap_err("invalid signed order"))
+        .collect()
+}
+
+/// Keep the Orders the evaluation hook accepts. The hook is called with each Order as JSON,
+/// and an exception it raises is raised to the caller.
+fn select(hook: &Bound<'_, PyAny>, orders: Vec<SignedOrder>) -> PyResult<Vec<SignedOrder>> {
+    let mut accepted = Vec::with_capacity(orders.len());
+    for order in orders {
+        let json = serde_json::to_string(&order).map_err(|err| to_py_err(err.into()))?;
+        if hook.call1((json,))?.is_truthy()? {
+            accepted.push(order);
+        }
+    }
+    Ok(accepted)
+}
+
+/// Connect a Filler configured from environment variables, as the Filler binaries are.
+async fn connect_filler() -> Result<Filler<TimeoutSigner<LocalOrAws>>, Error> {
+    let config = FillerConfig::from_env()?;
+    config.validate()?;
+
+    let mut signer = config.signer_config.connect().await?;
+    // ensure signer chain ID is unset so it can be used for Host and Rollup
+    signer.set_chain_id(None);
+    let signer = config.timeout_signer(signer);
+
+    let ru_provider = connect_provider(
+        signer.clone(),
+        config.ru_rpc_url.clone(),
+        config.ru_rpc_rate_limit(),
+        config.rpc_metrics("rollup"),
+    )
+    .await?;
+    let host_provider = connect_provider(
+        signer.clone(),
+        config.host_rpc_url.clone(),
+        config.host_rpc_rate_limit(),
+        config.rpc_metrics("host"),
+    )
+    .await?;
+
+    Filler::from_config(&config, signer, ru_provider, host_provider).await
+}
+
+/// A Filler, configured from environment variables.
+///
+/// Orders cross the boundary as JSON, in the transaction cache's format. Calls block the
+/// calling Python thread, releasing the GIL, while the Filler runs on its own tokio runtime,
+/// configured as in the binaries; see [`RuntimeConfig`].
+#[pyclass(name = "Filler", module = "orders")]
+#[derive(Debug)]
+pub struct PyFiller {
+    /// The Filler.
+    filler: Filler<TimeoutSigner<LocalOrAws>>,
+    /// The runtime driving the Filler.
+    runtime: Runtime,
+}
+
+#[pymethods]
+impl PyFiller {
+    /// Connect a Filler configured from environment variables, verifying that its providers
+    /// match the configured chain constants.
+    #[new]
+    fn new() -> PyResult<Self> {
+        let runtime = RuntimeConfig::from_env()
+            .map_err(Error::from)
+            .and_then(|config| config.build())
+            .map_err(to_py_err)?;
+        let filler = runtime.block_on(connect_filler()).map_err(to_py_err)?;
+        Ok(Self { filler, runtime })
+    }
+
+    /// Get the Orders in the transaction cache, as JSON.
+    fn get_orders(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        let orders = py
+            .detach(|| self.runtime.block_on(self.filler.get_orders()))
+            .map_err(to_py_err)?;
+        orders
+            .iter()
+            .map(|order| serde_json::to_string(order).map_err(|err| to_py_err(err.into())))
+            .collect()
+    }
+
+    /// Fill the Orders, given as JSON, in a single Bundle, as [`Filler::fill`] does. If an
+    /// evaluation hook is given, it is called with each Order as JSON, and only the Orders it
+    /// returns a truthy value for are filled.
+    ///
+    /// Returns the ids of the submitted Bundles, one per target block.
+    #[pyo3(signature = (orders, accepts = None))]
+    fn fill(
+        &self,
+        py: Python<'_>,
+        orders: Vec<String>,
+        accepts: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<String>> {
+        let mut orders = parse_orders(&orders).map_err(to_py_err)?;
+        if let Some(hook) = accepts {
+            orders = select(&hook, orders)?;
+        }
+        let bundle_ids = py
+            .detach(|| self.runtime.block_on(self.filler.fill(&orders)))
+            .map_err(to_py_err)?;
+        Ok(bundle_ids.iter().map(ToString::to_string).collect())
+    }
+}
+
+/// The `orders` Python module.
+#[pymodule]
+fn orders(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyFiller>()
+}
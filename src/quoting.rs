@@ -0,0 +1,300 @@
+use crate::{
+    analytics::ChainToken, clock::chain_time, ids::OrderHash, order::SendOrder,
+    provider::TxSenderProvider,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use eyre::{Error, eyre};
+use init4_bin_base::deps::tracing::{debug, error, info, instrument};
+use signet_types::{SignedOrder, UnsignedOrder};
+use std::{collections::HashMap, future::Future, time::Duration};
+use tokio::task::JoinHandle;
+
+/// Basis points in one whole.
+const BPS: f64 = 10_000.0;
+
+/// A source of token prices for auto-quoting.
+pub trait PriceOracle {
+    /// The mid price of `base` in terms of `quote`, as raw `quote` units per raw `base` unit.
+    ///
+    /// Prices are in raw token units, so the oracle is responsible for accounting for any
+    /// difference in token decimals.
+    fn price(
+        &self,
+        base: ChainToken,
+        quote: ChainToken,
+    ) -> impl Future<Output = Result<f64, Error>> + Send;
+}
+
+/// A [`PriceOracle`] with fixed prices, for operators without a price feed. Each token is
+/// priced in a common unit, and a pair is priced by the ratio of its tokens' prices.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StaticPrices {
+    /// The price of a raw unit of each token, in the common unit.
+    prices: HashMap<ChainToken, f64>,
+}
+
+impl StaticPrices {
+    /// Price tokens by the price of a raw unit of each, in a common unit.
+    pub const fn new(prices: HashMap<ChainToken, f64>) -> Self {
+        Self { prices }
+    }
+
+    /// Parse comma-separated `chain_id:token=price` entries, e.g. `1:0xC02a...=3000`, pricing a
+    /// raw unit of each token in a common unit. A chain's native token is `Address::ZERO`.
+    pub fn parse(prices: &str) -> Result<Self, Error> {
+        let prices = prices
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (token, price) = entry.split_once('=').ok_or_else(|| {
+                    eyre!("invalid token price {entry}: expected chain_id:token=price")
+                })?;
+                let (chain_id, address) = token
+                    .trim()
+                    .split_once(':')
+                    .ok_or_else(|| eyre!("invalid token {token}: expected chain_id:token"))?;
+                let chain_id = chain_id
+                    .parse()
+                    .map_err(|e| eyre!("invalid chain id {chain_id}: {e}"))?;
+                let address = address
+                    .parse()
+                    .map_err(|e| eyre!("invalid token {address}: {e}"))?;
+                let price: f64 = price
+                    .trim()
+                    .parse()
+                    .map_err(|e| eyre!("invalid price for {token}: {e}"))?;
+                if !price.is_finite() || price <= 0.0 {
+                    eyre::bail!("invalid price for {token}: must be positive");
+                }
+                Ok(((chain_id, address), price))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self::new(prices))
+    }
+
+    /// Whether the token is priced.
+    pub fn contains(&self, token: ChainToken) -> bool {
+        self.prices.contains_key(&token)
+    }
+
+    /// The price of a raw unit of the token, in the common unit.
+    fn unit_price(&self, (chain_id, token): ChainToken) -> Result<f64, Error> {
+        self.prices
+            .get(&(chain_id, token))
+            .copied()
+            .ok_or_else(|| eyre!("no price for token {token} on chain {chain_id}"))
+    }
+}
+
+impl PriceOracle for StaticPrices {
+    fn price(
+        &self,
+        base: ChainToken,
+        quote: ChainToken,
+    ) -> impl Future<Output = Result<f64, Error>> + Send {
+        let price = self
+            .unit_price(base)
+            .and_then(|base| Ok(base / self.unit_price(quote)?));
+        async move { price }
+    }
+}
+
+/// A pricing curve for a quoted pair: a ladder of Orders around the oracle mid price.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PricingCurve {
+    /// Spread of the first level above the mid price, in basis points.
+    pub spread_bps: u64,
+    /// Additional spread of each subsequent level, in basis points.
+    pub step_bps: u64,
+    /// Input amount of each level, closest to the mid price first.
+    pub sizes: Vec<U256>,
+}
+
+impl PricingCurve {
+    /// The Output amount asked for an Input amount at a ladder level, given the mid price.
+    pub fn ask(&self, level: usize, size: U256, mid: f64) -> Result<U256, Error> {
+        let spread_bps = self.spread_bps + self.step_bps * level as u64;
+        let ask = f64::from(size) * mid * (1.0 + spread_bps as f64 / BPS);
+        U256::try_from(ask.ceil()).map_err(|e| eyre!("invalid ask {ask} at level {level}: {e}"))
+    }
+}
+
+/// A token pair quoted by the [`AutoQuoter`]: resting Orders offer the Rollup `input` token in
+/// exchange for the `output` token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotedPair {
+    /// The Input token, on the Rollup.
+    pub input: Address,
+    /// The Output token, and the chain it is received on.
+    pub output: ChainToken,
+    /// The pricing curve for the pair.
+    pub curve: PricingCurve,
+}
+
+/// The most recent ladder quoted for a pair.
+#[derive(Debug, Clone, Copy)]
+struct Quoted {
+    /// The mid price the ladder was quoted at.
+    mid: f64,
+    /// The deadline of the ladder's Orders.
+    deadline: u64,
+}
+
+/// A simple market maker built on [`SendOrder`]: maintains resting Orders on configured token
+/// pairs, following each pair's [`PricingCurve`] around an oracle mid price.
+///
+/// Orders cannot be cancelled once sent without invalidating their Permit2 nonces, so each
+/// ladder is given a short lifetime instead. A pair is requoted when its Orders are about to
+/// expire, or when the mid price moves by more than the requote threshold; in the latter case
+/// the previous ladder remains fillable until its deadline, which bounds the stale exposure.
+#[derive(Debug)]
+pub struct AutoQuoter<S: Signer, O> {
+    /// Signs and sends the Orders.
+    sender: SendOrder<S>,
+    /// Prices the pairs.
+    oracle: O,
+    /// The address receiving Outputs.
+    recipient: Address,
+    /// The pairs to quote.
+    pairs: Vec<QuotedPair>,
+    /// Lifetime of each ladder of Orders, in seconds.
+    order_ttl: u64,
+    /// Mid price move, in basis points, after which a pair is requoted.
+    requote_bps: u64,
+    /// The most recent ladder quoted for each pair, by index.
+    quoted: Vec<Option<Quoted>>,
+}
+
+impl<S, O> AutoQuoter<S, O>
+where
+    S: Signer,
+    O: PriceOracle,
+{
+    /// Create a new AutoQuoter, sending Outputs to `recipient`.
+    ///
+    /// Each ladder of Orders lives for `order_ttl`, and pairs are requoted when the mid price
+    /// moves by more than `requote_bps`.
+    pub const fn new(
+        sender: SendOrder<S>,
+        oracle: O,
+        recipient: Address,
+        order_ttl: Duration,
+        requote_bps: u64,
+    ) -> Self {
+        Self {
+            sender,
+            oracle,
+            recipient,
+            pairs: Vec::new(),
+            order_ttl: order_ttl.as_secs(),
+            requote_bps,
+            quoted: Vec::new(),
+        }
+    }
+
+    /// Quote a token pair.
+    pub fn with_pair(mut self, pair: QuotedPair) -> Self {
+        self.pairs.push(pair);
+        self.quoted.push(None);
+        self
+    }
+
+    /// Whether a pair's ladder must be requoted at the given mid price and time.
+    fn needs_requote(&self, quoted: Option<Quoted>, mid: f64, now: u64) -> bool {
+        let Some(quoted) = quoted else {
+            return true;
+        };
+        // requote before the ladder expires, leaving a margin for the Orders to be filled
+        let expiring = now + self.order_ttl / 4 >= quoted.deadline;
+        let moved_bps = ((mid - quoted.mid) / quoted.mid).abs() * BPS;
+        expiring || moved_bps > self.requote_bps as f64
+    }
+
+    /// Build the ladder of Orders for a pair at the given mid price.
+    fn ladder(
+        &self,
+        pair: &QuotedPair,
+        mid: f64,
+        deadline: u64,
+    ) -> Result<Vec<UnsignedOrder<'static>>, Error> {
+        pair.curve
+            .sizes
+            .iter()
+            .enumerate()
+            .map(|(level, &size)| {
+                Ok(UnsignedOrder::default()
+                    .with_input(pair.input, size)
+                    .with_output(
+                        pair.output.1,
+                        pair.curve.ask(level, size, mid)?,
+                        self.recipient,
+                        pair.output.0 as u32,
+                    )
+                    .with_deadline(deadline))
+            })
+            .collect()
+    }
+
+    /// Requote every pair whose ladder is expiring or whose mid price has moved, signing and
+    /// sending new Orders. Returns the Orders sent.
+    #[instrument(skip(self))]
+    pub async fn refresh(&mut self, now: u64) -> Result<Vec<SignedOrder>, Error> {
+        let rollup_chain_id = self.sender.constants().rollup().chain_id();
+        let mut sent = Vec::new();
+
+        for index in 0..self.pairs.len() {
+            let pair = &self.pairs[index];
+            let mid = self
+                .oracle
+                .price((rollup_chain_id, pair.input), pair.output)
+                .await?;
+            if !mid.is_finite() || mid <= 0.0 {
+                eyre::bail!("invalid mid price {mid} for {pair:?}");
+            }
+            if !self.needs_requote(self.quoted[index], mid, now) {
+                debug!(input = %pair.input, output = %pair.output.1, mid, "Quote still fresh");
+                continue;
+            }
+
+            let deadline = now + self.order_ttl;
+            for unsigned in self.ladder(pair, mid, deadline)? {
+                let signed = self.sender.sign_unsigned_order(unsigned).await?;
+                self.sender.send_order(signed.clone()).await?;
+                debug!(order_hash = %OrderHash::from(&signed), "Quote sent");
+                sent.push(signed);
+            }
+            info!(input = %pair.input, output = %pair.output.1, mid, deadline, "Pair requoted");
+            self.quoted[index] = Some(Quoted { mid, deadline });
+        }
+
+        Ok(sent)
+    }
+}
+
+impl<S, O> AutoQuoter<S, O>
+where
+    S: Signer + Send + Sync + 'static,
+    O: PriceOracle + Send + Sync + 'static,
+{
+    /// Spawn a task that refreshes quotes on the given interval, forever, using the Rollup
+    /// chain time as the reference clock for deadlines.
+    pub fn spawn(mut self, ru_provider: TxSenderProvider, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                let refreshed = match chain_time(&ru_provider).await {
+                    Ok(now) => self.refresh(now).await,
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = refreshed {
+                    error!(%err, "Failed to refresh quotes");
+                }
+            }
+        })
+    }
+}
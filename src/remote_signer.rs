@@ -0,0 +1,218 @@
+use alloy::{
+    primitives::{Address, B256, ChainId, Signature},
+    signers::{self, Signer},
+};
+use async_trait::async_trait;
+use init4_bin_base::{
+    deps::tracing::{debug, instrument},
+    utils::from_env::FromEnv,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Default time to wait for a remote signature.
+const DEFAULT_SIGNING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default interval between polls for a remote signature.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Configuration for a [`RemoteSigner`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct RemoteSignerConfig {
+    /// Base URL of the remote signing service.
+    #[from_env(
+        var = "REMOTE_SIGNER_URL",
+        desc = "Base URL of the remote signing service"
+    )]
+    pub url: String,
+    /// The address the remote signing service signs for.
+    #[from_env(
+        var = "REMOTE_SIGNER_ADDRESS",
+        desc = "Address the remote signing service signs for"
+    )]
+    pub address: Address,
+    /// Maximum time to wait for a signature, in milliseconds.
+    #[from_env(
+        var = "REMOTE_SIGNER_TIMEOUT_MS",
+        desc = "Maximum time to wait for a remote signature, in ms",
+        optional
+    )]
+    pub timeout_ms: Option<u64>,
+}
+
+impl RemoteSignerConfig {
+    /// Connect a [`RemoteSigner`] using this configuration.
+    pub fn connect(&self) -> eyre::Result<RemoteSigner> {
+        let signer = RemoteSigner::new(self.url.parse()?, self.address)?;
+        Ok(match self.timeout_ms {
+            Some(timeout_ms) => signer.with_timeout(Duration::from_millis(timeout_ms)),
+            None => signer,
+        })
+    }
+}
+
+/// A signing request submitted to the remote signing service.
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    /// The address to sign with.
+    address: Address,
+    /// The hash to sign.
+    hash: B256,
+}
+
+/// The response to a submitted signing request.
+#[derive(Debug, Deserialize)]
+struct SignRequestCreated {
+    /// The id of the signing request, used to poll for its result.
+    id: String,
+}
+
+/// The status of a signing request.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum SignRequestStatus {
+    /// Awaiting approval or signature shares.
+    Pending,
+    /// Signed.
+    Signed {
+        /// The 65-byte signature, hex encoded.
+        signature: String,
+    },
+    /// Rejected by an approver.
+    Rejected {
+        /// Why the request was rejected.
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+/// A [`Signer`] backed by an external signing service, such as an MPC or threshold signing
+/// service that requires approvals before producing a signature.
+///
+/// Signing submits the hash with `POST {url}/sign`, then polls `GET {url}/sign/{id}` until the
+/// service reports the request as signed or rejected, or the timeout elapses. Approvals (e.g.
+/// via webhooks to approvers) happen entirely within the service.
+///
+/// Remote signatures may take seconds or minutes, so Fillers using a RemoteSigner should
+/// configure a deadline margin at least as long as the signing timeout, so Orders don't expire
+/// while their Fills are being signed.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    /// Base URL of the signing service.
+    url: reqwest::Url,
+    /// HTTP client.
+    client: reqwest::Client,
+    /// The address the service signs for.
+    address: Address,
+    /// The signer's chain ID.
+    chain_id: Option<ChainId>,
+    /// Maximum time to wait for a signature.
+    timeout: Duration,
+    /// Interval between polls for a signature.
+    poll_interval: Duration,
+}
+
+impl RemoteSigner {
+    /// Create a new RemoteSigner for the signing service at `url`, signing for `address`.
+    pub fn new(mut url: reqwest::Url, address: Address) -> eyre::Result<Self> {
+        // request paths are joined onto the base URL, which must end in a slash
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self {
+            url,
+            client: reqwest::ClientBuilder::new().use_rustls_tls().build()?,
+            address,
+            chain_id: None,
+            timeout: DEFAULT_SIGNING_TIMEOUT,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        })
+    }
+
+    /// Set the maximum time to wait for a signature.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the interval between polls for a signature.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The maximum time to wait for a signature.
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Submit a signing request, and poll until it is signed, rejected, or times out.
+    async fn request_signature(&self, hash: &B256) -> eyre::Result<Signature> {
+        let created: SignRequestCreated = self
+            .client
+            .post(self.url.join("sign")?)
+            .json(&SignRequest {
+                address: self.address,
+                hash: *hash,
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        debug!(request_id = %created.id, "Remote signing request submitted");
+
+        let status_url = self.url.join(&format!("sign/{}", created.id))?;
+        let started = Instant::now();
+        loop {
+            let status: SignRequestStatus = self
+                .client
+                .get(status_url.clone())
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            match status {
+                SignRequestStatus::Signed { signature } => {
+                    return signature
+                        .parse()
+                        .map_err(|e| eyre::eyre!("invalid remote signature {signature}: {e}"));
+                }
+                SignRequestStatus::Rejected { reason } => eyre::bail!(
+                    "remote signing request {} rejected: {}",
+                    created.id,
+                    reason.as_deref().unwrap_or("no reason given")
+                ),
+                SignRequestStatus::Pending if started.elapsed() >= self.timeout => eyre::bail!(
+                    "remote signing request {} timed out after {:?}",
+                    created.id,
+                    self.timeout
+                ),
+                SignRequestStatus::Pending => tokio::time::sleep(self.poll_interval).await,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    #[instrument(skip_all, fields(%hash, address = %self.address))]
+    async fn sign_hash(&self, hash: &B256) -> signers::Result<Signature> {
+        self.request_signature(hash)
+            .await
+            .map_err(signers::Error::message)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
@@ -0,0 +1,295 @@
+use crate::{
+    approval::{IntentInput, IntentOutput, OrderIntent},
+    contracts::permit2_nonce_used,
+    ids::OrderHash,
+    order::SendOrder,
+    provider::TxSenderProvider,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use chrono::Utc;
+use eyre::Error;
+use init4_bin_base::deps::tracing::{debug, info, instrument, warn};
+use std::{collections::BTreeMap, time::Duration};
+
+/// The status of an Order managed by the [`OrderRenewer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenewalStatus {
+    /// Submitted, and awaiting fill until its deadline.
+    Live {
+        /// The hash of the currently submitted Order.
+        order_hash: OrderHash,
+        /// The deadline of the currently submitted Order.
+        deadline: u64,
+        /// How many times the Order has been renewed.
+        renewals: u32,
+    },
+    /// Filled.
+    Filled {
+        /// The hash of the filled Order.
+        order_hash: OrderHash,
+    },
+    /// Reached its maximum lifetime before filling completely. Some clips may have filled.
+    Expired,
+}
+
+/// When to split a repeatedly expiring Order into smaller clips.
+///
+/// After `expiries_before_clip` consecutive expiries, the clip size is halved, and the remaining
+/// size of the Order is re-posted one clip at a time. Clips are never smaller than
+/// `min_clip_size`, measured in the Order's first Input token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipPolicy {
+    /// Consecutive expiries after which the clip size is halved.
+    pub expiries_before_clip: u32,
+    /// Minimum clip size, as an amount of the Order's first Input token.
+    pub min_clip_size: U256,
+}
+
+/// An Order renewed by the [`OrderRenewer`].
+#[derive(Debug, Clone)]
+struct RenewingOrder {
+    /// The full Order. Its deadline is replaced on each renewal.
+    intent: OrderIntent,
+    /// When to split the Order into smaller clips, if ever.
+    clip_policy: Option<ClipPolicy>,
+    /// Each clip is `1 / 2^clip_level` of the full Order.
+    clip_level: u32,
+    /// Number of clips of the current size left to fill.
+    clips_remaining: u64,
+    /// Consecutive expiries at the current clip size.
+    expiries: u32,
+    /// Lifetime of each submission, in seconds.
+    ttl: u64,
+    /// Time after which the Order is no longer renewed.
+    renew_until: u64,
+    /// The Permit2 nonce of the current submission.
+    nonce: u64,
+    /// The current status.
+    status: RenewalStatus,
+}
+
+impl RenewingOrder {
+    /// Whether the Order has expired often enough to be split into smaller clips, and the
+    /// smaller clips would be no smaller than the minimum clip size.
+    fn should_clip(&self) -> bool {
+        let Some(policy) = self.clip_policy else {
+            return false;
+        };
+        let Some(input) = self.intent.inputs.first() else {
+            return false;
+        };
+        self.expiries >= policy.expiries_before_clip
+            && self.clip_level < 63
+            && input.amount >> (self.clip_level + 1) as usize >= policy.min_clip_size
+    }
+
+    /// The Order for a single clip at the current clip size.
+    fn clip(&self) -> OrderIntent {
+        let shift = self.clip_level as usize;
+        OrderIntent {
+            inputs: self
+                .intent
+                .inputs
+                .iter()
+                .map(|input| IntentInput {
+                    amount: input.amount >> shift,
+                    ..*input
+                })
+                .collect(),
+            outputs: self
+                .intent
+                .outputs
+                .iter()
+                .map(|output| IntentOutput {
+                    amount: output.amount >> shift,
+                    ..*output
+                })
+                .collect(),
+            deadline: self.intent.deadline,
+        }
+    }
+}
+
+/// Re-signs and resubmits Orders with a fresh deadline when they expire unfilled, up to a
+/// maximum lifetime.
+///
+/// Renewals keep the Order's exchange rate unchanged, and are tracked under the caller's client
+/// order id. Orders that keep expiring can be split into smaller clips, per their [`ClipPolicy`],
+/// when the desired size exceeds what Fillers are taking. Each submission uses a new Permit2 nonce, so fills are detected by checking
+/// whether the current submission's nonce has been used.
+#[derive(Debug)]
+pub struct OrderRenewer<S: Signer> {
+    /// Signs and sends the Orders.
+    sender: SendOrder<S>,
+    /// Provider for the Rollup, used to check nonces.
+    ru_provider: TxSenderProvider,
+    /// The owner of the Orders.
+    owner: Address,
+    /// Nonce for the next submission.
+    next_nonce: u64,
+    /// Managed Orders, by client order id.
+    orders: BTreeMap<String, RenewingOrder>,
+}
+
+impl<S> OrderRenewer<S>
+where
+    S: Signer,
+{
+    /// Create a new OrderRenewer for Orders signed by `owner`, through the `sender`.
+    pub fn new(sender: SendOrder<S>, ru_provider: TxSenderProvider, owner: Address) -> Self {
+        Self {
+            sender,
+            ru_provider,
+            owner,
+            // microseconds since the epoch, so nonces don't collide with earlier runs
+            next_nonce: Utc::now().timestamp_micros() as u64,
+            orders: BTreeMap::new(),
+        }
+    }
+
+    /// The status of an Order, by client order id.
+    pub fn status(&self, client_id: &str) -> Option<RenewalStatus> {
+        self.orders.get(client_id).map(|order| order.status)
+    }
+
+    /// The fraction of an Order left to fill, by client order id, as `(numerator, denominator)`.
+    pub fn remaining(&self, client_id: &str) -> Option<(u64, u64)> {
+        self.orders
+            .get(client_id)
+            .map(|order| (order.clips_remaining, 1 << order.clip_level))
+    }
+
+    /// Sign and submit the intent with a deadline `ttl` after `now`, using a fresh nonce.
+    async fn submit(
+        &mut self,
+        intent: &OrderIntent,
+        ttl: u64,
+        now: u64,
+    ) -> Result<(u64, OrderHash, u64), Error> {
+        let nonce = self.next_nonce;
+        let deadline = now + ttl;
+        let unsigned = OrderIntent {
+            deadline,
+            ..intent.clone()
+        }
+        .to_unsigned()
+        .with_nonce(nonce);
+
+        let signed = self.sender.sign_unsigned_order(unsigned).await?;
+        self.sender.send_order(signed.clone()).await?;
+        self.next_nonce += 1;
+
+        Ok((nonce, OrderHash::from(&signed), deadline))
+    }
+
+    /// Submit an Order under a client order id, renewing it every `ttl` until it fills or
+    /// `max_lifetime` has passed since `now`. If a [`ClipPolicy`] is given, the Order is split
+    /// into smaller clips when it keeps expiring.
+    #[instrument(skip(self, intent))]
+    pub async fn submit_renewing(
+        &mut self,
+        client_id: String,
+        intent: OrderIntent,
+        ttl: Duration,
+        max_lifetime: Duration,
+        clip_policy: Option<ClipPolicy>,
+        now: u64,
+    ) -> Result<OrderHash, Error> {
+        if self.orders.contains_key(&client_id) {
+            eyre::bail!("client order id {client_id} is already in use");
+        }
+
+        let ttl = ttl.as_secs();
+        let (nonce, order_hash, deadline) = self.submit(&intent, ttl, now).await?;
+        info!(%order_hash, deadline, "Renewing order submitted");
+
+        self.orders.insert(
+            client_id,
+            RenewingOrder {
+                intent,
+                clip_policy,
+                clip_level: 0,
+                clips_remaining: 1,
+                expiries: 0,
+                ttl,
+                renew_until: now + max_lifetime.as_secs(),
+                nonce,
+                status: RenewalStatus::Live {
+                    order_hash,
+                    deadline,
+                    renewals: 0,
+                },
+            },
+        );
+        Ok(order_hash)
+    }
+
+    /// Check every live Order at chain time `now`, marking filled Orders, and renewing Orders
+    /// that expired unfilled within their maximum lifetime.
+    #[instrument(skip(self))]
+    pub async fn tick(&mut self, now: u64) -> Result<(), Error> {
+        let live: Vec<String> = self
+            .orders
+            .iter()
+            .filter(|(_, order)| matches!(order.status, RenewalStatus::Live { .. }))
+            .map(|(client_id, _)| client_id.clone())
+            .collect();
+
+        for client_id in live {
+            let order = self.orders[&client_id].clone();
+            let RenewalStatus::Live {
+                order_hash,
+                deadline,
+                renewals,
+            } = order.status
+            else {
+                continue;
+            };
+
+            if permit2_nonce_used(&self.ru_provider, self.owner, U256::from(order.nonce)).await? {
+                let order = self.orders.get_mut(&client_id).unwrap();
+                order.clips_remaining -= 1;
+                order.expiries = 0;
+                if order.clips_remaining == 0 {
+                    info!(%client_id, %order_hash, renewals, "Renewing order filled");
+                    order.status = RenewalStatus::Filled { order_hash };
+                    continue;
+                }
+                debug!(%client_id, %order_hash, clips_remaining = order.clips_remaining, "Clip filled");
+            } else if deadline >= now {
+                continue;
+            } else {
+                self.orders.get_mut(&client_id).unwrap().expiries += 1;
+            }
+
+            let order = self.orders.get_mut(&client_id).unwrap();
+            if now + order.ttl > order.renew_until {
+                warn!(%client_id, %order_hash, renewals, "Renewing order reached its maximum lifetime");
+                order.status = RenewalStatus::Expired;
+                continue;
+            }
+            if order.should_clip() {
+                order.clip_level += 1;
+                order.clips_remaining *= 2;
+                order.expiries = 0;
+                info!(%client_id, clip_level = order.clip_level, "Order split into smaller clips");
+            }
+
+            let clip = order.clip();
+            let ttl = order.ttl;
+            let (nonce, order_hash, deadline) = self.submit(&clip, ttl, now).await?;
+            info!(%client_id, %order_hash, deadline, renewals = renewals + 1, "Order renewed");
+            let order = self.orders.get_mut(&client_id).unwrap();
+            order.nonce = nonce;
+            order.status = RenewalStatus::Live {
+                order_hash,
+                deadline,
+                renewals: renewals + 1,
+            };
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{etcd::EtcdClient, ids::OrderHash};
+use eyre::Error;
+use init4_bin_base::{
+    deps::{
+        metrics::counter,
+        tracing::{debug, warn},
+    },
+    utils::from_env::FromEnv,
+};
+use signet_types::SignedOrder;
+use std::time::Duration;
+
+/// Default prefix of reservation keys.
+const DEFAULT_PREFIX: &str = "signet-orders/reservations/";
+
+/// Configuration for [`OrderReservations`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct ReservationConfig {
+    /// Base URL of the etcd cluster's HTTP gateway. Reservations are disabled if unset.
+    #[from_env(
+        var = "RESERVATION_ETCD_URL",
+        desc = "Base URL of the etcd HTTP gateway used for Order reservations",
+        optional
+    )]
+    pub etcd_url: Option<String>,
+    /// Prefix of reservation keys. Replicas sharing Order flow must share it.
+    #[from_env(
+        var = "RESERVATION_PREFIX",
+        desc = "Prefix of Order reservation keys",
+        optional
+    )]
+    pub prefix: Option<String>,
+    /// Name of this replica, stored with its reservations. Must be unique per replica, since a
+    /// replica may retake its own reservations. Defaults to the host name.
+    #[from_env(
+        var = "RESERVATION_NAME",
+        desc = "Unique name of this replica in Order reservations",
+        optional
+    )]
+    pub name: Option<String>,
+    /// Minimum seconds a reservation is held before other replicas may take the Order.
+    /// Reservations are always held until the fill's last target block has passed.
+    #[from_env(
+        var = "RESERVATION_TTL_SECS",
+        desc = "Minimum seconds an Order reservation is held",
+        optional
+    )]
+    pub ttl_secs: Option<u64>,
+}
+
+impl ReservationConfig {
+    /// Connect [`OrderReservations`] using this configuration, if an etcd URL is configured.
+    pub fn connect(&self) -> eyre::Result<Option<OrderReservations>> {
+        let Some(url) = &self.etcd_url else {
+            return Ok(None);
+        };
+        let name = self
+            .name
+            .clone()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .unwrap_or_else(|| format!("filler-{}", std::process::id()));
+        let mut reservations = OrderReservations::new(url.parse()?, name)?;
+        if let Some(prefix) = &self.prefix {
+            reservations = reservations.with_prefix(prefix);
+        }
+        if let Some(ttl_secs) = self.ttl_secs {
+            reservations = reservations.with_ttl(Duration::from_secs(ttl_secs));
+        }
+        Ok(Some(reservations))
+    }
+}
+
+/// Orders reserved by this replica, under one lease.
+#[derive(Debug, Clone, Default)]
+pub struct Reservation {
+    /// The Orders reserved.
+    pub orders: Vec<SignedOrder>,
+    /// The lease holding the reservations, if any Order was reserved in the store.
+    pub lease: Option<i64>,
+}
+
+/// A shared store of Order reservations, so horizontally scaled Filler replicas partition the
+/// Order flow instead of racing each other to fill the same Orders.
+///
+/// Before filling, a replica reserves each Order by creating a key for its hash in etcd,
+/// through its HTTP gateway, holding its name; an Order already reserved by another replica is
+/// left to that replica, while one already reserved by this replica is retaken, so its own
+/// retries within the TTL are not refused. Reservations are held until the fill's last target
+/// block has passed, or longer if a longer TTL is set, so an Order whose fill never lands can be
+/// taken by any replica once they expire, and a replica releases its reservations early if its
+/// fill fails before any Bundle is sent. If etcd can't be reached, nothing is reserved and
+/// nothing is filled. Orders left to other replicas increment the `filler.reserved_elsewhere`
+/// counter.
+#[derive(Debug, Clone)]
+pub struct OrderReservations {
+    /// Client for the etcd HTTP gateway.
+    etcd: EtcdClient,
+    /// Prefix of reservation keys.
+    prefix: String,
+    /// Name of this replica.
+    name: String,
+    /// Minimum time a reservation is held.
+    ttl: Duration,
+}
+
+impl OrderReservations {
+    /// Create a new reservation store through the etcd HTTP gateway at `url`, reserving as
+    /// `name`, which must be unique per replica.
+    pub fn new(url: reqwest::Url, name: impl Into<String>) -> eyre::Result<Self> {
+        Ok(Self {
+            etcd: EtcdClient::new(url)?,
+            prefix: DEFAULT_PREFIX.to_string(),
+            name: name.into(),
+            ttl: Duration::ZERO,
+        })
+    }
+
+    /// Set the prefix of reservation keys.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the minimum time a reservation is held. Reservations are always held for at least
+    /// the hold requested by [`OrderReservations::reserve`].
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Name of this replica.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reserve the Orders for this replica for at least `hold`, or the configured TTL if
+    /// longer, returning those it now holds. Orders reserved by another replica are left out.
+    ///
+    /// If etcd fails partway through the batch, the reservations already taken are released.
+    pub async fn reserve(
+        &self,
+        orders: Vec<SignedOrder>,
+        hold: Duration,
+    ) -> Result<Reservation, Error> {
+        if orders.is_empty() {
+            return Ok(Reservation::default());
+        }
+
+        // one lease holds every reservation in the batch, so they expire together
+        let lease = self.etcd.grant(self.ttl.max(hold)).await?;
+        let mut reserved = Vec::with_capacity(orders.len());
+        for order in orders {
+            let order_hash = OrderHash::from(&order);
+            match self.claim(order_hash, lease).await {
+                Ok(true) => reserved.push(order),
+                Ok(false) => {
+                    debug!(%order_hash, "Skipping order reserved elsewhere");
+                    counter!("filler.reserved_elsewhere").increment(1);
+                }
+                Err(err) => {
+                    self.release(lease).await;
+                    return Err(err);
+                }
+            }
+        }
+
+        if reserved.is_empty() {
+            self.release(lease).await;
+            return Ok(Reservation::default());
+        }
+        Ok(Reservation {
+            orders: reserved,
+            lease: Some(lease),
+        })
+    }
+
+    /// Reserve an Order under the lease, returning whether this replica now holds it: either
+    /// it was unreserved, or this replica had already reserved it.
+    async fn claim(&self, order_hash: OrderHash, lease: i64) -> Result<bool, Error> {
+        let key = format!("{}{order_hash}", self.prefix);
+        if self.etcd.put_if_absent(&key, &self.name, lease).await? {
+            return Ok(true);
+        }
+        self.etcd.put_if_value(&key, &self.name, lease).await
+    }
+
+    /// Release the reservations held under a lease, so other replicas can take their Orders
+    /// without waiting for them to expire. Failures are logged, and the reservations then
+    /// expire after the TTL.
+    pub async fn release(&self, lease: i64) {
+        if let Err(err) = self.etcd.revoke(lease).await {
+            warn!(%err, lease, "Failed to release order reservations");
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::ids::OrderHash;
+use alloy::primitives::{Address, U256};
+use init4_bin_base::{
+    deps::{
+        metrics::counter,
+        tracing::{debug, instrument, warn},
+    },
+    utils::from_env::FromEnv,
+};
+use serde::{Deserialize, Serialize};
+use signet_types::SignedOrder;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Default time to wait for a risk decision.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Default time a risk decision is reused for the same Order.
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Configuration for a [`RiskService`].
+#[derive(Debug, Clone, FromEnv)]
+pub struct RiskServiceConfig {
+    /// Base URL of the risk policy service. Risk checks are disabled if unset.
+    #[from_env(
+        var = "RISK_SERVICE_URL",
+        desc = "Base URL of the risk policy service",
+        optional
+    )]
+    pub url: Option<String>,
+    /// Maximum time to wait for a decision, in milliseconds.
+    #[from_env(
+        var = "RISK_SERVICE_TIMEOUT_MS",
+        desc = "Maximum time to wait for a risk decision, in ms",
+        optional
+    )]
+    pub timeout_ms: Option<u64>,
+    /// How long decisions are reused for the same Order, in milliseconds.
+    #[from_env(
+        var = "RISK_SERVICE_CACHE_MS",
+        desc = "How long risk decisions are reused for the same Order, in ms",
+        optional
+    )]
+    pub cache_ms: Option<u64>,
+    /// If true, Orders are filled when the service cannot be reached. Defaults to false.
+    #[from_env(
+        var = "RISK_SERVICE_FAIL_OPEN",
+        desc = "Fill Orders when the risk service cannot be reached",
+        optional
+    )]
+    pub fail_open: Option<bool>,
+}
+
+impl RiskServiceConfig {
+    /// Connect a [`RiskService`] using this configuration, if a URL is configured.
+    pub fn connect(&self) -> eyre::Result<Option<RiskService>> {
+        let Some(url) = &self.url else {
+            return Ok(None);
+        };
+        let mut service =
+            RiskService::new(url.parse()?)?.with_fail_open(self.fail_open.unwrap_or_default());
+        if let Some(timeout_ms) = self.timeout_ms {
+            service = service.with_timeout(Duration::from_millis(timeout_ms));
+        }
+        if let Some(cache_ms) = self.cache_ms {
+            service = service.with_cache_ttl(Duration::from_millis(cache_ms));
+        }
+        Ok(Some(service))
+    }
+}
+
+/// An Order Input in a [`RiskRequest`].
+#[derive(Debug, Serialize)]
+struct RiskInput {
+    /// The token, on the Rollup.
+    token: Address,
+    /// The amount, in raw token units.
+    amount: U256,
+}
+
+/// An Order Output in a [`RiskRequest`].
+#[derive(Debug, Serialize)]
+struct RiskOutput {
+    /// The chain the Output is provided on.
+    chain_id: u64,
+    /// The token.
+    token: Address,
+    /// The amount, in raw token units.
+    amount: U256,
+    /// The recipient of the Output.
+    recipient: Address,
+}
+
+/// The Order details submitted to the risk service.
+#[derive(Debug, Serialize)]
+struct RiskRequest {
+    /// The hash of the Order.
+    order_hash: OrderHash,
+    /// The owner of the Order.
+    owner: Address,
+    /// The Order deadline.
+    deadline: U256,
+    /// The Inputs the Filler receives, on the Rollup.
+    inputs: Vec<RiskInput>,
+    /// The Outputs the Filler provides.
+    outputs: Vec<RiskOutput>,
+}
+
+/// The risk service's decision on filling an Order.
+#[derive(Debug, Clone, Deserialize)]
+struct RiskDecision {
+    /// Whether the Order may be filled.
+    allow: bool,
+    /// Why the Order was denied.
+    #[serde(default)]
+    reason: Option<String>,
+    /// The maximum amount of any single Output the Filler may provide, if limited.
+    #[serde(default)]
+    max_output_amount: Option<U256>,
+}
+
+/// A client for an external risk policy service, consulted before each Order is filled, for
+/// desks with centralized risk systems.
+///
+/// Each Order's details are submitted with `POST {url}/decide`. The service responds with
+/// `allow`, an optional `reason`, and optional limits; an Order with any Output above
+/// `max_output_amount` is denied. Decisions are cached per Order for a short time.
+///
+/// If the service cannot be reached or times out, Orders are denied (fail-closed) unless the
+/// client is configured to fail open. Denials increment the `risk.denied` counter, and
+/// unreachable-service errors the `risk.errors` counter.
+#[derive(Debug)]
+pub struct RiskService {
+    /// Base URL of the risk service.
+    url: reqwest::Url,
+    /// HTTP client.
+    client: reqwest::Client,
+    /// Maximum time to wait for a decision.
+    timeout: Duration,
+    /// How long decisions are reused.
+    cache_ttl: Duration,
+    /// Whether Orders are filled when the service cannot be reached.
+    fail_open: bool,
+    /// Recent decisions, and when they were made, by Order hash.
+    cache: Mutex<HashMap<OrderHash, (Instant, RiskDecision)>>,
+}
+
+impl RiskService {
+    /// Create a new fail-closed RiskService for the policy service at `url`.
+    pub fn new(mut url: reqwest::Url) -> eyre::Result<Self> {
+        // request paths are joined onto the base URL, which must end in a slash
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self {
+            url,
+            client: reqwest::ClientBuilder::new().use_rustls_tls().build()?,
+            timeout: DEFAULT_TIMEOUT,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            fail_open: false,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Set the maximum time to wait for a decision.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how long decisions are reused for the same Order.
+    pub const fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Fill Orders when the service cannot be reached, rather than denying them.
+    pub const fn with_fail_open(mut self, fail_open: bool) -> Self {
+        self.fail_open = fail_open;
+        self
+    }
+
+    /// Request a decision on the Order from the service.
+    async fn request_decision(&self, order: &SignedOrder) -> eyre::Result<RiskDecision> {
+        let permit = &order.permit;
+        let request = RiskRequest {
+            order_hash: OrderHash::from(order),
+            owner: permit.owner,
+            deadline: permit.permit.deadline,
+            inputs: permit
+                .permit
+                .permitted
+                .iter()
+                .map(|input| RiskInput {
+                    token: input.token,
+                    amount: input.amount,
+                })
+                .collect(),
+            outputs: order
+                .outputs
+                .iter()
+                .map(|output| RiskOutput {
+                    chain_id: u64::from(output.chainId),
+                    token: output.token,
+                    amount: output.amount,
+                    recipient: output.recipient,
+                })
+                .collect(),
+        };
+        Ok(self
+            .client
+            .post(self.url.join("decide")?)
+            .timeout(self.timeout)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Whether the risk service allows the Order to be filled.
+    #[instrument(skip_all, fields(order_hash = %OrderHash::from(order)))]
+    pub async fn allows(&self, order: &SignedOrder) -> bool {
+        let order_hash = OrderHash::from(order);
+        let cached = self
+            .cache
+            .lock()
+            .unwrap()
+            .get(&order_hash)
+            .filter(|(decided_at, _)| decided_at.elapsed() < self.cache_ttl)
+            .map(|(_, decision)| decision.clone());
+
+        let decision = match cached {
+            Some(decision) => decision,
+            None => match self.request_decision(order).await {
+                Ok(decision) => {
+                    let mut cache = self.cache.lock().unwrap();
+                    cache.retain(|_, (decided_at, _)| decided_at.elapsed() < self.cache_ttl);
+                    cache.insert(order_hash, (Instant::now(), decision.clone()));
+                    decision
+                }
+                Err(err) => {
+                    warn!(%err, fail_open = self.fail_open, "Risk service unavailable");
+                    counter!("risk.errors").increment(1);
+                    return self.fail_open;
+                }
+            },
+        };
+        debug!(?decision, "Risk decision");
+
+        let over_limit = decision
+            .max_output_amount
+            .is_some_and(|max| order.outputs.iter().any(|output| output.amount > max));
+        if !decision.allow || over_limit {
+            warn!(
+                reason = decision.reason.as_deref().unwrap_or("no reason given"),
+                over_limit, "Risk service denied order"
+            );
+            counter!("risk.denied").increment(1);
+            return false;
+        }
+        true
+    }
+}
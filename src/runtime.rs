@@ -0,0 +1,57 @@
+use init4_bin_base::utils::from_env::FromEnv;
+use tokio::{runtime::Runtime, task::spawn_blocking};
+
+/// Configuration for the tokio runtime of long-running binaries.
+///
+/// Unset knobs keep tokio's defaults: one worker thread per core, and up to 512 blocking
+/// threads.
+#[derive(Debug, Clone, Copy, FromEnv)]
+pub struct RuntimeConfig {
+    /// Number of worker threads driving async tasks.
+    /// .env var: TOKIO_WORKER_THREADS
+    #[from_env(
+        var = "TOKIO_WORKER_THREADS",
+        desc = "Number of tokio worker threads",
+        optional
+    )]
+    pub worker_threads: Option<usize>,
+    /// Maximum number of threads running blocking tasks, such as transaction encoding.
+    /// .env var: TOKIO_MAX_BLOCKING_THREADS
+    #[from_env(
+        var = "TOKIO_MAX_BLOCKING_THREADS",
+        desc = "Maximum number of tokio blocking threads",
+        optional
+    )]
+    pub max_blocking_threads: Option<usize>,
+}
+
+impl RuntimeConfig {
+    /// Build a multi-threaded runtime with this configuration.
+    pub fn build(&self) -> eyre::Result<Runtime> {
+        if self.worker_threads == Some(0) {
+            eyre::bail!("TOKIO_WORKER_THREADS: must be greater than zero");
+        }
+        if self.max_blocking_threads == Some(0) {
+            eyre::bail!("TOKIO_MAX_BLOCKING_THREADS: must be greater than zero");
+        }
+
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all();
+        if let Some(worker_threads) = self.worker_threads {
+            builder.worker_threads(worker_threads);
+        }
+        if let Some(max_blocking_threads) = self.max_blocking_threads {
+            builder.max_blocking_threads(max_blocking_threads);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Run CPU-heavy work on the blocking thread pool, so it doesn't starve the reactor under load.
+pub async fn offload<T, F>(work: F) -> eyre::Result<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    Ok(spawn_blocking(work).await?)
+}
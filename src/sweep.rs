@@ -0,0 +1,265 @@
+use crate::{contracts::IERC20, provider::TxSenderProvider};
+use alloy::{
+    network::TransactionBuilder,
+    primitives::{Address, B256, U256},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+};
+use eyre::Error;
+use init4_bin_base::{
+    deps::{
+        metrics::counter,
+        tracing::{debug, error, info, instrument, warn},
+    },
+    utils::from_env::FromEnv,
+};
+use signet_constants::SignetConstants;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Configuration for periodic profit sweeps.
+#[derive(Debug, Clone, Copy, FromEnv)]
+pub struct ProfitSweepConfig {
+    /// Interval between sweeps, in milliseconds. Sweeping is disabled if unset.
+    #[from_env(
+        var = "SWEEP_INTERVAL_MS",
+        desc = "Interval between profit sweeps, in ms",
+        optional
+    )]
+    pub interval_ms: Option<u64>,
+    /// The address profits are swept to on each chain. Sweeping is disabled if unset.
+    #[from_env(
+        var = "SWEEP_TREASURY",
+        desc = "Address profits are swept to",
+        optional
+    )]
+    pub treasury: Option<Address>,
+    /// WETH kept on each chain as fill inventory, in wei. Only the excess is swept.
+    #[from_env(
+        var = "SWEEP_WETH_FLOAT_WEI",
+        desc = "WETH kept on each chain as fill inventory, in wei",
+        optional
+    )]
+    pub weth_float: Option<u64>,
+    /// If true, sweeps are logged but not sent.
+    #[from_env(
+        var = "SWEEP_DRY_RUN",
+        desc = "Log sweeps without sending them",
+        optional
+    )]
+    pub dry_run: Option<bool>,
+}
+
+/// A single balance swept by the [`ProfitSweep`].
+#[derive(Debug, Clone)]
+struct SweptBalance {
+    /// Name of the chain, used as a metric label.
+    chain: &'static str,
+    /// Provider for the chain, holding the signer that owns the balance.
+    provider: TxSenderProvider,
+    /// The token to sweep, or `None` for the native gas token.
+    token: Option<Address>,
+    /// Balance kept back, as gas or fill inventory.
+    float: U256,
+}
+
+impl SweptBalance {
+    /// Label used for the token in metrics and logs.
+    fn token_label(&self) -> String {
+        self.token
+            .map(|token| token.to_string())
+            .unwrap_or_else(|| "native".to_string())
+    }
+}
+
+/// Periodically transfers balances above a float to a treasury address.
+///
+/// Sweeps are sent as plain transactions, outside of fill Bundles. A chain is skipped while
+/// the signer has pending transactions on it, so sweeps only run while the Filler is idle there
+/// and never race in-flight transactions for nonces.
+///
+/// Each sweep increments the `sweep.transfers` counter, and each failed sweep the
+/// `sweep.failures` counter, labelled by `chain` and `token`, which alerting rules can be
+/// built on.
+#[derive(Debug)]
+pub struct ProfitSweep {
+    /// The address profits are swept from.
+    owner: Address,
+    /// The address profits are swept to.
+    treasury: Address,
+    /// Interval between sweeps.
+    interval: Duration,
+    /// Whether to log sweeps without sending them.
+    dry_run: bool,
+    /// The balances to sweep.
+    balances: Vec<SweptBalance>,
+}
+
+impl ProfitSweep {
+    /// Create a new ProfitSweep from the owner to the treasury, sweeping on the given interval.
+    pub const fn new(owner: Address, treasury: Address, interval: Duration) -> Self {
+        Self {
+            owner,
+            treasury,
+            interval,
+            dry_run: false,
+            balances: Vec::new(),
+        }
+    }
+
+    /// Create a ProfitSweep of WETH above the configured float on both the Host and Rollup.
+    ///
+    /// Returns `None` if no interval or treasury is configured.
+    pub fn from_config(
+        config: &ProfitSweepConfig,
+        owner: Address,
+        constants: &SignetConstants,
+        ru_provider: TxSenderProvider,
+        host_provider: TxSenderProvider,
+    ) -> Option<Self> {
+        let interval = Duration::from_millis(config.interval_ms?);
+        let float = U256::from(config.weth_float.unwrap_or_default());
+
+        Some(
+            Self::new(owner, config.treasury?, interval)
+                .with_dry_run(config.dry_run.unwrap_or_default())
+                .sweep_token(
+                    "rollup",
+                    ru_provider,
+                    constants.rollup().tokens().weth(),
+                    float,
+                )
+                .sweep_token(
+                    "host",
+                    host_provider,
+                    constants.host().tokens().weth(),
+                    float,
+                ),
+        )
+    }
+
+    /// Log sweeps without sending them.
+    pub const fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Sweep the native gas token above `float` on a chain. The float must cover the gas of
+    /// the sweep itself.
+    pub fn sweep_native(
+        mut self,
+        chain: &'static str,
+        provider: TxSenderProvider,
+        float: U256,
+    ) -> Self {
+        self.balances.push(SweptBalance {
+            chain,
+            provider,
+            token: None,
+            float,
+        });
+        self
+    }
+
+    /// Sweep an ERC20 token above `float` on a chain.
+    pub fn sweep_token(
+        mut self,
+        chain: &'static str,
+        provider: TxSenderProvider,
+        token: Address,
+        float: U256,
+    ) -> Self {
+        self.balances.push(SweptBalance {
+            chain,
+            provider,
+            token: Some(token),
+            float,
+        });
+        self
+    }
+
+    /// Whether the owner has transactions pending on the chain.
+    async fn has_pending(&self, provider: &TxSenderProvider) -> Result<bool, Error> {
+        let pending = provider.get_transaction_count(self.owner).pending().await?;
+        let latest = provider.get_transaction_count(self.owner).latest().await?;
+        Ok(pending > latest)
+    }
+
+    /// Transfer `amount` of the swept balance to the treasury.
+    async fn send(&self, swept: &SweptBalance, amount: U256) -> Result<B256, Error> {
+        let pending = match swept.token {
+            None => {
+                let tx = TransactionRequest::default()
+                    .with_to(self.treasury)
+                    .with_value(amount);
+                swept.provider.send_transaction(tx).await?
+            }
+            Some(token) => {
+                IERC20::new(token, &swept.provider)
+                    .transfer(self.treasury, amount)
+                    .send()
+                    .await?
+            }
+        };
+        Ok(*pending.tx_hash())
+    }
+
+    /// Sweep every balance above its float once.
+    ///
+    /// Failures on one balance are logged and counted, and do not stop the others.
+    #[instrument(skip_all, fields(owner = %self.owner, treasury = %self.treasury))]
+    pub async fn sweep(&self) -> Result<(), Error> {
+        for swept in &self.balances {
+            let token = swept.token_label();
+            if self.has_pending(&swept.provider).await? {
+                debug!(chain = swept.chain, %token, "Transactions pending; skipping sweep");
+                continue;
+            }
+
+            let balance = match swept.token {
+                Some(token) => {
+                    IERC20::new(token, &swept.provider)
+                        .balanceOf(self.owner)
+                        .call()
+                        .await?
+                }
+                None => swept.provider.get_balance(self.owner).await?,
+            };
+            let Some(excess) = balance.checked_sub(swept.float).filter(|e| !e.is_zero()) else {
+                debug!(chain = swept.chain, %token, %balance, "Nothing to sweep");
+                continue;
+            };
+
+            if self.dry_run {
+                info!(chain = swept.chain, %token, %excess, "Dry run; would sweep profit");
+                continue;
+            }
+            match self.send(swept, excess).await {
+                Ok(tx_hash) => {
+                    info!(chain = swept.chain, %token, %excess, %tx_hash, "Swept profit");
+                    counter!("sweep.transfers", "chain" => swept.chain, "token" => token)
+                        .increment(1);
+                }
+                Err(err) => {
+                    warn!(chain = swept.chain, %token, %excess, %err, "Failed to sweep profit");
+                    counter!("sweep.failures", "chain" => swept.chain, "token" => token)
+                        .increment(1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawn a task that sweeps on the configured interval, forever.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                if let Err(err) = self.sweep().await {
+                    error!(%err, "Failed to sweep profits");
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use alloy::{
+    consensus::SignableTransaction,
+    network::TxSigner,
+    primitives::{Address, B256, ChainId, Signature},
+    signers::{self, Signer},
+};
+use async_trait::async_trait;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{error, warn},
+};
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// A signer wrapper that bounds every signing operation by a timeout, retrying operations that
+/// time out.
+///
+/// Remote signers such as AWS KMS can hang indefinitely. Bounding them ensures a fill attempt
+/// fails cleanly, before anything is sent, rather than stalling past the blocks it targets.
+/// Only timeouts are retried; errors returned by the inner signer are returned immediately.
+#[derive(Debug, Clone)]
+pub struct TimeoutSigner<S> {
+    /// The wrapped signer.
+    inner: S,
+    /// Maximum time for a single signing attempt.
+    timeout: Duration,
+    /// Number of additional attempts after a timeout.
+    retries: u32,
+}
+
+impl<S> TimeoutSigner<S> {
+    /// Wrap a signer, bounding each signing attempt by `timeout`, without retries.
+    pub const fn new(inner: S, timeout: Duration) -> Self {
+        Self {
+            inner,
+            timeout,
+            retries: 0,
+        }
+    }
+
+    /// Retry signing operations that time out, up to `retries` additional times.
+    pub const fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// The wrapped signer.
+    pub const fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Log and count a timed out attempt, returning the error to surface if no attempts are
+    /// left.
+    fn timed_out(&self, operation: &'static str, attempt: u32) -> Option<signers::Error> {
+        counter!("signer.timeouts", "operation" => operation).increment(1);
+        if attempt < self.retries {
+            warn!(operation, attempt, timeout = ?self.timeout, "Signing timed out; retrying");
+            return None;
+        }
+        error!(operation, attempts = attempt + 1, timeout = ?self.timeout, "Signing timed out");
+        Some(signers::Error::message(format!(
+            "{operation} timed out after {} attempts of {:?}",
+            attempt + 1,
+            self.timeout
+        )))
+    }
+}
+
+#[async_trait]
+impl<S> Signer for TimeoutSigner<S>
+where
+    S: Signer + Send + Sync,
+{
+    async fn sign_hash(&self, hash: &B256) -> signers::Result<Signature> {
+        let mut attempt = 0;
+        loop {
+            match timeout(self.timeout, self.inner.sign_hash(hash)).await {
+                Ok(result) => return result,
+                Err(_) => {
+                    if let Some(err) = self.timed_out("sign_hash", attempt) {
+                        return Err(err);
+                    }
+                }
+            }
+            attempt += 1;
+        }
+    }
+
+    fn address(&self) -> Address {
+        self.inner.address()
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.inner.chain_id()
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.inner.set_chain_id(chain_id);
+    }
+}
+
+#[async_trait]
+impl<S> TxSigner<Signature> for TimeoutSigner<S>
+where
+    S: TxSigner<Signature> + Send + Sync,
+{
+    fn address(&self) -> Address {
+        TxSigner::address(&self.inner)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> signers::Result<Signature> {
+        let mut attempt = 0;
+        loop {
+            match timeout(self.timeout, self.inner.sign_transaction(tx)).await {
+                Ok(result) => return result,
+                Err(_) => {
+                    if let Some(err) = self.timed_out("sign_transaction", attempt) {
+                        return Err(err);
+                    }
+                }
+            }
+            attempt += 1;
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{
+    approval::{IntentInput, IntentOutput, OrderIntent},
+    clock::chain_time,
+    contracts::permit2_nonce_used,
+    ids::OrderHash,
+    order::SendOrder,
+    provider::TxSenderProvider,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use chrono::Utc;
+use eyre::Error;
+use init4_bin_base::deps::tracing::{debug, info, instrument, warn};
+use std::time::Duration;
+
+/// A slice that has been submitted and is neither filled nor expired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct OutstandingSlice {
+    /// The Permit2 nonce the slice was signed with.
+    nonce: u64,
+    /// The deadline of the slice.
+    deadline: u64,
+}
+
+/// Fill progress of a [`TwapSlicer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwapProgress {
+    /// Number of slices the intent is split into.
+    pub slices: u64,
+    /// Number of slices filled.
+    pub filled: u64,
+    /// Number of slices submitted and awaiting fill.
+    pub outstanding: u64,
+}
+
+impl TwapProgress {
+    /// Whether every slice has been filled.
+    pub const fn is_complete(&self) -> bool {
+        self.filled >= self.slices
+    }
+}
+
+/// Splits a large [`OrderIntent`] into equal slices, submitted one at a time on an interval.
+///
+/// Each slice is an Order for `1 / slices` of every Input and Output of the intent, signed with
+/// a distinct Permit2 nonce. Fill progress is tracked by checking whether each slice's nonce
+/// has been used. Slices that expire unfilled are resubmitted, and slicing stops as soon as
+/// every slice has been filled.
+///
+/// Amounts are rounded down when sliced, so up to `slices - 1` units of each token may be left
+/// unfilled.
+#[derive(Debug)]
+pub struct TwapSlicer<S: Signer> {
+    /// Signs and sends the slices.
+    sender: SendOrder<S>,
+    /// Provider for the Rollup, used to check slice nonces and chain time.
+    ru_provider: TxSenderProvider,
+    /// The owner of the Orders, who signs the slices.
+    owner: Address,
+    /// The intent to slice. Its deadline is ignored.
+    intent: OrderIntent,
+    /// Number of slices.
+    slices: u64,
+    /// Lifetime of each slice, in seconds.
+    slice_ttl: u64,
+    /// Nonce for the next slice.
+    next_nonce: u64,
+    /// Number of slices filled.
+    filled: u64,
+    /// Slices awaiting fill.
+    outstanding: Vec<OutstandingSlice>,
+}
+
+impl<S> TwapSlicer<S>
+where
+    S: Signer,
+{
+    /// Create a new TwapSlicer splitting the intent into `slices` Orders, each live for
+    /// `slice_ttl`. Slices are signed by `owner`, through the `sender`.
+    pub fn new(
+        sender: SendOrder<S>,
+        ru_provider: TxSenderProvider,
+        owner: Address,
+        intent: OrderIntent,
+        slices: u64,
+        slice_ttl: Duration,
+    ) -> Result<Self, Error> {
+        if slices == 0 {
+            eyre::bail!("TWAP must have at least one slice");
+        }
+        Ok(Self {
+            sender,
+            ru_provider,
+            owner,
+            intent,
+            slices,
+            slice_ttl: slice_ttl.as_secs(),
+            // microseconds since the epoch, so nonces don't collide with earlier runs
+            next_nonce: Utc::now().timestamp_micros() as u64,
+            filled: 0,
+            outstanding: Vec::new(),
+        })
+    }
+
+    /// The current fill progress.
+    pub const fn progress(&self) -> TwapProgress {
+        TwapProgress {
+            slices: self.slices,
+            filled: self.filled,
+            outstanding: self.outstanding.len() as u64,
+        }
+    }
+
+    /// The Order for a single slice, live until `deadline`.
+    fn slice(&self, deadline: u64) -> OrderIntent {
+        let slices = U256::from(self.slices);
+        OrderIntent {
+            inputs: self
+                .intent
+                .inputs
+                .iter()
+                .map(|input| IntentInput {
+                    amount: input.amount / slices,
+                    ..*input
+                })
+                .collect(),
+            outputs: self
+                .intent
+                .outputs
+                .iter()
+                .map(|output| IntentOutput {
+                    amount: output.amount / slices,
+                    ..*output
+                })
+                .collect(),
+            deadline,
+        }
+    }
+
+    /// Update fill progress from the nonces of outstanding slices, dropping expired slices so
+    /// they are resubmitted.
+    async fn update_progress(&mut self, now: u64) {
+        let mut outstanding = Vec::with_capacity(self.outstanding.len());
+        for slice in std::mem::take(&mut self.outstanding) {
+            let used = permit2_nonce_used(&self.ru_provider, self.owner, U256::from(slice.nonce))
+                .await
+                // keep the slice outstanding, and check again on the next tick
+                .inspect_err(|err| warn!(%err, nonce = slice.nonce, "Failed to check slice nonce"))
+                .unwrap_or_default();
+            if used {
+                self.filled += 1;
+                debug!(nonce = slice.nonce, "Slice filled");
+            } else if slice.deadline < now {
+                debug!(nonce = slice.nonce, "Slice expired unfilled");
+            } else {
+                outstanding.push(slice);
+            }
+        }
+        self.outstanding = outstanding;
+    }
+
+    /// Update fill progress, then submit the next slice if any remain.
+    ///
+    /// Returns the progress after submitting.
+    #[instrument(skip(self))]
+    pub async fn tick(&mut self, now: u64) -> Result<TwapProgress, Error> {
+        self.update_progress(now).await;
+
+        let progress = self.progress();
+        if progress.filled + progress.outstanding >= self.slices {
+            return Ok(progress);
+        }
+
+        let nonce = self.next_nonce;
+        let deadline = now + self.slice_ttl;
+        let unsigned = self.slice(deadline).to_unsigned().with_nonce(nonce);
+        let signed = self.sender.sign_unsigned_order(unsigned).await?;
+        self.sender.send_order(signed.clone()).await?;
+
+        self.next_nonce += 1;
+        self.outstanding.push(OutstandingSlice { nonce, deadline });
+        info!(
+            order_hash = %OrderHash::from(&signed),
+            nonce,
+            deadline,
+            filled = self.filled,
+            slices = self.slices,
+            "TWAP slice submitted"
+        );
+
+        Ok(self.progress())
+    }
+
+    /// Submit slices on the given interval until every slice has been filled, using the Rollup
+    /// chain time as the reference clock for deadlines.
+    pub async fn run(&mut self, interval: Duration) -> Result<TwapProgress, Error> {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            let now = chain_time(&self.ru_provider).await?;
+            let progress = self.tick(now).await?;
+            if progress.is_complete() {
+                info!(slices = self.slices, "TWAP complete");
+                return Ok(progress);
+            }
+        }
+    }
+}
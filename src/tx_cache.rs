@@ -0,0 +1,92 @@
+use init4_bin_base::deps::{metrics::counter, tracing::warn};
+use reqwest::StatusCode;
+use std::{error::Error as StdError, fmt};
+
+/// The kind of a transaction cache failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TxCacheErrorKind {
+    /// The cache rejected the request (4xx), e.g. an invalid Order or Bundle. Retrying the same
+    /// request will not help.
+    Rejected,
+    /// The cache failed to handle the request (5xx). The request may succeed if retried.
+    Unavailable,
+    /// The request failed before a response was received, or the response could not be read.
+    Transport,
+}
+
+impl TxCacheErrorKind {
+    /// The kind as a string, used as a metric label.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rejected => "rejected",
+            Self::Unavailable => "unavailable",
+            Self::Transport => "transport",
+        }
+    }
+}
+
+impl fmt::Display for TxCacheErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A failed transaction cache request, classified by whether the cache rejected it or could
+/// not be reached.
+///
+/// Wrapped in an [`eyre::Report`]; recover it with `report.downcast_ref::<TxCacheError>()`.
+#[derive(Debug)]
+pub struct TxCacheError {
+    /// The operation that failed, e.g. `forward_bundle`.
+    pub operation: &'static str,
+    /// The kind of failure.
+    pub kind: TxCacheErrorKind,
+    /// The HTTP status returned by the cache, if a response was received.
+    pub status: Option<StatusCode>,
+    /// The underlying error from the transaction cache client.
+    source: eyre::Report,
+}
+
+impl TxCacheError {
+    /// Classify an error returned by the transaction cache client, counting it in metrics.
+    ///
+    /// The client reports failures as opaque errors; the HTTP status is recovered from the
+    /// underlying [`reqwest::Error`], when there is one.
+    pub fn classify(operation: &'static str, source: eyre::Report) -> Self {
+        let status = source
+            .chain()
+            .find_map(|err| err.downcast_ref::<reqwest::Error>())
+            .and_then(reqwest::Error::status);
+        let kind = match status {
+            Some(status) if status.is_client_error() => TxCacheErrorKind::Rejected,
+            Some(status) if status.is_server_error() => TxCacheErrorKind::Unavailable,
+            _ => TxCacheErrorKind::Transport,
+        };
+
+        counter!("tx_cache.errors", "operation" => operation, "kind" => kind.as_str()).increment(1);
+        warn!(operation, %kind, ?status, error = %source, "Transaction cache request failed");
+
+        Self {
+            operation,
+            kind,
+            status,
+            source,
+        }
+    }
+}
+
+impl fmt::Display for TxCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transaction cache {} {}", self.operation, self.kind)?;
+        if let Some(status) = self.status {
+            write!(f, " ({status})")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl StdError for TxCacheError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
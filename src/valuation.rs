@@ -0,0 +1,198 @@
+use crate::{
+    analytics::ChainToken, clock::chain_time, contracts::IERC20, provider::TxSenderProvider,
+    quoting::PriceOracle,
+};
+use alloy::primitives::{Address, U256};
+use eyre::Error;
+use init4_bin_base::deps::{
+    metrics::gauge,
+    tracing::{debug, error, instrument},
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// Default number of snapshots kept in the history.
+const DEFAULT_HISTORY_LEN: usize = 1_440;
+
+/// A token balance held by the signer, and its value in the numéraire.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Holding {
+    /// Name of the chain the token is held on.
+    pub chain: &'static str,
+    /// The token, and the chain id it is held on.
+    pub token: ChainToken,
+    /// The balance, in raw token units.
+    pub balance: U256,
+    /// The value of the balance, in raw numéraire units.
+    pub value: f64,
+}
+
+/// A point-in-time valuation of the signer's inventory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventorySnapshot {
+    /// The chain time at which the snapshot was taken, in seconds.
+    pub timestamp: u64,
+    /// Each valued holding.
+    pub holdings: Vec<Holding>,
+}
+
+impl InventorySnapshot {
+    /// The total value of the inventory, in raw numéraire units.
+    pub fn total_value(&self) -> f64 {
+        self.holdings.iter().map(|holding| holding.value).sum()
+    }
+}
+
+/// Recent inventory snapshots, oldest first, shared with the task taking them.
+pub type InventoryHistory = Arc<Mutex<VecDeque<InventorySnapshot>>>;
+
+/// A token watched by the [`InventoryValuer`].
+#[derive(Debug, Clone)]
+struct ValuedToken {
+    /// Name of the chain, used as a metric label.
+    chain: &'static str,
+    /// Provider for the chain.
+    provider: TxSenderProvider,
+    /// The token, and the chain id it is held on.
+    token: ChainToken,
+}
+
+/// Periodically values the signer's token inventory in a numéraire token, using a
+/// [`PriceOracle`], so operators can see capital utilization over time.
+///
+/// Values are exported as the `inventory.value` gauge, labelled by `chain` and `token`, and
+/// the total as the `inventory.total_value` gauge. Recent snapshots are also kept in memory,
+/// and can be read through [`InventoryValuer::history`].
+#[derive(Debug)]
+pub struct InventoryValuer<O> {
+    /// The address whose inventory is valued.
+    owner: Address,
+    /// The token inventory is valued in.
+    numeraire: ChainToken,
+    /// The source of prices.
+    oracle: O,
+    /// Provider for the Rollup, used for chain time.
+    ru_provider: TxSenderProvider,
+    /// The tokens to value.
+    tokens: Vec<ValuedToken>,
+    /// Recent snapshots.
+    history: InventoryHistory,
+    /// Maximum number of snapshots kept in the history.
+    history_len: usize,
+}
+
+impl<O> InventoryValuer<O>
+where
+    O: PriceOracle,
+{
+    /// Create a new InventoryValuer for the owner's inventory, valued in `numeraire`.
+    pub fn new(
+        owner: Address,
+        numeraire: ChainToken,
+        oracle: O,
+        ru_provider: TxSenderProvider,
+    ) -> Self {
+        Self {
+            owner,
+            numeraire,
+            oracle,
+            ru_provider,
+            tokens: Vec::new(),
+            history: Arc::default(),
+            history_len: DEFAULT_HISTORY_LEN,
+        }
+    }
+
+    /// Value an ERC20 token held on a chain.
+    pub fn watch_token(
+        mut self,
+        chain: &'static str,
+        provider: TxSenderProvider,
+        token: ChainToken,
+    ) -> Self {
+        self.tokens.push(ValuedToken {
+            chain,
+            provider,
+            token,
+        });
+        self
+    }
+
+    /// Keep at most `len` snapshots in the history.
+    pub const fn with_history_len(mut self, len: usize) -> Self {
+        self.history_len = len;
+        self
+    }
+
+    /// A handle to the recent snapshots, oldest first.
+    pub fn history(&self) -> InventoryHistory {
+        self.history.clone()
+    }
+
+    /// Value every watched token once, exporting gauges and recording the snapshot.
+    #[instrument(skip_all, fields(owner = %self.owner))]
+    pub async fn snapshot(&self) -> Result<InventorySnapshot, Error> {
+        let timestamp = chain_time(&self.ru_provider).await?;
+        let mut holdings = Vec::with_capacity(self.tokens.len());
+        for valued in &self.tokens {
+            let balance = IERC20::new(valued.token.1, &valued.provider)
+                .balanceOf(self.owner)
+                .call()
+                .await?;
+            let price = if valued.token == self.numeraire {
+                1.0
+            } else {
+                self.oracle.price(valued.token, self.numeraire).await?
+            };
+            let value = f64::from(balance) * price;
+            debug!(chain = valued.chain, token = %valued.token.1, %balance, value, "Valued holding");
+            gauge!(
+                "inventory.value",
+                "chain" => valued.chain,
+                "token" => valued.token.1.to_string()
+            )
+            .set(value);
+            holdings.push(Holding {
+                chain: valued.chain,
+                token: valued.token,
+                balance,
+                value,
+            });
+        }
+
+        let snapshot = InventorySnapshot {
+            timestamp,
+            holdings,
+        };
+        gauge!("inventory.total_value").set(snapshot.total_value());
+
+        let mut history = self.history.lock().unwrap();
+        history.push_back(snapshot.clone());
+        while history.len() > self.history_len {
+            history.pop_front();
+        }
+        Ok(snapshot)
+    }
+}
+
+impl<O> InventoryValuer<O>
+where
+    O: PriceOracle + Send + Sync + 'static,
+{
+    /// Spawn a task that snapshots inventory on the given interval, forever.
+    pub fn spawn(self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                if let Err(err) = self.snapshot().await {
+                    error!(%err, "Failed to value inventory");
+                }
+            }
+        })
+    }
+}
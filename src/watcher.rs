@@ -0,0 +1,141 @@
+use crate::{
+    clock::chain_time, ids::OrderHash, provider::TxSenderProvider, tx_cache::TxCacheError,
+};
+use eyre::Result;
+use init4_bin_base::deps::{
+    metrics::counter,
+    tracing::{debug, error},
+};
+use signet_constants::SignetConstants;
+use signet_tx_cache::client::TxCache;
+use signet_types::SignedOrder;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+/// Default number of new Orders buffered for the consumer.
+const DEFAULT_CAPACITY: usize = 256;
+
+/// Watches the transaction cache for new Orders, so Fillers subscribe to Order flow instead of
+/// each running their own polling loop.
+///
+/// The transaction cache is polled on an interval, and each Order not seen before is sent on a
+/// [`mpsc`] channel, once. Seen Orders are remembered, by hash, until their deadline passes in
+/// Rollup chain time, or until the consumer forgets them, e.g. to retry a failed fill. When
+/// the channel is full, polling waits for the consumer to catch up. New Orders increment the
+/// `watcher.new_orders` counter, and failed polls the `watcher.fetch_failures` counter.
+///
+/// Clones share the Orders seen, so the consumer can keep a clone to forget Orders while the
+/// Watcher runs.
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    /// The transaction cache.
+    tx_cache: Arc<TxCache>,
+    /// The Rollup provider, for chain time.
+    ru_provider: TxSenderProvider,
+    /// Interval between polls.
+    interval: Duration,
+    /// Number of new Orders buffered for the consumer.
+    capacity: usize,
+    /// Deadlines of the Orders already sent, by hash.
+    seen: Arc<Mutex<HashMap<OrderHash, u64>>>,
+}
+
+impl Watcher {
+    /// Create a new Watcher polling the transaction cache on the given interval.
+    pub fn new(tx_cache: TxCache, ru_provider: TxSenderProvider, interval: Duration) -> Self {
+        Self {
+            tx_cache: Arc::new(tx_cache),
+            ru_provider,
+            interval,
+            capacity: DEFAULT_CAPACITY,
+            seen: Arc::default(),
+        }
+    }
+
+    /// Create a new Watcher polling the transaction cache of the given environment.
+    pub fn from_constants(
+        constants: &SignetConstants,
+        ru_provider: TxSenderProvider,
+        interval: Duration,
+    ) -> Result<Self> {
+        let tx_cache_url: reqwest::Url = constants.environment().transaction_cache().parse()?;
+        let client = reqwest::ClientBuilder::new().use_rustls_tls().build()?;
+        Ok(Self::new(
+            TxCache::new_with_client(tx_cache_url, client),
+            ru_provider,
+            interval,
+        ))
+    }
+
+    /// Set the number of new Orders buffered for the consumer. Must be non-zero.
+    pub const fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Forget that an Order was seen, so it is sent again if it is still in the transaction
+    /// cache at the next poll.
+    pub fn forget(&self, order_hash: OrderHash) {
+        self.seen.lock().unwrap().remove(&order_hash);
+    }
+
+    /// Poll the transaction cache once, returning the Orders not seen before and remembering
+    /// them. Orders whose deadline has passed at chain time `now` are forgotten.
+    pub async fn poll(&self, now: u64) -> Result<Vec<SignedOrder>> {
+        let orders = self
+            .tx_cache
+            .get_orders()
+            .await
+            .map_err(|err| TxCacheError::classify("get_orders", err))?;
+
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, deadline| *deadline >= now);
+        let new: Vec<SignedOrder> = orders
+            .into_iter()
+            .filter(|order| {
+                let deadline = u64::try_from(order.permit.permit.deadline).unwrap_or(u64::MAX);
+                seen.insert(OrderHash::from(order), deadline).is_none()
+            })
+            .collect();
+        counter!("watcher.new_orders").increment(new.len() as u64);
+        Ok(new)
+    }
+
+    /// Poll the transaction cache once, at the current Rollup chain time.
+    async fn poll_chain_time(&self) -> Result<Vec<SignedOrder>> {
+        let now = chain_time(&self.ru_provider).await?;
+        self.poll(now).await
+    }
+
+    /// Spawn a task polling forever, returning the channel new Orders are sent on. The task
+    /// stops when the receiver is dropped.
+    pub fn spawn(self) -> (mpsc::Receiver<SignedOrder>, JoinHandle<()>) {
+        let (sender, receiver) = mpsc::channel(self.capacity.max(1));
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                let orders = match self.poll_chain_time().await {
+                    Ok(orders) => orders,
+                    Err(err) => {
+                        error!(%err, "Failed to poll for orders");
+                        counter!("watcher.fetch_failures").increment(1);
+                        continue;
+                    }
+                };
+                debug!(orders_count = orders.len(), "Watched new orders");
+                for order in orders {
+                    // waits while the channel is full
+                    if sender.send(order).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        (receiver, handle)
+    }
+}
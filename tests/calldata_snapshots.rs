@@ -0,0 +1,70 @@
+//! Golden-file snapshots of the exact fill and initiate calldata produced for representative
+//! Orders, so changes to fill planning can't silently change onchain behavior.
+
+mod common;
+
+use alloy::rpc::types::TransactionRequest;
+use common::{
+    assert_snapshot, constants, ru_to_host_order, ru_to_ru_order, sign_fills, sign_order, signer,
+};
+use orders::filler::plan::{host_txn_requests, rollup_txn_requests};
+use signet_types::SignedOrder;
+use std::fmt::Write;
+
+/// Plan the fill of the Orders, and render the Rollup and Host transactions.
+async fn render_plan(orders: &[SignedOrder]) -> String {
+    let constants = constants();
+    let filler = signer().address();
+    let signed_fills = sign_fills(&constants, orders, 1).await;
+
+    let ru_txs = rollup_txn_requests(&constants, filler, &signed_fills, orders);
+    let host_txs = host_txn_requests(&constants, &signed_fills);
+
+    let mut out = String::new();
+    render_txs(&mut out, "rollup", &ru_txs);
+    render_txs(&mut out, "host", &host_txs);
+    out
+}
+
+/// Render the destination and calldata of each transaction, one per line.
+fn render_txs(out: &mut String, chain: &str, txs: &[TransactionRequest]) {
+    for tx in txs {
+        let to = tx
+            .to
+            .and_then(|kind| kind.to().copied())
+            .map(|to| to.to_string())
+            .unwrap_or_default();
+        let input = tx.input.input().cloned().unwrap_or_default();
+        writeln!(out, "{chain} to={to} input={input}").unwrap();
+    }
+}
+
+#[tokio::test]
+async fn ru_to_host_fill() {
+    let constants = constants();
+    let recipient = signer().address();
+    let order = sign_order(&constants, ru_to_host_order(&constants, recipient), 1).await;
+
+    assert_snapshot("ru_to_host_fill", &render_plan(&[order]).await);
+}
+
+#[tokio::test]
+async fn ru_to_ru_fill() {
+    let constants = constants();
+    let recipient = signer().address();
+    let order = sign_order(&constants, ru_to_ru_order(&constants, recipient), 1).await;
+
+    assert_snapshot("ru_to_ru_fill", &render_plan(&[order]).await);
+}
+
+#[tokio::test]
+async fn aggregate_fill() {
+    let constants = constants();
+    let recipient = signer().address();
+    let orders = [
+        sign_order(&constants, ru_to_host_order(&constants, recipient), 1).await,
+        sign_order(&constants, ru_to_ru_order(&constants, recipient), 2).await,
+    ];
+
+    assert_snapshot("aggregate_fill", &render_plan(&orders).await);
+}
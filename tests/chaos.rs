@@ -0,0 +1,58 @@
+//! Failure handling under injected faults. Run with `cargo test --features chaos`.
+
+#![cfg(feature = "chaos")]
+
+mod common;
+
+use alloy::{primitives::B256, signers::Signer};
+use common::signer;
+use orders::{
+    chaos::{ChaosSigner, Faults},
+    timeout_signer::TimeoutSigner,
+};
+use std::time::Duration;
+
+/// Record which of `calls` injections fail.
+async fn failures(faults: &Faults, calls: usize) -> Vec<bool> {
+    let mut failed = Vec::with_capacity(calls);
+    for _ in 0..calls {
+        failed.push(faults.inject("test").await.is_err());
+    }
+    failed
+}
+
+#[tokio::test]
+async fn faults_are_reproducible() {
+    let first = failures(&Faults::new(7).with_failures(0.5), 64).await;
+    let second = failures(&Faults::new(7).with_failures(0.5), 64).await;
+
+    assert_eq!(first, second);
+    assert!(first.contains(&true) && first.contains(&false));
+}
+
+#[tokio::test]
+async fn failing_signer_errors() {
+    let signer = ChaosSigner::new(signer(), Faults::new(1).with_failures(1.0));
+
+    assert!(signer.sign_hash(&B256::ZERO).await.is_err());
+}
+
+#[tokio::test]
+async fn unfaulted_signer_signs() {
+    let signer = ChaosSigner::new(signer(), Faults::new(1));
+
+    assert!(signer.sign_hash(&B256::ZERO).await.is_ok());
+}
+
+#[tokio::test]
+async fn stalled_signer_times_out_after_retries() {
+    let faults = Faults::new(1).with_delays(1.0, Duration::from_secs(3_600));
+    let signer = TimeoutSigner::new(
+        ChaosSigner::new(signer(), faults),
+        Duration::from_millis(10),
+    )
+    .with_retries(2);
+
+    let err = signer.sign_hash(&B256::ZERO).await.unwrap_err();
+    assert!(err.to_string().contains("timed out after 3 attempts"));
+}
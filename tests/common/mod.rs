@@ -0,0 +1,173 @@
+//! Shared fixtures for integration tests: a deterministic signer, constants, and
+//! representative Orders.
+
+#![allow(dead_code)]
+
+use alloy::{
+    consensus::constants::GWEI_TO_WEI,
+    network::EthereumWallet,
+    primitives::{Address, U256},
+    providers::ProviderBuilder,
+    rpc::types::{Block, Header},
+    signers::local::PrivateKeySigner,
+    transports::mock::Asserter,
+};
+use orders::{constants::resolve_constants, filler::Filler, provider::TxSenderProvider};
+use signet_constants::SignetConstants;
+use signet_types::{AggregateOrders, SignedFill, SignedOrder, UnsignedFill, UnsignedOrder};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+/// Well-known test key (the first default anvil account). Never fund this key.
+pub const SIGNER_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Well-known test key for the Filler (the second default anvil account), distinct from the
+/// fixture key so fixture Orders are not self-trades. Never fund this key.
+pub const FILLER_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+/// Fixed deadline for all fixtures, so signatures are deterministic.
+pub const DEADLINE: u64 = 1_900_000_000;
+
+/// Chain time of every mocked block, an hour before the fixture deadline.
+pub const CHAIN_TIME: u64 = DEADLINE - 3_600;
+
+/// Deterministic signer for all fixtures.
+pub fn signer() -> PrivateKeySigner {
+    SIGNER_KEY.parse().unwrap()
+}
+
+/// Deterministic signer for the Filler.
+pub fn filler_signer() -> PrivateKeySigner {
+    FILLER_KEY.parse().unwrap()
+}
+
+/// A latest block at [`CHAIN_TIME`], as a mocked RPC response.
+pub fn latest_block() -> Block {
+    Block::empty(Header::new(alloy::consensus::Header {
+        number: 1,
+        timestamp: CHAIN_TIME,
+        ..Default::default()
+    }))
+}
+
+/// A provider answering every RPC request, in order, with the next response pushed to
+/// `asserter`, and erroring once none are left.
+pub fn mocked_provider(asserter: Asserter) -> TxSenderProvider {
+    ProviderBuilder::new()
+        .wallet(EthereumWallet::from(filler_signer()))
+        .connect_mocked_client(asserter)
+}
+
+/// A Filler whose Rollup and Host RPCs are mocked by the given asserters.
+pub fn mocked_filler(ru: Asserter, host: Asserter) -> Filler<PrivateKeySigner> {
+    Filler::new(
+        filler_signer(),
+        mocked_provider(ru),
+        mocked_provider(host),
+        constants(),
+    )
+    .unwrap()
+}
+
+/// A fresh path in the system temp dir, unique to this test process.
+pub fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("signet-orders-{}-{name}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+/// Constants for all fixtures.
+pub fn constants() -> SignetConstants {
+    resolve_constants("pecorino").unwrap()
+}
+
+/// An unsigned Order swapping Rollup WETH for Host WETH.
+pub fn ru_to_host_order(constants: &SignetConstants, recipient: Address) -> UnsignedOrder<'static> {
+    UnsignedOrder::default()
+        .with_input(constants.rollup().tokens().weth(), U256::from(GWEI_TO_WEI))
+        .with_output(
+            constants.host().tokens().weth(),
+            U256::from(GWEI_TO_WEI),
+            recipient,
+            constants.host().chain_id() as u32,
+        )
+        .with_deadline(DEADLINE)
+}
+
+/// An unsigned Order swapping Rollup WETH for Rollup WETH.
+pub fn ru_to_ru_order(constants: &SignetConstants, recipient: Address) -> UnsignedOrder<'static> {
+    UnsignedOrder::default()
+        .with_input(constants.rollup().tokens().weth(), U256::from(GWEI_TO_WEI))
+        .with_output(
+            constants.rollup().tokens().weth(),
+            U256::from(GWEI_TO_WEI),
+            recipient,
+            constants.rollup().chain_id() as u32,
+        )
+        .with_deadline(DEADLINE)
+}
+
+/// Sign an Order with the fixture signer and a fixed nonce.
+pub async fn sign_order(
+    constants: &SignetConstants,
+    unsigned: UnsignedOrder<'_>,
+    nonce: u64,
+) -> SignedOrder {
+    unsigned
+        .with_nonce(nonce)
+        .with_chain(constants.system())
+        .sign(&signer())
+        .await
+        .unwrap()
+}
+
+/// Sign an aggregate Fill of the Orders with the fixture signer and a fixed nonce.
+pub async fn sign_fills(
+    constants: &SignetConstants,
+    orders: &[SignedOrder],
+    nonce: u64,
+) -> HashMap<u64, SignedFill> {
+    let agg: AggregateOrders = orders.iter().collect();
+    UnsignedFill::from(&agg)
+        .with_nonce(nonce)
+        .with_deadline(DEADLINE)
+        .with_ru_chain_id(constants.rollup().chain_id())
+        .with_chain(constants.system().clone())
+        .sign(&signer())
+        .await
+        .unwrap()
+}
+
+/// Compare `actual` against the named golden file in `tests/snapshots`.
+///
+/// A missing golden file fails the test. Set `UPDATE_SNAPSHOTS=1` to write new golden files,
+/// or overwrite existing ones after an intentional change; review and commit them.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    assert_golden("tests/snapshots", &format!("{name}.snap"), actual);
+}
+
+/// Compare `actual` against the named test vector in `tests/vectors`.
+///
+/// Vectors are golden files like snapshots, and are updated the same way.
+pub fn assert_vector(name: &str, actual: &str) {
+    assert_golden("tests/vectors", &format!("{name}.json"), actual);
+}
+
+/// Compare `actual` against the golden file `file` in `dir`, relative to the crate root.
+fn assert_golden(dir: &str, file: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(dir)
+        .join(file);
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    if update {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("golden file {file} is missing; run with UPDATE_SNAPSHOTS=1 to write it")
+    });
+    assert_eq!(
+        expected, actual,
+        "golden file {file} changed; re-run with UPDATE_SNAPSHOTS=1 to accept"
+    );
+}
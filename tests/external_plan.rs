@@ -0,0 +1,132 @@
+//! Validation of `ExternalPlan`s: each Order is filled at most once per chain, Rollup Fills
+//! precede Initiates, and Orders with Host Outputs are filled on the Host.
+
+mod common;
+
+use common::{constants, ru_to_host_order, ru_to_ru_order, sign_order, signer};
+use orders::{
+    filler::{ExternalPlan, PlanAction, PlanChain},
+    ids::OrderHash,
+};
+use signet_types::SignedOrder;
+
+fn fill(chain: PlanChain, orders: &[&SignedOrder]) -> PlanAction {
+    PlanAction::Fill {
+        chain,
+        orders: orders.iter().map(|order| OrderHash::from(*order)).collect(),
+    }
+}
+
+fn initiate(order: &SignedOrder) -> PlanAction {
+    PlanAction::Initiate {
+        order: OrderHash::from(order),
+    }
+}
+
+/// Validate a plan of the Orders and actions against the fixture Rollup.
+fn validate(orders: &[&SignedOrder], actions: Vec<PlanAction>) -> Result<(), String> {
+    ExternalPlan {
+        orders: orders.iter().map(|order| (*order).clone()).collect(),
+        actions,
+    }
+    .validate(constants().rollup().chain_id())
+    .map_err(|err| err.to_string())
+}
+
+#[tokio::test]
+async fn complete_plans_are_valid() {
+    let constants = constants();
+    let recipient = signer().address();
+    let to_host = sign_order(&constants, ru_to_host_order(&constants, recipient), 0).await;
+    let to_ru = sign_order(&constants, ru_to_ru_order(&constants, recipient), 1).await;
+
+    validate(
+        &[&to_host, &to_ru],
+        vec![
+            fill(PlanChain::Rollup, &[&to_ru]),
+            initiate(&to_host),
+            initiate(&to_ru),
+            fill(PlanChain::Host, &[&to_host]),
+        ],
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn host_outputs_require_a_host_fill() {
+    let constants = constants();
+    let order = sign_order(
+        &constants,
+        ru_to_host_order(&constants, signer().address()),
+        0,
+    )
+    .await;
+
+    let err = validate(&[&order], vec![initiate(&order)]).unwrap_err();
+    assert_eq!(
+        err,
+        format!(
+            "order {} has host outputs but no host fill",
+            OrderHash::from(&order)
+        )
+    );
+
+    // a Rollup Fill does not deliver Host Outputs
+    let err = validate(
+        &[&order],
+        vec![fill(PlanChain::Rollup, &[&order]), initiate(&order)],
+    )
+    .unwrap_err();
+    assert!(err.contains("no host fill"), "{err}");
+}
+
+#[tokio::test]
+async fn duplicate_fills_on_a_chain_are_rejected() {
+    let constants = constants();
+    let recipient = signer().address();
+    let to_host = sign_order(&constants, ru_to_host_order(&constants, recipient), 0).await;
+    let to_ru = sign_order(&constants, ru_to_ru_order(&constants, recipient), 1).await;
+
+    let err = validate(
+        &[&to_host],
+        vec![
+            fill(PlanChain::Host, &[&to_host]),
+            fill(PlanChain::Host, &[&to_host]),
+            initiate(&to_host),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        format!(
+            "order {} is filled more than once on the Host",
+            OrderHash::from(&to_host)
+        )
+    );
+
+    // within a single Fill, too
+    let err = validate(
+        &[&to_ru],
+        vec![fill(PlanChain::Rollup, &[&to_ru, &to_ru]), initiate(&to_ru)],
+    )
+    .unwrap_err();
+    assert!(err.contains("filled more than once on the Rollup"), "{err}");
+}
+
+#[tokio::test]
+async fn rollup_fill_must_precede_initiate() {
+    let constants = constants();
+    let order = sign_order(
+        &constants,
+        ru_to_ru_order(&constants, signer().address()),
+        0,
+    )
+    .await;
+
+    let err = validate(
+        &[&order],
+        vec![initiate(&order), fill(PlanChain::Rollup, &[&order])],
+    )
+    .unwrap_err();
+    assert!(err.contains("before its rollup fill"), "{err}");
+}
@@ -0,0 +1,203 @@
+//! Screening in `Filler::fill` and `Filler::submit_plan`: Orders screened out of a batch are
+//! skipped rather than failing it, so one unfillable Order never stops the Orders after it from
+//! being filled, while a plan including a screened-out Order is refused outright.
+//!
+//! The Filler's RPCs are mocked with only the responses screening needs, so an Order that
+//! passes screening goes on to be filled and fails at the first RPC request after it, which
+//! `Filler::fill_individually` logs before moving on to the next Order.
+
+mod common;
+
+use alloy::transports::mock::Asserter;
+use common::{
+    CHAIN_TIME, constants, latest_block, mocked_filler, ru_to_ru_order, sign_order, signer,
+    temp_path,
+};
+use orders::{
+    compliance::{ComplianceEvent, ComplianceLog, Decision},
+    filler::{ExternalPlan, OrderShard, PlanAction},
+    ids::OrderHash,
+};
+use signet_types::SignedOrder;
+use std::path::Path;
+
+/// The decision and skip reason recorded for each Order in the compliance log, in order.
+fn decisions(path: &Path) -> Vec<(OrderHash, Decision, Option<String>)> {
+    ComplianceLog::verify(path)
+        .unwrap()
+        .into_iter()
+        .filter_map(|record| match record.entry.event {
+            ComplianceEvent::OrderConsidered {
+                order_hash,
+                decision,
+                reason,
+                ..
+            } => Some((order_hash, decision, reason)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn invalid_order_does_not_stop_individual_fills() {
+    let constants = constants();
+    let recipient = signer().address();
+    let expired = sign_order(
+        &constants,
+        ru_to_ru_order(&constants, recipient).with_deadline(CHAIN_TIME - 1),
+        0,
+    )
+    .await;
+    let valid = sign_order(&constants, ru_to_ru_order(&constants, recipient), 1).await;
+
+    // chain time for each of the two fills, and nothing after
+    let ru = Asserter::new();
+    ru.push_success(&latest_block());
+    ru.push_success(&latest_block());
+    let log = temp_path("invalid-order-compliance.jsonl");
+    let filler = mocked_filler(ru.clone(), Asserter::new())
+        .with_compliance_log(Some(ComplianceLog::open(&log).unwrap()));
+
+    let bundle_ids = filler
+        .fill_individually(&[expired.clone(), valid.clone()])
+        .await
+        .unwrap();
+
+    // the valid Order was screened in, though its fill failed against the mocked RPCs
+    assert!(bundle_ids.is_empty());
+    assert!(ru.read_q().is_empty());
+    assert_eq!(
+        decisions(&log),
+        vec![
+            (
+                OrderHash::from(&expired),
+                Decision::Skipped,
+                Some("invalid, expiring, or self-trade".to_string()),
+            ),
+            (OrderHash::from(&valid), Decision::Accepted, None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn failed_fill_does_not_stop_individual_fills() {
+    let constants = constants();
+    let recipient = signer().address();
+    let first = sign_order(&constants, ru_to_ru_order(&constants, recipient), 0).await;
+    let second = sign_order(&constants, ru_to_ru_order(&constants, recipient), 1).await;
+
+    // chain time for the first fill and a failure after it, then chain time for the second
+    let ru = Asserter::new();
+    ru.push_success(&latest_block());
+    ru.push_failure_msg("rpc unavailable");
+    ru.push_success(&latest_block());
+    let log = temp_path("failed-fill-compliance.jsonl");
+    let filler = mocked_filler(ru.clone(), Asserter::new())
+        .with_compliance_log(Some(ComplianceLog::open(&log).unwrap()));
+
+    let bundle_ids = filler
+        .fill_individually(&[first.clone(), second.clone()])
+        .await
+        .unwrap();
+
+    // the first fill failed, and the second Order was still screened in
+    assert!(bundle_ids.is_empty());
+    assert!(ru.read_q().is_empty());
+    assert_eq!(
+        decisions(&log),
+        vec![
+            (OrderHash::from(&first), Decision::Accepted, None),
+            (OrderHash::from(&second), Decision::Accepted, None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn screened_out_batch_fills_nothing() {
+    let constants = constants();
+    let expired = sign_order(
+        &constants,
+        ru_to_ru_order(&constants, signer().address()).with_deadline(CHAIN_TIME - 1),
+        0,
+    )
+    .await;
+
+    let ru = Asserter::new();
+    ru.push_success(&latest_block());
+    let filler = mocked_filler(ru, Asserter::new());
+
+    assert!(filler.fill(&[expired]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn other_shards_do_not_stop_individual_fills() {
+    let constants = constants();
+    let recipient = signer().address();
+    let shard = OrderShard::new(0, 2).unwrap();
+    let mut orders = Vec::new();
+    for nonce in 0..8 {
+        orders.push(sign_order(&constants, ru_to_ru_order(&constants, recipient), nonce).await);
+    }
+    let (owned, others): (Vec<_>, Vec<_>) = orders
+        .into_iter()
+        .partition(|order| shard.owns(OrderHash::from(order)));
+    assert!(!owned.is_empty() && !others.is_empty());
+
+    // Orders in the other shard first, then one in this shard
+    let batch: Vec<SignedOrder> = others.iter().chain(&owned[..1]).cloned().collect();
+    let ru = Asserter::new();
+    for _ in &batch {
+        ru.push_success(&latest_block());
+    }
+    let log = temp_path("other-shards-compliance.jsonl");
+    let filler = mocked_filler(ru.clone(), Asserter::new())
+        .with_order_shard(Some(shard))
+        .with_compliance_log(Some(ComplianceLog::open(&log).unwrap()));
+
+    let bundle_ids = filler.fill_individually(&batch).await.unwrap();
+
+    // the Order in this shard was screened in, though its fill failed against the mocked RPCs
+    assert!(bundle_ids.is_empty());
+    assert!(ru.read_q().is_empty());
+    let mut expected: Vec<_> = others
+        .iter()
+        .map(|order| {
+            (
+                OrderHash::from(order),
+                Decision::Skipped,
+                Some("in another shard".to_string()),
+            )
+        })
+        .collect();
+    expected.push((OrderHash::from(&owned[0]), Decision::Accepted, None));
+    assert_eq!(decisions(&log), expected);
+}
+
+#[tokio::test]
+async fn plan_with_screened_out_order_is_refused() {
+    let constants = constants();
+    let recipient = signer().address();
+    let shard = OrderShard::new(0, 2).unwrap();
+    let mut orders = Vec::new();
+    for nonce in 0..8 {
+        orders.push(sign_order(&constants, ru_to_ru_order(&constants, recipient), nonce).await);
+    }
+    let other = orders
+        .into_iter()
+        .find(|order| !shard.owns(OrderHash::from(order)))
+        .unwrap();
+    let plan = ExternalPlan {
+        orders: vec![other.clone()],
+        actions: vec![PlanAction::Initiate {
+            order: OrderHash::from(&other),
+        }],
+    };
+
+    // chain time for screening, and nothing after
+    let ru = Asserter::new();
+    ru.push_success(&latest_block());
+    let filler = mocked_filler(ru, Asserter::new()).with_order_shard(Some(shard));
+
+    let err = filler.submit_plan(&plan).await.unwrap_err();
+    assert!(err.to_string().contains("in another shard"), "{err}");
+}
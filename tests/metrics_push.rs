@@ -0,0 +1,89 @@
+//! Pushing metrics to a Prometheus Pushgateway, mocked by a local HTTP server recording the
+//! body of each request.
+
+use init4_bin_base::deps::metrics::counter;
+use orders::metrics_push::MetricsPushConfig;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+    sync::mpsc,
+};
+
+/// Serve HTTP requests on a local port, sending each request line and body over the channel.
+async fn pushgateway() -> (String, mpsc::UnboundedReceiver<(String, String)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/metrics/job/test", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                loop {
+                    let mut request_line = String::new();
+                    if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+                    let mut content_length = 0;
+                    loop {
+                        let mut header = String::new();
+                        stream.read_line(&mut header).await.unwrap();
+                        let header = header.trim_end();
+                        if header.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = header.split_once(':')
+                            && name.eq_ignore_ascii_case("content-length")
+                        {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    stream.read_exact(&mut body).await.unwrap();
+                    // record the push before responding, so it's seen once the push returns
+                    let request = request_line.trim_end().to_string();
+                    let _ = tx.send((request, String::from_utf8(body).unwrap()));
+                    stream
+                        .get_mut()
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                        .await
+                        .unwrap();
+                }
+            });
+        }
+    });
+    (url, rx)
+}
+
+#[tokio::test]
+async fn final_metrics_are_pushed_on_finish() {
+    let (url, mut pushes) = pushgateway().await;
+    let config = MetricsPushConfig {
+        url: Some(url),
+        interval_ms: Some(3_600_000),
+    };
+
+    let pusher = config.install().unwrap().unwrap();
+    counter!("push_test.fills").increment(3);
+    pusher.finish().await.unwrap();
+
+    // the interval pushes once on start, and finishing pushes the final metrics
+    let mut last = None;
+    while let Ok(push) = pushes.try_recv() {
+        last = Some(push);
+    }
+    let (request, body) = last.unwrap();
+    assert_eq!(request, "PUT /metrics/job/test HTTP/1.1");
+    assert!(body.contains("push_test_fills 3"), "{body}");
+}
+
+#[test]
+fn unset_url_pushes_nothing() {
+    let config = MetricsPushConfig {
+        url: None,
+        interval_ms: None,
+    };
+    assert!(!config.is_enabled());
+    assert!(config.install().unwrap().is_none());
+}
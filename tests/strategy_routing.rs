@@ -0,0 +1,126 @@
+//! Routing in `StrategyRouter`: each Order is assigned to at most one strategy, the
+//! highest-priority strategy accepting it, with ties broken by the order strategies were added.
+
+mod common;
+
+use alloy::{signers::local::PrivateKeySigner, transports::mock::Asserter};
+use common::{constants, mocked_filler, ru_to_ru_order, sign_order, signer};
+use orders::{
+    filler::{AnyOrder, OrderFilter, Strategy, StrategyRouter},
+    ids::OrderHash,
+};
+use signet_types::SignedOrder;
+use std::collections::HashSet;
+
+/// Accepts the Orders with the given hashes.
+#[derive(Debug)]
+struct Only(HashSet<OrderHash>);
+
+impl Only {
+    fn orders<'a>(orders: impl IntoIterator<Item = &'a SignedOrder>) -> Self {
+        Self(orders.into_iter().map(OrderHash::from).collect())
+    }
+}
+
+impl OrderFilter for Only {
+    fn accepts(&self, order: &SignedOrder) -> bool {
+        self.0.contains(&OrderHash::from(order))
+    }
+}
+
+/// A strategy filling the Orders accepted by `filter` with a Filler whose RPCs are never used.
+fn strategy(
+    name: &'static str,
+    priority: u32,
+    filter: impl OrderFilter + 'static,
+) -> Strategy<PrivateKeySigner> {
+    Strategy::new(
+        name,
+        priority,
+        filter,
+        mocked_filler(Asserter::new(), Asserter::new()),
+    )
+}
+
+/// Three distinct fixture Orders.
+async fn orders() -> Vec<SignedOrder> {
+    let constants = constants();
+    let recipient = signer().address();
+    let mut orders = Vec::new();
+    for nonce in 0..3 {
+        orders.push(sign_order(&constants, ru_to_ru_order(&constants, recipient), nonce).await);
+    }
+    orders
+}
+
+#[tokio::test]
+async fn orders_go_to_the_highest_priority_strategy() {
+    let orders = orders().await;
+    let router = StrategyRouter::default()
+        .with_strategy(strategy("low", 1, AnyOrder))
+        .with_strategy(strategy("high", 10, Only::orders(&orders[..1])));
+
+    assert_eq!(
+        router.route(&orders),
+        vec![
+            ("high", vec![orders[0].clone()]),
+            ("low", orders[1..].to_vec()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn equal_priorities_keep_insertion_order() {
+    let orders = orders().await;
+    let router = StrategyRouter::default()
+        .with_strategy(strategy("first", 5, Only::orders(&orders[..2])))
+        .with_strategy(strategy("second", 5, AnyOrder))
+        .with_strategy(strategy("third", 5, AnyOrder));
+
+    assert_eq!(
+        router.route(&orders),
+        vec![
+            ("first", orders[..2].to_vec()),
+            ("second", vec![orders[2].clone()]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn each_order_is_assigned_at_most_once() {
+    let orders = orders().await;
+    let router = StrategyRouter::default()
+        .with_strategy(strategy("a", 3, AnyOrder))
+        .with_strategy(strategy("b", 2, AnyOrder))
+        .with_strategy(strategy("none", 1, Only(HashSet::new())));
+
+    let routed = router.route(&orders);
+    assert_eq!(routed, vec![("a", orders.clone())]);
+
+    let assigned: Vec<OrderHash> = routed
+        .iter()
+        .flat_map(|(_, orders)| orders.iter().map(OrderHash::from))
+        .collect();
+    let unique: HashSet<OrderHash> = assigned.iter().copied().collect();
+    assert_eq!(assigned.len(), unique.len());
+}
+
+#[tokio::test]
+async fn unaccepted_orders_are_not_assigned() {
+    let orders = orders().await;
+    let router = StrategyRouter::default().with_strategy(strategy(
+        "only-first",
+        1,
+        Only::orders(&orders[..1]),
+    ));
+
+    assert_eq!(
+        router.route(&orders),
+        vec![("only-first", vec![orders[0].clone()])]
+    );
+    assert!(
+        StrategyRouter::<PrivateKeySigner>::default()
+            .route(&orders)
+            .is_empty()
+    );
+}
@@ -0,0 +1,147 @@
+//! Canonical test vectors for Filler implementations in other languages.
+//!
+//! Each vector in `tests/vectors` records, for a representative set of Orders signed with the
+//! fixture key: the signed Orders, the signed aggregate Fills per chain, the Rollup and Host
+//! transactions planned to fill them, and the Bundle JSON submitted to the transaction cache.
+//! Transactions in the Bundle are signed with fixed nonces and fees, so every field is
+//! deterministic. See `tests/vectors/README.md` for the format.
+
+mod common;
+
+use alloy::{
+    eips::Encodable2718,
+    network::{EthereumWallet, TransactionBuilder},
+    primitives::{Address, Bytes},
+    rpc::types::{TransactionRequest, mev::EthSendBundle},
+};
+use common::{
+    DEADLINE, assert_vector, constants, ru_to_host_order, ru_to_ru_order, sign_fills, sign_order,
+    signer,
+};
+use orders::filler::plan::{host_txn_requests, rollup_txn_requests};
+use serde_json::{Value, json};
+use signet_bundle::SignetEthBundle;
+use signet_types::SignedOrder;
+use std::collections::BTreeMap;
+
+/// The Rollup block every vector's Bundle targets.
+const TARGET_BLOCK: u64 = 100;
+
+/// Gas limit of every vector transaction.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Max fee per gas of every vector transaction.
+const MAX_FEE_PER_GAS: u128 = 100_000_000_000;
+
+/// Max priority fee per gas of every vector transaction.
+const MAX_PRIORITY_FEE_PER_GAS: u128 = 2_000_000_000;
+
+/// Render a transaction request as its destination and calldata.
+fn render_request(tx: &TransactionRequest) -> Value {
+    let to = tx.to.and_then(|kind| kind.to().copied());
+    json!({
+        "to": to,
+        "input": tx.input.input().cloned().unwrap_or_default(),
+    })
+}
+
+/// Sign the transactions for `chain_id` with the fixture key and sequential nonces from zero,
+/// and encode them for inclusion in a Bundle.
+async fn sign_txs(chain_id: u64, filler: Address, txs: &[TransactionRequest]) -> Vec<Bytes> {
+    let wallet = EthereumWallet::from(signer());
+    let mut encoded = Vec::with_capacity(txs.len());
+    for (nonce, tx) in txs.iter().enumerate() {
+        let envelope = tx
+            .clone()
+            .with_from(filler)
+            .with_chain_id(chain_id)
+            .with_nonce(nonce as u64)
+            .with_gas_limit(GAS_LIMIT)
+            .with_max_fee_per_gas(MAX_FEE_PER_GAS)
+            .with_max_priority_fee_per_gas(MAX_PRIORITY_FEE_PER_GAS)
+            .build(&wallet)
+            .await
+            .unwrap();
+        encoded.push(Bytes::from(envelope.encoded_2718()));
+    }
+    encoded
+}
+
+/// Build the test vector for filling the Orders.
+async fn vector(description: &str, orders: &[SignedOrder]) -> String {
+    let constants = constants();
+    let filler = signer().address();
+    let signed_fills = sign_fills(&constants, orders, 1).await;
+
+    let ru_txs = rollup_txn_requests(&constants, filler, &signed_fills, orders);
+    let host_txs = host_txn_requests(&constants, &signed_fills);
+
+    let bundle = SignetEthBundle {
+        host_txs: sign_txs(constants.host().chain_id(), filler, &host_txs).await,
+        bundle: EthSendBundle {
+            txs: sign_txs(constants.rollup().chain_id(), filler, &ru_txs).await,
+            block_number: TARGET_BLOCK,
+            ..Default::default()
+        },
+    };
+
+    // sort Fills by chain, so the vector is stable
+    let fills: BTreeMap<u64, _> = signed_fills.into_iter().collect();
+    let vector = json!({
+        "description": description,
+        "constants": "pecorino",
+        "filler": filler,
+        "deadline": DEADLINE,
+        "orders": orders,
+        "fills": fills,
+        "rollupTxs": ru_txs.iter().map(render_request).collect::<Vec<_>>(),
+        "hostTxs": host_txs.iter().map(render_request).collect::<Vec<_>>(),
+        "bundle": bundle,
+    });
+    let mut out = serde_json::to_string_pretty(&vector).unwrap();
+    out.push('\n');
+    out
+}
+
+#[tokio::test]
+async fn ru_to_host_vector() {
+    let constants = constants();
+    let recipient = signer().address();
+    let order = sign_order(&constants, ru_to_host_order(&constants, recipient), 1).await;
+
+    assert_vector(
+        "ru_to_host",
+        &vector("One Order swapping Rollup WETH for Host WETH", &[order]).await,
+    );
+}
+
+#[tokio::test]
+async fn ru_to_ru_vector() {
+    let constants = constants();
+    let recipient = signer().address();
+    let order = sign_order(&constants, ru_to_ru_order(&constants, recipient), 1).await;
+
+    assert_vector(
+        "ru_to_ru",
+        &vector("One Order swapping Rollup WETH for Rollup WETH", &[order]).await,
+    );
+}
+
+#[tokio::test]
+async fn aggregate_vector() {
+    let constants = constants();
+    let recipient = signer().address();
+    let orders = [
+        sign_order(&constants, ru_to_host_order(&constants, recipient), 1).await,
+        sign_order(&constants, ru_to_ru_order(&constants, recipient), 2).await,
+    ];
+
+    assert_vector(
+        "aggregate",
+        &vector(
+            "Two Orders, one to each chain, filled by one aggregate Fill per chain",
+            &orders,
+        )
+        .await,
+    );
+}
@@ -0,0 +1,63 @@
+//! Reuse of signed transaction batches across fills of the same Orders.
+
+use alloy::{
+    primitives::{Address, Bytes},
+    rpc::types::TransactionRequest,
+};
+use orders::filler::{TxEncodingCache, TxEnvelopeKind};
+use std::time::Duration;
+
+/// A batch of one transaction request to `to`.
+fn requests(to: Address) -> Vec<TransactionRequest> {
+    vec![TransactionRequest::default().to(to)]
+}
+
+/// An encoded batch of one transaction.
+fn encoded() -> Vec<Bytes> {
+    vec![Bytes::from_static(&[0x02, 0x01])]
+}
+
+#[test]
+fn identical_batch_is_reused() {
+    let cache = TxEncodingCache::new(Duration::from_secs(60));
+    let key =
+        TxEncodingCache::request_hash(1, TxEnvelopeKind::Eip1559, 7, &requests(Address::ZERO))
+            .unwrap();
+    assert_eq!(cache.get(key), None);
+
+    cache.insert(key, encoded());
+
+    let again =
+        TxEncodingCache::request_hash(1, TxEnvelopeKind::Eip1559, 7, &requests(Address::ZERO))
+            .unwrap();
+    assert_eq!(cache.get(again), Some(encoded()));
+}
+
+#[test]
+fn batch_expires_after_ttl() {
+    let cache = TxEncodingCache::new(Duration::from_millis(10));
+    let key =
+        TxEncodingCache::request_hash(1, TxEnvelopeKind::Eip1559, 7, &requests(Address::ZERO))
+            .unwrap();
+    cache.insert(key, encoded());
+
+    std::thread::sleep(Duration::from_millis(20));
+
+    assert_eq!(cache.get(key), None);
+}
+
+#[test]
+fn batches_differing_in_any_field_have_distinct_keys() {
+    let key = |chain_id, tx_kind, nonce, to| {
+        TxEncodingCache::request_hash(chain_id, tx_kind, nonce, &requests(to)).unwrap()
+    };
+    let base = key(1, TxEnvelopeKind::Eip1559, 7, Address::ZERO);
+
+    assert_ne!(base, key(2, TxEnvelopeKind::Eip1559, 7, Address::ZERO));
+    assert_ne!(base, key(1, TxEnvelopeKind::Legacy, 7, Address::ZERO));
+    assert_ne!(base, key(1, TxEnvelopeKind::Eip1559, 8, Address::ZERO));
+    assert_ne!(
+        base,
+        key(1, TxEnvelopeKind::Eip1559, 7, Address::repeat_byte(1))
+    );
+}